@@ -0,0 +1,315 @@
+//! The in-memory papers and citation edges a [`crate::MockScholar`] serves.
+use std::collections::HashMap;
+
+use semantic_scholar::{Paper, PaperId, SearchFilters};
+
+/// One citation: `citing` cites `cited`, with the edge metadata the citations and
+/// references endpoints report.
+#[derive(Debug, Clone, Default)]
+pub struct CitationEdge {
+    pub citing: String,
+    pub cited: String,
+    pub intents: Vec<String>,
+    pub is_influential: bool,
+    pub contexts: Vec<String>,
+}
+
+impl CitationEdge {
+    pub fn new(citing: impl Into<String>, cited: impl Into<String>) -> Self {
+        Self {
+            citing: citing.into(),
+            cited: cited.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn intents<S: Into<String>>(mut self, intents: impl IntoIterator<Item = S>) -> Self {
+        self.intents = intents.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn influential(mut self) -> Self {
+        self.is_influential = true;
+        self
+    }
+
+    pub fn context(mut self, context: impl Into<String>) -> Self {
+        self.contexts.push(context.into());
+        self
+    }
+}
+
+/// Papers keyed by `paperId`, plus the citation graph between them.
+///
+/// ```
+/// use semantic_scholar::Paper;
+/// use semantic_scholar_mock::Corpus;
+///
+/// let corpus = Corpus::new()
+///     .paper(Paper { paper_id: Some("a".into()), title: Some("TF-CBT RCT".into()), ..Default::default() })
+///     .paper(Paper { paper_id: Some("b".into()), title: Some("TF-CBT follow-up".into()), ..Default::default() })
+///     .cites("b", "a");
+/// assert_eq!(corpus.citations_of("a").len(), 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Corpus {
+    papers: Vec<Paper>,
+    edges: Vec<CitationEdge>,
+}
+
+impl Corpus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a paper.
+    ///
+    /// # Panics
+    /// If `paper.paper_id` is `None`.
+    pub fn paper(mut self, paper: Paper) -> Self {
+        assert!(paper.paper_id.is_some(), "corpus papers need a paper_id");
+        self.papers.push(paper);
+        self
+    }
+
+    pub fn papers(self, papers: impl IntoIterator<Item = Paper>) -> Self {
+        papers.into_iter().fold(self, Self::paper)
+    }
+
+    /// Record that `citing` cites `cited`, with no intents or contexts.
+    pub fn cites(self, citing: &str, cited: &str) -> Self {
+        self.edge(CitationEdge::new(citing, cited))
+    }
+
+    pub fn edge(mut self, edge: CitationEdge) -> Self {
+        self.edges.push(edge);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.papers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.papers.is_empty()
+    }
+
+    /// Resolve any ID form the API accepts (`DOI:…`, `PMID:…`, bare S2 ID, …).
+    /// Like the real API, an unprefixed ID that isn't otherwise recognised is
+    /// looked up as an S2 ID, so short corpus IDs such as `p1` resolve.
+    pub fn get(&self, id: &str) -> Option<&Paper> {
+        let id: PaperId = id.parse().unwrap_or_else(|_| PaperId::S2(id.into()));
+        self.papers.iter().find(|p| matches_id(p, &id))
+    }
+
+    /// Papers matching a bulk-search `query` and `filters`, ordered by `sort`
+    /// (`field:asc|desc`, default `paperId:asc`). Venue and date filters are ignored.
+    pub fn search(&self, query: &str, filters: &SearchFilters, sort: Option<&str>) -> Vec<&Paper> {
+        let mut hits: Vec<&Paper> = self
+            .papers
+            .iter()
+            .filter(|p| matches_query(p, query))
+            .filter(|p| matches_filters(p, filters))
+            .collect();
+        sort_papers(&mut hits, sort.unwrap_or("paperId:asc"));
+        hits
+    }
+
+    /// Edges into `id` paired with the citing paper, in insertion order.
+    pub fn citations_of(&self, id: &str) -> Vec<(&CitationEdge, &Paper)> {
+        let Some(target) = self.get(id).and_then(|p| p.paper_id.as_deref()) else {
+            return Vec::new();
+        };
+        self.edges
+            .iter()
+            .filter(|e| e.cited == target)
+            .filter_map(|e| Some((e, self.by_s2_id(&e.citing)?)))
+            .collect()
+    }
+
+    /// Edges out of `id` paired with the cited paper, in insertion order.
+    pub fn references_of(&self, id: &str) -> Vec<(&CitationEdge, &Paper)> {
+        let Some(source) = self.get(id).and_then(|p| p.paper_id.as_deref()) else {
+            return Vec::new();
+        };
+        self.edges
+            .iter()
+            .filter(|e| e.citing == source)
+            .filter_map(|e| Some((e, self.by_s2_id(&e.cited)?)))
+            .collect()
+    }
+
+    /// Papers related to the positive seeds and not to the negative ones.
+    ///
+    /// A citation edge to a seed scores 2 and a shared field of study scores 1;
+    /// negative seeds subtract. Seeds themselves are never recommended. Ties are
+    /// broken by citation count.
+    pub fn recommend(&self, positive: &[String], negative: &[String]) -> Vec<&Paper> {
+        let resolve =
+            |ids: &[String]| -> Vec<&Paper> { ids.iter().filter_map(|id| self.get(id)).collect() };
+        let (pos, neg) = (resolve(positive), resolve(negative));
+        let seeds: Vec<&str> = pos
+            .iter()
+            .chain(&neg)
+            .filter_map(|p| p.paper_id.as_deref())
+            .collect();
+
+        let mut scores: HashMap<&str, i64> = HashMap::new();
+        for candidate in &self.papers {
+            let Some(cid) = candidate.paper_id.as_deref() else {
+                continue;
+            };
+            if seeds.contains(&cid) {
+                continue;
+            }
+            let score = pos
+                .iter()
+                .map(|s| self.relatedness(s, candidate))
+                .sum::<i64>()
+                - neg
+                    .iter()
+                    .map(|s| self.relatedness(s, candidate))
+                    .sum::<i64>();
+            if score > 0 {
+                scores.insert(cid, score);
+            }
+        }
+        let mut recs: Vec<&Paper> = self
+            .papers
+            .iter()
+            .filter(|p| {
+                p.paper_id
+                    .as_deref()
+                    .is_some_and(|id| scores.contains_key(id))
+            })
+            .collect();
+        recs.sort_by_key(|p| {
+            let id = p.paper_id.as_deref().unwrap_or_default();
+            (
+                std::cmp::Reverse(scores[id]),
+                std::cmp::Reverse(p.citation_count.unwrap_or(0)),
+            )
+        });
+        recs
+    }
+
+    fn by_s2_id(&self, id: &str) -> Option<&Paper> {
+        self.papers
+            .iter()
+            .find(|p| p.paper_id.as_deref() == Some(id))
+    }
+
+    fn relatedness(&self, seed: &Paper, candidate: &Paper) -> i64 {
+        let (a, b) = (seed.paper_id.as_deref(), candidate.paper_id.as_deref());
+        let linked = self.edges.iter().any(|e| {
+            (Some(e.citing.as_str()) == a && Some(e.cited.as_str()) == b)
+                || (Some(e.citing.as_str()) == b && Some(e.cited.as_str()) == a)
+        });
+        let shared_field = match (&seed.fields_of_study, &candidate.fields_of_study) {
+            (Some(x), Some(y)) => x.iter().any(|f| y.contains(f)),
+            _ => false,
+        };
+        2 * i64::from(linked) + i64::from(shared_field)
+    }
+}
+
+fn matches_id(paper: &Paper, id: &PaperId) -> bool {
+    let ext = paper.external_ids.as_ref();
+    let ext_eq = |get: fn(&semantic_scholar::ExternalIds) -> Option<&String>, want: &str| {
+        ext.and_then(get)
+            .is_some_and(|v| v.eq_ignore_ascii_case(want))
+    };
+    match id {
+        PaperId::S2(s2) => paper.paper_id.as_deref() == Some(s2.as_str()),
+        PaperId::CorpusId(n) => ext.and_then(|x| x.corpus_id) == Some(*n),
+        PaperId::Doi(doi) => ext_eq(|x| x.doi.as_ref(), doi),
+        PaperId::ArXiv(a) => ext_eq(|x| x.arxiv.as_ref(), a),
+        PaperId::Pmid(pmid) => ext_eq(|x| x.pubmed.as_ref(), pmid),
+        PaperId::Pmcid(pmc) => ext_eq(|x| x.pubmed_central.as_ref(), pmc),
+        PaperId::Acl(acl) => ext_eq(|x| x.acl.as_ref(), acl),
+        PaperId::Mag(mag) => ext_eq(|x| x.mag.as_ref(), mag),
+        PaperId::Url(url) => paper.url.as_deref() == Some(url.as_str()),
+    }
+}
+
+/// A simplified reading of the bulk query syntax: `|` separates alternatives, each
+/// alternative needs all its terms, `-term` excludes, quotes/`+`/parentheses are
+/// ignored and a trailing `*` matches any suffix. Matching is a case-insensitive
+/// substring test against title and abstract.
+fn matches_query(paper: &Paper, query: &str) -> bool {
+    let text = format!(
+        "{} {}",
+        paper.title.as_deref().unwrap_or_default(),
+        paper.abstract_text.as_deref().unwrap_or_default()
+    )
+    .to_lowercase();
+    let cleaned: String = query
+        .chars()
+        .map(|c| {
+            if matches!(c, '"' | '+' | '(' | ')') {
+                ' '
+            } else {
+                c
+            }
+        })
+        .collect::<String>()
+        .to_lowercase();
+
+    cleaned.split('|').any(|alternative| {
+        let mut any_term = false;
+        let ok = alternative.split_whitespace().all(|term| {
+            any_term = true;
+            match term.strip_prefix('-') {
+                Some(excluded) => !text.contains(excluded.trim_end_matches('*')),
+                None => text.contains(term.trim_end_matches('*')),
+            }
+        });
+        ok && (any_term || query.trim().is_empty())
+    })
+}
+
+/// `"2019"`, `"2015-"`, `"-2020"` or `"2015-2020"`.
+fn matches_filters(paper: &Paper, filters: &SearchFilters) -> bool {
+    let min = filters.min_citations.map_or(0, u64::from);
+    filters.year.as_deref().map_or(true, |y| matches_year(paper.year, y))
+        && paper.citation_count.unwrap_or(0) >= min
+        && (filters.publication_types.is_empty()
+            || filters
+                .publication_types
+                .iter()
+                .any(|t| paper.has_publication_type(t.as_str())))
+        && (filters.fields_of_study.is_empty()
+            || filters.fields_of_study.iter().any(|f| {
+                paper
+                    .fields_of_study
+                    .iter()
+                    .flatten()
+                    .any(|name| name == f.as_str())
+            }))
+        && (!filters.open_access_pdf || paper.open_access_pdf.is_some())
+}
+
+fn matches_year(year: Option<u32>, filter: &str) -> bool {
+    let Some(year) = year else {
+        return false;
+    };
+    let parse = |s: &str| s.trim().parse::<u32>().ok();
+    match filter.split_once('-') {
+        Some((from, to)) => {
+            parse(from).map_or(true, |f| year >= f) && parse(to).map_or(true, |t| year <= t)
+        }
+        None => parse(filter) == Some(year),
+    }
+}
+
+fn sort_papers(papers: &mut [&Paper], sort: &str) {
+    let (field, order) = sort.split_once(':').unwrap_or((sort, "asc"));
+    match field {
+        "citationCount" => papers.sort_by_key(|p| p.citation_count.unwrap_or(0)),
+        "publicationDate" => papers.sort_by(|a, b| a.publication_date.cmp(&b.publication_date)),
+        _ => papers.sort_by(|a, b| a.paper_id.cmp(&b.paper_id)),
+    }
+    if order == "desc" {
+        papers.reverse();
+    }
+}
@@ -0,0 +1,544 @@
+//! In-process mock of the Semantic Scholar API.
+//!
+//! [`MockScholar`] serves ranked and bulk search, single-paper lookup, citations, references and
+//! recommendations from an in-memory [`Corpus`] on an ephemeral localhost port, so
+//! code built on [`SemanticScholarClient`] — up to full agent runs — can be tested
+//! without network access. Failures (429s, 5xx, anything else) can be queued to
+//! exercise retry and error paths.
+//!
+//! ```
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use semantic_scholar::{fields::Endpoint, FieldSet, Paper, PaperId};
+//! use semantic_scholar_mock::{Corpus, Failure, MockScholar};
+//!
+//! let corpus = Corpus::new().paper(Paper {
+//!     paper_id: Some("p1".into()),
+//!     title: Some("Exposure therapy for childhood anxiety".into()),
+//!     ..Default::default()
+//! });
+//! let mock = MockScholar::start(corpus).await;
+//! mock.fail_next(Failure::rate_limited());
+//!
+//! // The first attempt gets a 429, the retry succeeds.
+//! let fields = FieldSet::parse(Endpoint::PaperDetail, "title").unwrap();
+//! let paper = mock.client().get_paper(&PaperId::S2("p1".into()), &fields).await.unwrap();
+//! assert_eq!(paper.title.as_deref(), Some("Exposure therapy for childhood anxiety"));
+//! assert_eq!(mock.requests().len(), 2);
+//! # }
+//! ```
+mod corpus;
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use semantic_scholar::{Paper, RetryPolicy, SearchFilters, SemanticScholarClient};
+use serde_json::{json, Map, Value};
+use tokio::{net::TcpListener, task::JoinHandle};
+
+pub use corpus::{CitationEdge, Corpus};
+
+/// Largest page the mock returns, matching the real paginated endpoints.
+const MAX_PAGE: usize = 1000;
+
+/// The endpoints the mock serves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Route {
+    /// `GET /graph/v1/paper/search`
+    Search,
+    /// `GET /graph/v1/paper/search/bulk`
+    BulkSearch,
+    /// `GET /graph/v1/paper/{id}`
+    Paper,
+    /// `GET /graph/v1/paper/{id}/citations`
+    Citations,
+    /// `GET /graph/v1/paper/{id}/references`
+    References,
+    /// `GET /recommendations/v1/papers/forpaper/{id}` and `POST /recommendations/v1/papers/`
+    Recommendations,
+}
+
+/// A scripted failure served instead of the normal response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    /// 429 with an optional `Retry-After` in seconds.
+    RateLimited { retry_after: Option<u64> },
+    /// Any other status with a `{"error": message}` body.
+    Status { status: u16, message: String },
+}
+
+impl Failure {
+    /// 429 with `Retry-After: 0`, so a retrying client tries again immediately.
+    pub fn rate_limited() -> Self {
+        Self::RateLimited {
+            retry_after: Some(0),
+        }
+    }
+
+    pub fn status(status: u16, message: impl Into<String>) -> Self {
+        Self::Status {
+            status,
+            message: message.into(),
+        }
+    }
+
+    fn into_response(self) -> Response {
+        match self {
+            Self::RateLimited { retry_after } => {
+                let mut resp = (
+                    StatusCode::TOO_MANY_REQUESTS,
+                    Json(json!({ "message": "Too Many Requests" })),
+                )
+                    .into_response();
+                if let Some(secs) = retry_after {
+                    resp.headers_mut()
+                        .insert("retry-after", HeaderValue::from(secs));
+                }
+                resp
+            }
+            Self::Status { status, message } => (
+                StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+                Json(json!({ "error": message })),
+            )
+                .into_response(),
+        }
+    }
+}
+
+/// A request the mock received.
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub route: Route,
+    /// The paper ID path segment, percent-decoded, if the route has one.
+    pub paper_id: Option<String>,
+    pub query: HashMap<String, String>,
+    pub body: Option<Value>,
+    /// The status the mock answered with.
+    pub status: u16,
+}
+
+#[derive(Default)]
+struct Inner {
+    corpus: Corpus,
+    failures: VecDeque<(Option<Route>, Failure)>,
+    requests: Vec<RecordedRequest>,
+}
+
+type Shared = Arc<Mutex<Inner>>;
+
+/// A running mock server, see the [crate docs](crate). The server stops when this
+/// is dropped.
+pub struct MockScholar {
+    base_url: String,
+    state: Shared,
+    task: JoinHandle<()>,
+}
+
+impl MockScholar {
+    /// Bind an ephemeral localhost port and start serving `corpus`.
+    pub async fn start(corpus: Corpus) -> Self {
+        let state: Shared = Arc::new(Mutex::new(Inner {
+            corpus,
+            ..Inner::default()
+        }));
+        let app = Router::new()
+            .route("/graph/v1/paper/search", get(search))
+            .route("/graph/v1/paper/search/bulk", get(bulk_search))
+            .route("/graph/v1/paper/:id", get(paper))
+            .route("/graph/v1/paper/:id/citations", get(citations))
+            .route("/graph/v1/paper/:id/references", get(references))
+            .route(
+                "/recommendations/v1/papers/forpaper/:id",
+                get(recommend_for_paper),
+            )
+            .route("/recommendations/v1/papers/", post(recommend_multi))
+            .with_state(state.clone());
+
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind mock Semantic Scholar server");
+        let addr = listener.local_addr().expect("mock server address");
+        let task = tokio::spawn(async move {
+            axum::serve(listener, app)
+                .await
+                .expect("mock server failed");
+        });
+        Self {
+            base_url: format!("http://{addr}"),
+            state,
+            task,
+        }
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// A client pointed at this mock, retrying with millisecond backoffs.
+    pub fn client(&self) -> SemanticScholarClient {
+        SemanticScholarClient::builder()
+            .base_url(&self.base_url)
+            .retry_policy(RetryPolicy {
+                max_retries: 3,
+                initial_backoff: Duration::from_millis(1),
+                max_backoff: Duration::from_millis(10),
+                multiplier: 2,
+                jitter: 0.0,
+                retry_transient: true,
+            })
+            .build()
+            .expect("build mock client")
+    }
+
+    /// Serve `failure` for the next request to any route.
+    pub fn fail_next(&self, failure: Failure) {
+        self.lock().failures.push_back((None, failure));
+    }
+
+    /// Serve `failure` for the next request to `route`; other routes are unaffected.
+    pub fn fail_next_on(&self, route: Route, failure: Failure) {
+        self.lock().failures.push_back((Some(route), failure));
+    }
+
+    /// Every request received so far, oldest first.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.lock().requests.clone()
+    }
+
+    /// Number of requests received for `route`.
+    pub fn request_count(&self, route: Route) -> usize {
+        self.lock()
+            .requests
+            .iter()
+            .filter(|r| r.route == route)
+            .count()
+    }
+
+    /// Replace the corpus while the server keeps running.
+    pub fn set_corpus(&self, corpus: Corpus) {
+        self.lock().corpus = corpus;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.state.lock().unwrap()
+    }
+}
+
+impl Drop for MockScholar {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+// ─── Handlers ────────────────────────────────────────────────────────────────
+
+type Params = HashMap<String, String>;
+
+/// Record the request, then either serve a queued failure or run `respond`.
+fn handle(
+    state: &Shared,
+    route: Route,
+    paper_id: Option<String>,
+    query: Params,
+    body: Option<Value>,
+    respond: impl FnOnce(&Corpus, &Params, Option<&Value>) -> Result<Value, Failure>,
+) -> Response {
+    let mut inner = state.lock().unwrap();
+    let queued = inner
+        .failures
+        .iter()
+        .position(|(r, _)| r.map_or(true, |r| r == route))
+        .and_then(|i| inner.failures.remove(i))
+        .map(|(_, f)| f);
+    let result = match queued {
+        Some(failure) => Err(failure),
+        None => respond(&inner.corpus, &query, body.as_ref()),
+    };
+    let response = match result {
+        Ok(value) => Json(value).into_response(),
+        Err(failure) => failure.into_response(),
+    };
+    inner.requests.push(RecordedRequest {
+        route,
+        paper_id,
+        query,
+        body,
+        status: response.status().as_u16(),
+    });
+    response
+}
+
+/// Relevance is approximated by citation count; only the first 1000 hits are reachable.
+async fn search(State(state): State<Shared>, Query(query): Query<Params>) -> Response {
+    handle(&state, Route::Search, None, query, None, |corpus, q, _| {
+        let hits = corpus.search(
+            q.get("query").map(String::as_str).unwrap_or_default(),
+            &search_filters(q)?,
+            Some("citationCount:desc"),
+        );
+        let offset: usize = q.get("offset").and_then(|o| o.parse().ok()).unwrap_or(0);
+        let limit = q.get("limit").and_then(|l| l.parse().ok()).unwrap_or(100);
+        if limit > 100 || offset + limit > MAX_PAGE {
+            return Err(Failure::status(
+                400,
+                "Requested data for this limit and/or offset is not available",
+            ));
+        }
+        let end = (offset + limit).min(hits.len());
+        let fields = requested_fields(q);
+        Ok(json!({
+            "total": hits.len(),
+            "offset": offset,
+            "next": (end < hits.len().min(MAX_PAGE)).then_some(end),
+            "data": hits[offset.min(end)..end].iter().map(|p| project(p, &fields)).collect::<Vec<_>>(),
+        }))
+    })
+}
+
+async fn bulk_search(State(state): State<Shared>, Query(query): Query<Params>) -> Response {
+    handle(
+        &state,
+        Route::BulkSearch,
+        None,
+        query,
+        None,
+        |corpus, q, _| {
+            let hits = corpus.search(
+                q.get("query").map(String::as_str).unwrap_or_default(),
+                &search_filters(q)?,
+                q.get("sort").map(String::as_str),
+            );
+            let start: usize = q.get("token").and_then(|t| t.parse().ok()).unwrap_or(0);
+            let end = (start + page_size(q, MAX_PAGE)).min(hits.len());
+            let fields = requested_fields(q);
+            Ok(json!({
+                "total": hits.len(),
+                "token": (end < hits.len()).then(|| end.to_string()),
+                "data": hits[start.min(end)..end].iter().map(|p| project(p, &fields)).collect::<Vec<_>>(),
+            }))
+        },
+    )
+}
+
+async fn paper(
+    State(state): State<Shared>,
+    Path(id): Path<String>,
+    Query(query): Query<Params>,
+) -> Response {
+    handle(
+        &state,
+        Route::Paper,
+        Some(id.clone()),
+        query,
+        None,
+        |corpus, q, _| {
+            let paper = corpus.get(&id).ok_or_else(|| not_found(&id))?;
+            Ok(project(paper, &requested_fields(q)))
+        },
+    )
+}
+
+async fn citations(
+    State(state): State<Shared>,
+    Path(id): Path<String>,
+    Query(query): Query<Params>,
+) -> Response {
+    handle(
+        &state,
+        Route::Citations,
+        Some(id.clone()),
+        query,
+        None,
+        |corpus, q, _| {
+            corpus.get(&id).ok_or_else(|| not_found(&id))?;
+            Ok(edge_page(corpus.citations_of(&id), "citingPaper", q))
+        },
+    )
+}
+
+async fn references(
+    State(state): State<Shared>,
+    Path(id): Path<String>,
+    Query(query): Query<Params>,
+) -> Response {
+    handle(
+        &state,
+        Route::References,
+        Some(id.clone()),
+        query,
+        None,
+        |corpus, q, _| {
+            corpus.get(&id).ok_or_else(|| not_found(&id))?;
+            Ok(edge_page(corpus.references_of(&id), "citedPaper", q))
+        },
+    )
+}
+
+async fn recommend_for_paper(
+    State(state): State<Shared>,
+    Path(id): Path<String>,
+    Query(query): Query<Params>,
+) -> Response {
+    handle(
+        &state,
+        Route::Recommendations,
+        Some(id.clone()),
+        query,
+        None,
+        |corpus, q, _| {
+            corpus.get(&id).ok_or_else(|| not_found(&id))?;
+            Ok(recommendations(corpus.recommend(std::slice::from_ref(&id), &[]), q))
+        },
+    )
+}
+
+async fn recommend_multi(
+    State(state): State<Shared>,
+    Query(query): Query<Params>,
+    Json(body): Json<Value>,
+) -> Response {
+    handle(
+        &state,
+        Route::Recommendations,
+        None,
+        query,
+        Some(body),
+        |corpus, q, body| {
+            let ids = |key: &str| -> Vec<String> {
+                body.and_then(|b| b[key].as_array())
+                    .map(|a| {
+                        a.iter()
+                            .filter_map(|v| v.as_str().map(String::from))
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            };
+            let positive = ids("positivePaperIds");
+            if positive.is_empty() {
+                return Err(Failure::status(400, "positivePaperIds must not be empty"));
+            }
+            Ok(recommendations(
+                corpus.recommend(&positive, &ids("negativePaperIds")),
+                q,
+            ))
+        },
+    )
+}
+
+// ─── Response shaping ────────────────────────────────────────────────────────
+
+fn not_found(id: &str) -> Failure {
+    Failure::status(404, format!("Paper with id {id} not found"))
+}
+
+/// Search filters from query parameters; unknown filter values are a 400, as upstream.
+fn search_filters(q: &Params) -> Result<SearchFilters, Failure> {
+    fn list<T: std::str::FromStr>(q: &Params, key: &str) -> Result<Vec<T>, Failure> {
+        q.get(key)
+            .map(|v| v.split(',').map(str::parse).collect::<Result<_, _>>())
+            .transpose()
+            .map(Option::unwrap_or_default)
+            .map_err(|_| Failure::status(400, format!("Unrecognized {key} value")))
+    }
+    Ok(SearchFilters {
+        year: q.get("year").cloned(),
+        publication_date_or_year: q.get("publicationDateOrYear").cloned(),
+        min_citations: q.get("minCitationCount").and_then(|m| m.parse().ok()),
+        publication_types: list(q, "publicationTypes")?,
+        fields_of_study: list(q, "fieldsOfStudy")?,
+        venues: list(q, "venue")?,
+        open_access_pdf: q.contains_key("openAccessPdf"),
+    })
+}
+
+fn page_size(q: &Params, max: usize) -> usize {
+    q.get("limit")
+        .and_then(|l| l.parse().ok())
+        .unwrap_or(max)
+        .min(max)
+}
+
+/// Top-level names from `fields=`, e.g. `authors.name` selects `authors`. Empty
+/// means the endpoint's defaults (here: `paperId` and `title`).
+fn requested_fields(q: &Params) -> Vec<String> {
+    q.get("fields")
+        .map(|f| {
+            f.split(',')
+                .map(|f| f.split('.').next().unwrap_or_default().trim().to_string())
+                .filter(|f| !f.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Serialize `paper` keeping only `paperId` and the requested fields.
+fn project(paper: &Paper, fields: &[String]) -> Value {
+    let Value::Object(all) = serde_json::to_value(paper).expect("Paper serializes") else {
+        unreachable!("Paper serializes to an object");
+    };
+    let keep = |k: &str| {
+        k == "paperId"
+            || if fields.is_empty() {
+                k == "title"
+            } else {
+                fields.iter().any(|f| f == k)
+            }
+    };
+    Value::Object(
+        all.into_iter()
+            .filter(|(k, _)| keep(k))
+            .collect::<Map<_, _>>(),
+    )
+}
+
+fn edge_page(edges: Vec<(&CitationEdge, &Paper)>, key: &str, q: &Params) -> Value {
+    let offset: usize = q.get("offset").and_then(|o| o.parse().ok()).unwrap_or(0);
+    let end = (offset + page_size(q, 100)).min(edges.len());
+    let fields = requested_fields(q);
+    let data: Vec<Value> = edges[offset.min(end)..end]
+        .iter()
+        .map(|(edge, paper)| {
+            // Edge attributes are only served when asked for, like upstream.
+            let mut item = Map::new();
+            item.insert(key.to_string(), project(paper, &fields));
+            let wanted = |name: &str| fields.iter().any(|f| f == name);
+            if wanted("intents") {
+                item.insert("intents".into(), json!(edge.intents));
+            }
+            if wanted("isInfluential") {
+                item.insert("isInfluential".into(), json!(edge.is_influential));
+            }
+            if wanted("contexts") {
+                item.insert("contexts".into(), json!(edge.contexts));
+            }
+            Value::Object(item)
+        })
+        .collect();
+    json!({
+        "offset": offset,
+        "next": (end < edges.len()).then_some(end),
+        "data": data,
+    })
+}
+
+fn recommendations(papers: Vec<&Paper>, q: &Params) -> Value {
+    let fields = requested_fields(q);
+    let limit = q
+        .get("limit")
+        .and_then(|l| l.parse().ok())
+        .unwrap_or(100)
+        .min(500);
+    json!({
+        "recommendedPapers": papers.iter().take(limit).map(|p| project(p, &fields)).collect::<Vec<_>>(),
+    })
+}
@@ -0,0 +1,363 @@
+/// Drives `SemanticScholarClient` against `MockScholar` for every served endpoint.
+use futures::TryStreamExt;
+use semantic_scholar::{
+    fields::{Endpoint, FieldSet},
+    CitationIntent, Error, ExternalIds, Paper, PaperId, PublicationType, SearchFilters,
+    SearchResponse,
+};
+use semantic_scholar_mock::{CitationEdge, Corpus, Failure, MockScholar, Route};
+
+fn paper(id: &str, title: &str, year: u32, citations: u64) -> Paper {
+    Paper {
+        paper_id: Some(id.into()),
+        title: Some(title.into()),
+        year: Some(year),
+        citation_count: Some(citations),
+        fields_of_study: Some(vec!["Psychology".into()]),
+        ..Default::default()
+    }
+}
+
+fn corpus() -> Corpus {
+    let mut rct = paper("rct", "TF-CBT randomized trial in children", 2012, 300);
+    rct.external_ids = Some(ExternalIds {
+        doi: Some("10.1016/j.jaac.2011.12.001".into()),
+        pubmed: Some("22265362".into()),
+        ..Default::default()
+    });
+    rct.abstract_text = Some("Trauma-focused cognitive behavioral therapy for PTSD.".into());
+    rct.publication_types = Some(vec!["JournalArticle".into(), "ClinicalTrial".into()]);
+    let mut meta = paper(
+        "meta",
+        "Meta-analysis of trauma treatments for children",
+        2019,
+        120,
+    );
+    meta.publication_types = Some(vec!["Review".into(), "MetaAnalysis".into()]);
+    Corpus::new()
+        .paper(rct)
+        .paper(meta)
+        .paper(paper("pcit", "Parent-child interaction therapy", 2017, 80))
+        .paper(paper(
+            "adult",
+            "Prolonged exposure for adult PTSD",
+            2010,
+            500,
+        ))
+        .edge(
+            CitationEdge::new("meta", "rct")
+                .intents(["result"])
+                .influential()
+                .context("TF-CBT outperformed usual care"),
+        )
+        .cites("pcit", "rct")
+        .cites("meta", "adult")
+}
+
+/// Mock IDs such as `p1` aren't S2-shaped, so fall back to wrapping them directly.
+fn id(s: &str) -> PaperId {
+    s.parse().unwrap_or_else(|_| PaperId::S2(s.into()))
+}
+
+/// `fields=` selection for `endpoint`, e.g. `fields_for(Endpoint::PaperDetail, "title")`.
+fn fields_for(endpoint: Endpoint, fields: &str) -> FieldSet {
+    FieldSet::parse(endpoint, fields).unwrap()
+}
+
+#[tokio::test]
+async fn bulk_search_filters_sorts_and_pages() {
+    let mock = MockScholar::start(corpus()).await;
+    let client = mock.client();
+
+    let resp = client
+        .search_bulk(
+            "children | PTSD",
+            &fields_for(Endpoint::BulkSearch, "title,year"),
+            &SearchFilters::new().year("2011-").min_citations(100),
+            Some("citationCount:desc"),
+            10,
+        )
+        .await
+        .unwrap();
+    let ids: Vec<_> = resp
+        .data
+        .iter()
+        .filter_map(|p| p.paper_id.as_deref())
+        .collect();
+    assert_eq!(ids, ["rct", "meta"]);
+    assert_eq!(resp.total, Some(2));
+    assert!(
+        resp.data[0].citation_count.is_none(),
+        "unrequested field served"
+    );
+
+    let all: Vec<Paper> = client
+        .search_bulk_stream(
+            "",
+            &fields_for(Endpoint::BulkSearch, "title"),
+            &SearchFilters::new(),
+            None,
+            10,
+        )
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(all.len(), 4);
+
+    let filters = SearchFilters::new()
+        .publication_type(PublicationType::MetaAnalysis)
+        .publication_type(PublicationType::ClinicalTrial);
+    let trials = client
+        .search_bulk(
+            "",
+            &fields_for(Endpoint::BulkSearch, "title"),
+            &filters,
+            None,
+            10,
+        )
+        .await
+        .unwrap();
+    assert_eq!(trials.total, Some(2));
+    let reviews = client
+        .search_bulk(
+            "",
+            &fields_for(Endpoint::BulkSearch, "title"),
+            &SearchFilters::new().publication_type(PublicationType::Review),
+            None,
+            10,
+        )
+        .await
+        .unwrap();
+    assert_eq!(reviews.data[0].paper_id.as_deref(), Some("meta"));
+}
+
+#[tokio::test]
+async fn ranked_search_filters_and_pages() {
+    let mock = MockScholar::start(corpus()).await;
+    let client = mock.client();
+
+    let resp = client
+        .search(
+            "children",
+            &fields_for(Endpoint::Search, "title"),
+            &SearchFilters::new().year("2015-").min_citations(100),
+            10,
+            0,
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.total, Some(1));
+    assert_eq!(resp.data[0].paper_id.as_deref(), Some("meta"));
+    let q = &mock.requests()[0].query;
+    assert_eq!(q["year"], "2015-");
+    assert_eq!(q["minCitationCount"], "100");
+
+    let mut big = Corpus::new();
+    for i in 0..1200 {
+        big = big.paper(paper(&format!("p{i:04}"), "CBT trial", 2020, i));
+    }
+    mock.set_corpus(big);
+    let papers: Vec<Paper> = client
+        .search_stream(
+            "cbt",
+            &fields_for(Endpoint::Search, "title"),
+            &SearchFilters::new(),
+            5000,
+        )
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(
+        papers.len(),
+        1000,
+        "ranked search stops at its result ceiling"
+    );
+    let searches: Vec<_> = mock
+        .requests()
+        .into_iter()
+        .filter(|r| r.route == Route::Search)
+        .skip(1)
+        .collect();
+    assert_eq!(searches.len(), 10);
+    assert!(searches.iter().all(|r| r.query["limit"] == "100"));
+    assert_eq!(searches[9].query["offset"], "900");
+
+    let err = client
+        .search(
+            "cbt",
+            &fields_for(Endpoint::Search, "title"),
+            &SearchFilters::new(),
+            100,
+            950,
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::BadRequest { .. }), "{err}");
+
+    let pages: Vec<SearchResponse> = client
+        .search_pages(
+            "cbt",
+            &fields_for(Endpoint::Search, "title"),
+            &SearchFilters::new(),
+            250,
+        )
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(
+        pages.iter().map(|p| p.data.len()).collect::<Vec<_>>(),
+        [100, 100, 50]
+    );
+    assert_eq!(pages[0].total, Some(1200));
+}
+
+#[tokio::test]
+async fn paper_resolves_external_ids() {
+    let mock = MockScholar::start(corpus()).await;
+    let client = mock.client();
+    for form in [
+        "rct",
+        "https://doi.org/10.1016/j.jaac.2011.12.001",
+        "PMID:22265362",
+    ] {
+        let p = client
+            .get_paper(&id(form), &fields_for(Endpoint::PaperDetail, "title"))
+            .await
+            .unwrap();
+        assert_eq!(p.paper_id.as_deref(), Some("rct"), "{form}");
+    }
+    let err = client
+        .get_paper(&id("missing"), &fields_for(Endpoint::PaperDetail, "title"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::NotFound { .. }), "unexpected: {err}");
+}
+
+#[tokio::test]
+async fn citations_and_references_carry_edge_metadata() {
+    let mock = MockScholar::start(corpus()).await;
+    let client = mock.client();
+
+    let fields = fields_for(Endpoint::Citations, "title")
+        .with_edge_fields()
+        .unwrap();
+    let cites = client
+        .get_citations(&id("rct"), &fields, 1, 0)
+        .await
+        .unwrap();
+    assert_eq!(cites.next, Some(1));
+    let first = &cites.data[0];
+    assert_eq!(
+        first.citing_paper.as_ref().unwrap().paper_id.as_deref(),
+        Some("meta")
+    );
+    assert_eq!(first.is_influential, Some(true));
+    assert_eq!(
+        first.intents.as_deref(),
+        Some(&[CitationIntent::Result][..])
+    );
+    assert!(first.has_intent(&CitationIntent::Result));
+    assert_eq!(
+        first.contexts.as_deref(),
+        Some(&["TF-CBT outperformed usual care".to_string()][..])
+    );
+
+    let bare = client
+        .get_citations(&id("rct"), &fields_for(Endpoint::Citations, "title"), 1, 0)
+        .await
+        .unwrap();
+    assert!(
+        bare.data[0].intents.is_none(),
+        "edge fields must be requested"
+    );
+
+    let refs = client
+        .get_references(
+            &id("meta"),
+            &fields_for(Endpoint::References, "title"),
+            10,
+            0,
+        )
+        .await
+        .unwrap();
+    let cited: Vec<_> = refs
+        .data
+        .iter()
+        .filter_map(|r| r.cited_paper.as_ref()?.paper_id.as_deref())
+        .collect();
+    assert_eq!(cited, ["rct", "adult"]);
+}
+
+#[tokio::test]
+async fn recommendations_rank_linked_papers_and_honour_negatives() {
+    let mock = MockScholar::start(corpus()).await;
+    let client = mock.client();
+
+    let single = client
+        .get_recommendations(
+            &id("rct"),
+            &fields_for(Endpoint::Recommendations, "title"),
+            10,
+        )
+        .await
+        .unwrap();
+    let ids: Vec<_> = single
+        .recommended_papers
+        .iter()
+        .filter_map(|p| p.paper_id.as_deref())
+        .collect();
+    assert_eq!(ids, ["meta", "pcit", "adult"]);
+
+    let multi = client
+        .get_recommendations_multi(
+            &[id("rct")],
+            &[id("adult")],
+            &fields_for(Endpoint::Recommendations, "title"),
+            10,
+        )
+        .await
+        .unwrap();
+    let ids: Vec<_> = multi
+        .recommended_papers
+        .iter()
+        .filter_map(|p| p.paper_id.as_deref())
+        .collect();
+    assert_eq!(ids, ["pcit"]);
+}
+
+#[tokio::test]
+async fn queued_429_is_retried() {
+    let mock = MockScholar::start(corpus()).await;
+    mock.fail_next(Failure::rate_limited());
+    mock.fail_next(Failure::rate_limited());
+
+    mock.client()
+        .get_paper(&id("rct"), &fields_for(Endpoint::PaperDetail, "title"))
+        .await
+        .unwrap();
+    let statuses: Vec<u16> = mock.requests().iter().map(|r| r.status).collect();
+    assert_eq!(statuses, [429, 429, 200]);
+}
+
+#[tokio::test]
+async fn route_specific_failure_leaves_other_routes_alone() {
+    let mock = MockScholar::start(corpus()).await;
+    mock.fail_next_on(Route::Citations, Failure::status(403, "Forbidden"));
+    let client = mock.client();
+
+    client
+        .get_paper(&id("rct"), &fields_for(Endpoint::PaperDetail, "title"))
+        .await
+        .unwrap();
+    let err = client
+        .get_citations(&id("rct"), &fields_for(Endpoint::Citations, "title"), 10, 0)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, Error::Unauthorized { status: 403, .. }),
+        "unexpected: {err}"
+    );
+    assert_eq!(mock.request_count(Route::Paper), 1);
+    assert_eq!(mock.request_count(Route::Citations), 1);
+    assert_eq!(mock.requests()[1].paper_id.as_deref(), Some("rct"));
+}
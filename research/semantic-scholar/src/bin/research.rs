@@ -1,20 +1,45 @@
 /// `research` — Semantic Scholar paper discovery CLI
 ///
-/// Outputs JSON to stdout (agent-friendly). Errors go to stderr.
+/// Outputs JSON to stdout (agent-friendly), or with --format BibTeX, RIS or CSL-JSON for
+/// reference managers and CSV or Markdown tables. Errors go to stderr.
 ///
 /// Examples:
 ///   research search "momentum cryptocurrency market regime" --limit 15 --year 2019-
 ///   research search "RSI bollinger bands strategy" --ranked --min-citations 20
+///   research search '"trauma-focused" (child* | adolescen*)' -t meta-analysis --field-of-study psychology
 ///   research paper "arXiv:1705.10311"
+///   research search "TF-CBT" -t meta-analysis --limit 50 --format ris > tfcbt.ris
+///   research cite "PMID:22265362" --limit 100 --format bibtex > citing.bib
+///   research snippets "TF-CBT effect size PTSD symptoms" --year 2015- --limit 5
+///   research autocomplete "trauma-focused cognitive beh"
 ///   research recommend "arXiv:1705.10311" --limit 8
+///   research recommend "PMID:15106204" "PMID:21395357" --negative "PMID:16380627"
 ///   research cite "arXiv:2305.02622" --limit 10
 ///   research refs "arXiv:2305.02622"
+///   research cite "PMID:22265362" --intent methodology --influential-only
+///   research snowball "PMID:22265362" --hops 2 --year 2012- --min-citations 20
+///   research snowball "PMID:22265362" --direction forward --format graphml > graph.graphml
+///   research author search "Judith Cohen" --limit 5
+///   research author papers 1741101 --limit 20
+///   research --cache-dir ~/.cache/s2 --offline paper "DOI:10.1037/a0012345"
+use std::path::PathBuf;
+
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+use futures::{future, StreamExt, TryStreamExt};
 use serde_json::json;
 
 use semantic_scholar::{
-    types::{Paper, PAPER_FIELDS_BRIEF, PAPER_FIELDS_FULL, SEARCH_FIELDS},
+    client::SEARCH_RESULT_LIMIT,
+    export::Table,
+    fields::{AuthorFieldSet, Endpoint, FieldSet},
+    snowball::{Direction, SnowballOptions},
+    types::{
+        Author, CitationIntent, CitationItem, Paper, ReferenceItem, SearchResponse, SnippetMatch,
+        AUTHOR_FIELDS, PAPER_FIELDS_BRIEF, PAPER_FIELDS_FULL,
+        SEARCH_FIELDS,
+    },
+    ExportFormat, FieldOfStudy, PaperId, PublicationType, ResponseCache, SearchFilters,
     SemanticScholarClient,
 };
 
@@ -33,6 +58,19 @@ struct Cli {
     #[arg(long)]
     api_key: Option<String>,
 
+    /// Cache responses in this directory (or set SEMANTIC_SCHOLAR_CACHE_DIR)
+    #[arg(long)]
+    cache_dir: Option<PathBuf>,
+
+    /// Answer only from the cache and never touch the network (requires a cache dir)
+    #[arg(long)]
+    offline: bool,
+
+    /// Output format. bibtex, ris and csl-json import into Zotero, EndNote or Mendeley;
+    /// graphml is only available for snowball
+    #[arg(long, global = true, value_enum, default_value = "json")]
+    format: OutputFormat,
+
     #[command(subcommand)]
     cmd: Command,
 }
@@ -41,7 +79,7 @@ struct Cli {
 enum Command {
     /// Search papers by keyword query.
     ///
-    /// Query syntax: "exact phrase", +must_include, -exclude, term1 | term2
+    /// Query syntax: "exact phrase", +must_include, -exclude, term1 | term2, prefix*
     Search {
         /// Search query
         query: String,
@@ -58,36 +96,93 @@ enum Command {
         #[arg(short, long)]
         min_citations: Option<u32>,
 
-        /// Sort results: "citationCount:desc", "publicationDate:desc", "paperId:asc"
+        /// Publication date range: "2019-03-05:2020-06", "2020:", ":2018-01"
+        #[arg(long)]
+        date: Option<String>,
+
+        /// Only papers of this type, e.g. MetaAnalysis, Review, ClinicalTrial (repeatable)
+        #[arg(short = 't', long = "publication-type")]
+        publication_types: Vec<PublicationType>,
+
+        /// Only papers in this field, e.g. Psychology, Medicine (repeatable)
+        #[arg(long = "field-of-study")]
+        fields_of_study: Vec<FieldOfStudy>,
+
+        /// Only papers from this venue (repeatable)
+        #[arg(long = "venue")]
+        venues: Vec<String>,
+
+        /// Only papers with a free PDF
         #[arg(long)]
+        open_access: bool,
+
+        /// Sort results: "citationCount:desc", "publicationDate:desc", "paperId:asc" (bulk only)
+        #[arg(long, conflicts_with = "ranked")]
         sort: Option<String>,
 
         /// Fields to return (comma-separated)
         #[arg(short, long, default_value = SEARCH_FIELDS)]
         fields: String,
 
-        /// Use relevance-ranked search instead of bulk (slower, richer ranking, max 1000)
+        /// Use relevance-ranked search instead of bulk (slower, richer ranking, max 1000).
+        /// Takes the same filters, but a plain-text query and no --sort
         #[arg(long)]
         ranked: bool,
     },
 
     /// Get full details for a single paper.
     ///
-    /// paper_id formats: S2PaperId, "DOI:10.xxx/yyy", "arXiv:1705.10311",
-    /// "PMID:12345", URL, etc.
+    /// paper_id formats: S2PaperId, "DOI:10.xxx/yyy", a bare DOI or doi.org link,
+    /// "arXiv:1705.10311" or an arxiv.org link, "PMID:12345" or a PubMed link,
+    /// "PMC1234567", "CorpusId:123", URL, etc.
     Paper {
         /// Paper identifier (S2PaperId, arXiv ID, DOI, etc.)
-        paper_id: String,
+        paper_id: PaperId,
 
         /// Fields to return (comma-separated)
         #[arg(short, long, default_value = PAPER_FIELDS_FULL)]
         fields: String,
     },
 
-    /// Find papers similar to a given paper (uses SPECTER2 embeddings).
+    /// Search passages from paper bodies, e.g. to find where effect sizes are reported.
+    Snippets {
+        /// Plain-text query (bulk query syntax is not supported here)
+        query: String,
+
+        /// Max snippets to return
+        #[arg(short, long, default_value = "10")]
+        limit: u32,
+
+        /// Year filter: "2023", "2020-2025", "2020-", "-2023"
+        #[arg(short, long)]
+        year: Option<String>,
+
+        /// Only search papers with at least this many citations
+        #[arg(short, long)]
+        min_citations: Option<u32>,
+
+        /// Only search inside this paper (repeatable, up to 100)
+        #[arg(short, long = "paper")]
+        papers: Vec<PaperId>,
+    },
+
+    /// Suggest paper titles for a partial title, e.g. to resolve it to a paper ID.
+    Autocomplete {
+        /// Partial title (only the first 100 characters are used)
+        query: String,
+    },
+
+    /// Find papers similar to one or more seed papers (uses SPECTER2 embeddings).
+    ///
+    /// With several seeds or any --negative IDs, uses the multi-example endpoint.
     Recommend {
-        /// Paper identifier
-        paper_id: String,
+        /// Paper identifier(s) to find more papers like
+        #[arg(required = true)]
+        paper_ids: Vec<PaperId>,
+
+        /// Paper identifier to steer away from (repeatable)
+        #[arg(short, long = "negative")]
+        negative: Vec<PaperId>,
 
         /// Max recommendations
         #[arg(short, long, default_value = "10")]
@@ -101,7 +196,7 @@ enum Command {
     /// Papers that cite this paper (forward citations).
     Cite {
         /// Paper identifier
-        paper_id: String,
+        paper_id: PaperId,
 
         /// Max results
         #[arg(short, long, default_value = "10")]
@@ -110,12 +205,20 @@ enum Command {
         /// Fields to return for each citing paper
         #[arg(short, long, default_value = PAPER_FIELDS_BRIEF)]
         fields: String,
+
+        /// Only keep citations Semantic Scholar marks as influential
+        #[arg(long)]
+        influential_only: bool,
+
+        /// Only keep citations with this intent: background, methodology, result (repeatable)
+        #[arg(long = "intent")]
+        intents: Vec<CitationIntent>,
     },
 
     /// Papers this paper references (backward citations).
     Refs {
         /// Paper identifier
-        paper_id: String,
+        paper_id: PaperId,
 
         /// Max results
         #[arg(short, long, default_value = "20")]
@@ -124,6 +227,131 @@ enum Command {
         /// Fields to return for each referenced paper
         #[arg(short, long, default_value = PAPER_FIELDS_BRIEF)]
         fields: String,
+
+        /// Only keep citations Semantic Scholar marks as influential
+        #[arg(long)]
+        influential_only: bool,
+
+        /// Only keep citations with this intent: background, methodology, result (repeatable)
+        #[arg(long = "intent")]
+        intents: Vec<CitationIntent>,
+    },
+
+    /// Snowball from seed papers through citations and references into a citation graph.
+    ///
+    /// Seeds are always kept; discovered papers must pass --year and --min-citations,
+    /// and the edge leading to them --influential-only and --intent.
+    Snowball {
+        /// Seed paper identifier(s)
+        #[arg(required = true)]
+        seeds: Vec<PaperId>,
+
+        /// Expansion rounds from the seeds
+        #[arg(long, default_value = "1")]
+        hops: u32,
+
+        /// Edges to follow: forward (citing papers), backward (references) or both
+        #[arg(short, long, default_value = "both")]
+        direction: Direction,
+
+        /// Year filter for discovered papers: "2023", "2020-2025", "2020-", "-2023"
+        #[arg(short, long)]
+        year: Option<String>,
+
+        /// Only keep discovered papers with at least this many citations
+        #[arg(short, long)]
+        min_citations: Option<u64>,
+
+        /// Only follow citations Semantic Scholar marks as influential
+        #[arg(long)]
+        influential_only: bool,
+
+        /// Only follow citations with this intent: background, methodology, result (repeatable)
+        #[arg(long = "intent")]
+        intents: Vec<CitationIntent>,
+
+        /// Stop once the graph holds this many papers
+        #[arg(long, default_value = "500")]
+        max_papers: usize,
+
+        /// Citations or references read per paper and direction
+        #[arg(long, default_value = "1000")]
+        max_edges: usize,
+
+        /// Fields to return for each paper (keep year and citationCount for the filters)
+        #[arg(short, long, default_value = PAPER_FIELDS_BRIEF)]
+        fields: String,
+    },
+
+    /// Look up authors: search by name, show a profile, or list their papers.
+    Author {
+        #[command(subcommand)]
+        cmd: AuthorCommand,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Json,
+    Bibtex,
+    Ris,
+    CslJson,
+    Csv,
+    Markdown,
+    Graphml,
+}
+
+impl OutputFormat {
+    fn export(self) -> Option<ExportFormat> {
+        match self {
+            Self::Bibtex => Some(ExportFormat::Bibtex),
+            Self::Ris => Some(ExportFormat::Ris),
+            Self::CslJson => Some(ExportFormat::CslJson),
+            Self::Csv => Some(ExportFormat::Csv),
+            Self::Markdown => Some(ExportFormat::Markdown),
+            Self::Json | Self::Graphml => None,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum AuthorCommand {
+    /// Search authors by name.
+    Search {
+        /// Author name
+        query: String,
+
+        /// Max results
+        #[arg(short, long, default_value = "10")]
+        limit: u32,
+
+        /// Fields to return for each author
+        #[arg(short, long, default_value = AUTHOR_FIELDS)]
+        fields: String,
+    },
+
+    /// Profile for one author: affiliations, h-index, paper and citation counts.
+    Show {
+        /// Semantic Scholar author ID
+        author_id: String,
+
+        /// Fields to return
+        #[arg(short, long, default_value = AUTHOR_FIELDS)]
+        fields: String,
+    },
+
+    /// Papers written by an author.
+    Papers {
+        /// Semantic Scholar author ID
+        author_id: String,
+
+        /// Max results (follows pagination beyond one page)
+        #[arg(short, long, default_value = "20")]
+        limit: usize,
+
+        /// Fields to return for each paper
+        #[arg(short, long, default_value = PAPER_FIELDS_BRIEF)]
+        fields: String,
     },
 }
 
@@ -133,7 +361,26 @@ async fn main() -> Result<()> {
     let api_key = cli
         .api_key
         .or_else(|| std::env::var("SEMANTIC_SCHOLAR_API_KEY").ok());
-    let client = SemanticScholarClient::new(api_key.as_deref());
+    let cache_dir = cli
+        .cache_dir
+        .or_else(|| std::env::var_os("SEMANTIC_SCHOLAR_CACHE_DIR").map(PathBuf::from));
+    let mut builder = SemanticScholarClient::builder();
+    if let Some(key) = api_key {
+        builder = builder.api_key(key);
+    }
+    match (cache_dir, cli.offline) {
+        (Some(dir), offline) => {
+            let cache = ResponseCache::open(dir)?;
+            builder = builder.cache(if offline { cache.offline() } else { cache });
+        }
+        (None, true) => anyhow::bail!("--offline needs --cache-dir or SEMANTIC_SCHOLAR_CACHE_DIR"),
+        (None, false) => {}
+    }
+    let client = builder.build()?;
+    let format = cli.format;
+    if format == OutputFormat::Graphml && !matches!(cli.cmd, Command::Snowball { .. }) {
+        anyhow::bail!("--format graphml is only available for snowball");
+    }
 
     let output = match cli.cmd {
         Command::Search {
@@ -141,110 +388,420 @@ async fn main() -> Result<()> {
             limit,
             year,
             min_citations,
+            date,
+            publication_types,
+            fields_of_study,
+            venues,
+            open_access,
             sort,
             fields,
             ranked,
         } => {
+            let filters = SearchFilters {
+                year,
+                publication_date_or_year: date,
+                min_citations,
+                publication_types,
+                fields_of_study,
+                venues,
+                open_access_pdf: open_access,
+            };
+            let endpoint = if ranked {
+                Endpoint::Search
+            } else {
+                Endpoint::BulkSearch
+            };
+            let fields = FieldSet::parse(endpoint, &fields)?;
             if ranked {
-                let resp = client.search(&query, &fields, limit, 0).await?;
-                json!({
+                if limit > SEARCH_RESULT_LIMIT {
+                    anyhow::bail!(
+                        "--ranked serves at most {SEARCH_RESULT_LIMIT} results; use bulk search for more"
+                    );
+                }
+                let pages: Vec<SearchResponse> = client
+                    .search_pages(&query, &fields, &filters, limit as usize)
+                    .try_collect()
+                    .await?;
+                let total = pages.first().and_then(|p| p.total);
+                let papers: Vec<Paper> = pages.into_iter().flat_map(|p| p.data).collect();
+                let json = json!({
                     "query": query,
                     "mode": "relevance-ranked",
-                    "total": resp.total,
-                    "count": resp.data.len(),
-                    "papers": resp.data.iter().map(paper_summary).collect::<Vec<_>>(),
-                })
+                    "total": total,
+                    "count": papers.len(),
+                    "papers": papers.iter().map(paper_summary).collect::<Vec<_>>(),
+                });
+                Output::papers(json, papers)
             } else {
                 let resp = client
                     .search_bulk(
                         &query,
                         &fields,
-                        year.as_deref(),
-                        min_citations,
+                        &filters,
                         sort.as_deref(),
                         limit,
                     )
                     .await?;
-                json!({
+                let json = json!({
                     "query": query,
                     "mode": "bulk",
                     "total": resp.total,
                     "count": resp.data.len(),
                     "papers": resp.data.iter().map(paper_summary).collect::<Vec<_>>(),
-                })
+                });
+                Output::papers(json, resp.data)
             }
         }
 
         Command::Paper { paper_id, fields } => {
+            let fields = FieldSet::parse(Endpoint::PaperDetail, &fields)?;
             let paper = client.get_paper(&paper_id, &fields).await?;
-            json!({
+            let json = json!({
                 "paper": paper_summary(&paper),
                 "abstract": paper.abstract_text,
                 "venue": paper.venue,
+                "journal": paper.journal,
+                "external_ids": paper.external_ids,
+                "reference_count": paper.reference_count,
                 "publication_date": paper.publication_date,
                 "is_open_access": paper.is_open_access,
                 "influential_citations": paper.influential_citation_count,
-            })
+            });
+            Output::papers(json, vec![paper])
         }
 
-        Command::Recommend {
-            paper_id,
+        Command::Snippets {
+            query,
             limit,
-            fields,
+            year,
+            min_citations,
+            papers,
         } => {
             let resp = client
-                .get_recommendations(&paper_id, &fields, limit)
+                .search_snippets(&query, &papers, year.as_deref(), min_citations, limit)
                 .await?;
-            json!({
-                "seed_paper_id": paper_id,
-                "count": resp.recommended_papers.len(),
-                "papers": resp.recommended_papers.iter().map(paper_summary).collect::<Vec<_>>(),
-            })
+            let json = json!({
+                "query": query,
+                "count": resp.data.len(),
+                "snippets": resp.data.iter().map(snippet_summary).collect::<Vec<_>>(),
+            });
+            // One reference per paper, however many passages matched in it.
+            let mut papers: Vec<Paper> = Vec::new();
+            for hit in &resp.data {
+                let paper = snippet_paper(hit);
+                if !papers.iter().any(|p| p.paper_id == paper.paper_id) {
+                    papers.push(paper);
+                }
+            }
+            let table = resp.data.iter().fold(
+                Table::new(["paper_id", "title", "section", "kind", "text", "score"]),
+                |table, hit| {
+                    table.row([
+                        hit.paper
+                            .paper_id()
+                            .map(|id| id.to_string())
+                            .unwrap_or_default(),
+                        hit.paper.title.clone().unwrap_or_default(),
+                        hit.snippet.section.clone().unwrap_or_default(),
+                        hit.snippet.snippet_kind.clone().unwrap_or_default(),
+                        hit.snippet.text.clone(),
+                        hit.score.map(|s| format!("{s:.3}")).unwrap_or_default(),
+                    ])
+                },
+            );
+            Output::table(json, papers, table)
+        }
+
+        Command::Autocomplete { query } => {
+            let resp = client.autocomplete(&query).await?;
+            let json = json!({
+                "query": query,
+                "count": resp.matches.len(),
+                "matches": resp.matches.iter().map(|m| json!({
+                    "paper_id": m.paper_id(),
+                    "title": m.title,
+                    "authors": m.authors(),
+                    "year": m.year(),
+                })).collect::<Vec<_>>(),
+            });
+            // `authorsYear` is a display string ("Cohen et al."), not author names.
+            let papers = resp
+                .matches
+                .iter()
+                .map(|m| Paper {
+                    paper_id: Some(m.id.clone()),
+                    title: m.title.clone(),
+                    year: m.year(),
+                    ..Default::default()
+                })
+                .collect();
+            let table = resp.matches.iter().fold(
+                Table::new(["paper_id", "title", "authors", "year"]),
+                |table, m| {
+                    table.row([
+                        m.id.clone(),
+                        m.title.clone().unwrap_or_default(),
+                        m.authors().unwrap_or_default().to_string(),
+                        m.year().map(|y| y.to_string()).unwrap_or_default(),
+                    ])
+                },
+            );
+            Output::table(json, papers, table)
+        }
+
+        Command::Recommend {
+            paper_ids,
+            negative,
+            limit,
+            fields,
+        } => {
+            let fields = FieldSet::parse(Endpoint::Recommendations, &fields)?;
+            if let ([paper_id], []) = (paper_ids.as_slice(), negative.as_slice()) {
+                let resp = client
+                    .get_recommendations(paper_id, &fields, limit)
+                    .await?;
+                let json = json!({
+                    "seed_paper_id": paper_id,
+                    "count": resp.recommended_papers.len(),
+                    "papers": resp.recommended_papers.iter().map(paper_summary).collect::<Vec<_>>(),
+                });
+                Output::papers(json, resp.recommended_papers)
+            } else {
+                let resp = client
+                    .get_recommendations_multi(&paper_ids, &negative, &fields, limit)
+                    .await?;
+                let json = json!({
+                    "positive_paper_ids": paper_ids,
+                    "negative_paper_ids": negative,
+                    "count": resp.recommended_papers.len(),
+                    "papers": resp.recommended_papers.iter().map(paper_summary).collect::<Vec<_>>(),
+                });
+                Output::papers(json, resp.recommended_papers)
+            }
         }
 
         Command::Cite {
             paper_id,
             limit,
             fields,
+            influential_only,
+            intents,
         } => {
-            let resp = client.get_citations(&paper_id, &fields, limit).await?;
-            let papers: Vec<_> = resp
-                .data
+            let fields = FieldSet::parse(Endpoint::Citations, &fields)?.with_edge_fields()?;
+            let filter = EdgeFilter {
+                influential_only,
+                intents,
+            };
+            let items: Vec<CitationItem> = client
+                .citations_stream(&paper_id, &fields, filter.scan_limit(limit))
+                .try_filter(|c| future::ready(filter.keeps(&c.intents, c.is_influential)))
+                .take(limit as usize)
+                .try_collect()
+                .await?;
+            let papers: Vec<_> = items
                 .iter()
-                .filter_map(|c| c.citing_paper.as_ref())
-                .map(paper_summary)
+                .filter_map(|c| {
+                    let paper = c.citing_paper.as_ref()?;
+                    Some(edge_summary(paper, &c.intents, c.is_influential, &c.contexts))
+                })
                 .collect();
-            json!({
+            let json = json!({
                 "cited_paper_id": paper_id,
                 "count": papers.len(),
                 "citing_papers": papers,
-            })
+            });
+            Output::papers(
+                json,
+                items.into_iter().filter_map(|c| c.citing_paper).collect(),
+            )
         }
 
         Command::Refs {
             paper_id,
             limit,
             fields,
+            influential_only,
+            intents,
         } => {
-            let resp = client.get_references(&paper_id, &fields, limit).await?;
-            let papers: Vec<_> = resp
-                .data
+            let fields = FieldSet::parse(Endpoint::References, &fields)?.with_edge_fields()?;
+            let filter = EdgeFilter {
+                influential_only,
+                intents,
+            };
+            let items: Vec<ReferenceItem> = client
+                .references_stream(&paper_id, &fields, filter.scan_limit(limit))
+                .try_filter(|r| future::ready(filter.keeps(&r.intents, r.is_influential)))
+                .take(limit as usize)
+                .try_collect()
+                .await?;
+            let papers: Vec<_> = items
                 .iter()
-                .filter_map(|r| r.cited_paper.as_ref())
-                .map(paper_summary)
+                .filter_map(|r| {
+                    let paper = r.cited_paper.as_ref()?;
+                    Some(edge_summary(paper, &r.intents, r.is_influential, &r.contexts))
+                })
                 .collect();
-            json!({
+            let json = json!({
                 "paper_id": paper_id,
                 "count": papers.len(),
                 "references": papers,
-            })
+            });
+            Output::papers(
+                json,
+                items.into_iter().filter_map(|r| r.cited_paper).collect(),
+            )
+        }
+
+        Command::Snowball {
+            seeds,
+            hops,
+            direction,
+            year,
+            min_citations,
+            influential_only,
+            intents,
+            max_papers,
+            max_edges,
+            fields,
+        } => {
+            let fields = FieldSet::parse(Endpoint::Citations, &fields)?;
+            let options = SnowballOptions {
+                hops,
+                direction,
+                year,
+                min_citations,
+                influential_only,
+                intents,
+                max_edges_per_paper: max_edges,
+                max_papers,
+                fields,
+                ..Default::default()
+            };
+            let graph = client.snowball(&seeds, &options).await?;
+            if format == OutputFormat::Graphml {
+                print!("{}", graph.to_graphml());
+                return Ok(());
+            }
+            let nodes: Vec<_> = graph
+                .nodes
+                .iter()
+                .map(|n| {
+                    let mut summary = paper_summary(&n.paper);
+                    summary["hop"] = json!(n.hop);
+                    summary
+                })
+                .collect();
+            let json = json!({
+                "seeds": seeds,
+                "unresolved_seeds": graph.unresolved_seeds,
+                "failed_expansions": graph.failed_expansions,
+                "hops": hops,
+                "node_count": nodes.len(),
+                "edge_count": graph.edges.len(),
+                "nodes": nodes,
+                "edges": graph.edges,
+            });
+            Output::papers(json, graph.nodes.into_iter().map(|n| n.paper).collect())
         }
+
+        Command::Author { cmd } => match cmd {
+            AuthorCommand::Search {
+                query,
+                limit,
+                fields,
+            } => {
+                let fields = AuthorFieldSet::parse(&fields)?;
+                let resp = client.search_authors(&query, &fields, limit, 0).await?;
+                let json = json!({
+                    "query": query,
+                    "total": resp.total,
+                    "count": resp.data.len(),
+                    "authors": resp.data.iter().map(author_summary).collect::<Vec<_>>(),
+                });
+                Output::authors(json, author_table(&resp.data))
+            }
+
+            AuthorCommand::Show { author_id, fields } => {
+                let fields = AuthorFieldSet::parse(&fields)?;
+                let author = client.get_author(&author_id, &fields).await?;
+                let json = json!({ "author": author_summary(&author) });
+                Output::authors(json, author_table(&[author]))
+            }
+
+            AuthorCommand::Papers {
+                author_id,
+                limit,
+                fields,
+            } => {
+                let fields = FieldSet::parse(Endpoint::AuthorPapers, &fields)?;
+                let papers: Vec<Paper> = client
+                    .author_papers_stream(&author_id, &fields, limit)
+                    .try_collect()
+                    .await?;
+                let json = json!({
+                    "author_id": author_id,
+                    "count": papers.len(),
+                    "papers": papers.iter().map(paper_summary).collect::<Vec<_>>(),
+                });
+                Output::papers(json, papers)
+            }
+        },
     };
 
-    println!("{}", serde_json::to_string_pretty(&output)?);
+    let Some(export) = format.export() else {
+        println!("{}", serde_json::to_string_pretty(&output.json)?);
+        return Ok(());
+    };
+    match (export, &output.table) {
+        (ExportFormat::Csv, Some(table)) => print!("{}", table.to_csv()),
+        (ExportFormat::Markdown, Some(table)) => print!("{}", table.to_markdown()),
+        _ if output.authors => {
+            anyhow::bail!("authors are not references; use --format json, csv or markdown")
+        }
+        _ => print!("{}", export.render(&output.papers)),
+    }
     Ok(())
 }
 
+/// A subcommand's result: JSON by default, papers for the export formats.
+struct Output {
+    json: serde_json::Value,
+    papers: Vec<Paper>,
+    /// Replaces the paper table for CSV and Markdown, e.g. with snippets or authors.
+    table: Option<Table>,
+    /// Author results, which have no reference-manager export.
+    authors: bool,
+}
+
+impl Output {
+    fn papers(json: serde_json::Value, papers: Vec<Paper>) -> Self {
+        Self {
+            json,
+            papers,
+            table: None,
+            authors: false,
+        }
+    }
+
+    fn table(json: serde_json::Value, papers: Vec<Paper>, table: Table) -> Self {
+        Self {
+            json,
+            papers,
+            table: Some(table),
+            authors: false,
+        }
+    }
+
+    fn authors(json: serde_json::Value, table: Table) -> Self {
+        Self {
+            json,
+            papers: Vec::new(),
+            table: Some(table),
+            authors: true,
+        }
+    }
+}
+
 /// Flatten a Paper into a compact JSON summary suitable for agent consumption.
 fn paper_summary(paper: &Paper) -> serde_json::Value {
     let tldr = paper.tldr.as_ref().and_then(|t| t.text.as_deref());
@@ -268,9 +825,122 @@ fn paper_summary(paper: &Paper) -> serde_json::Value {
         "abstract": abstract_snippet,
         "pdf_url": paper.open_access_pdf.as_ref().and_then(|p| p.url.as_deref()),
         "url": paper.url,
+        "doi": paper.doi(),
+        "publication_types": paper.publication_types,
         "authors": paper.authors.as_ref().map(|authors| {
             authors.iter().filter_map(|a| a.name.as_deref()).collect::<Vec<_>>()
         }),
         "fields": paper.fields_of_study,
     })
 }
+
+/// Flatten a snippet hit into the passage plus enough paper context to cite it.
+fn snippet_summary(hit: &SnippetMatch) -> serde_json::Value {
+    json!({
+        "paper_id": hit.paper.paper_id(),
+        "title": hit.paper.title,
+        "authors": hit.paper.authors,
+        "kind": hit.snippet.snippet_kind,
+        "section": hit.snippet.section,
+        "text": hit.snippet.text,
+        "offset": hit.snippet.snippet_offset,
+        "score": hit.score,
+    })
+}
+
+/// The paper behind a snippet hit, as far as snippet search describes it.
+fn snippet_paper(hit: &SnippetMatch) -> Paper {
+    Paper {
+        paper_id: hit.paper.paper_id().map(|id| id.to_string()),
+        title: hit.paper.title.clone(),
+        authors: hit.paper.authors.as_ref().map(|names| {
+            names
+                .iter()
+                .map(|name| Author {
+                    name: Some(name.clone()),
+                    ..Default::default()
+                })
+                .collect()
+        }),
+        ..Default::default()
+    }
+}
+
+/// Most citation edges `cite`/`refs` read while looking for filter matches.
+const EDGE_SCAN_LIMIT: usize = 10_000;
+
+/// `--influential-only` and `--intent` for `cite` and `refs`.
+struct EdgeFilter {
+    influential_only: bool,
+    intents: Vec<CitationIntent>,
+}
+
+impl EdgeFilter {
+    fn keeps(&self, intents: &Option<Vec<CitationIntent>>, is_influential: Option<bool>) -> bool {
+        (!self.influential_only || is_influential == Some(true))
+            && (self.intents.is_empty()
+                || intents.iter().flatten().any(|i| self.intents.contains(i)))
+    }
+
+    /// Unfiltered listings need exactly `limit` edges; filtered ones keep paging.
+    fn scan_limit(&self, limit: u32) -> usize {
+        if self.influential_only || !self.intents.is_empty() {
+            EDGE_SCAN_LIMIT
+        } else {
+            limit as usize
+        }
+    }
+}
+
+/// A paper summary plus why and how it was cited.
+fn edge_summary(
+    paper: &Paper,
+    intents: &Option<Vec<CitationIntent>>,
+    is_influential: Option<bool>,
+    contexts: &Option<Vec<String>>,
+) -> serde_json::Value {
+    let mut summary = paper_summary(paper);
+    summary["intents"] = json!(intents);
+    summary["is_influential"] = json!(is_influential);
+    summary["contexts"] = json!(contexts);
+    summary
+}
+
+/// Authors as CSV or Markdown rows.
+fn author_table(authors: &[Author]) -> Table {
+    let header = [
+        "author_id",
+        "name",
+        "affiliations",
+        "h_index",
+        "paper_count",
+        "citation_count",
+        "url",
+    ];
+    let count = |n: Option<u64>| n.map(|n| n.to_string()).unwrap_or_default();
+    authors.iter().fold(Table::new(header), |table, a| {
+        table.row([
+            a.author_id.clone().unwrap_or_default(),
+            a.name.clone().unwrap_or_default(),
+            a.affiliations.as_deref().unwrap_or_default().join("; "),
+            count(a.h_index),
+            count(a.paper_count),
+            count(a.citation_count),
+            a.url.clone().unwrap_or_default(),
+        ])
+    })
+}
+
+/// Flatten an Author into a compact JSON summary.
+fn author_summary(author: &Author) -> serde_json::Value {
+    json!({
+        "author_id": author.author_id,
+        "name": author.name,
+        "affiliations": author.affiliations,
+        "h_index": author.h_index,
+        "paper_count": author.paper_count,
+        "citation_count": author.citation_count,
+        "homepage": author.homepage,
+        "url": author.url,
+    })
+}
@@ -0,0 +1,296 @@
+//! Persistent on-disk response cache.
+//!
+//! Successful responses are stored as one JSON file per request under a cache
+//! directory, keyed on method, URL path (relative to the base URL), query parameters
+//! and request body. Entries expire per [`CacheCategory`], so search results can go
+//! stale sooner than paper metadata. In [`CacheMode::Offline`] the client never
+//! touches the network and a miss is an [`Error::CacheMiss`].
+use std::{
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::Error;
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Distinguishes temp files written concurrently by this process.
+static TMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// How the cache participates in requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CacheMode {
+    /// Serve fresh entries from disk, fetch and store everything else.
+    #[default]
+    ReadWrite,
+    /// Serve entries from disk regardless of age and never send a request.
+    Offline,
+}
+
+/// Groups of endpoints that share a TTL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CacheCategory {
+    /// Paper and author keyword search, title match, autocomplete.
+    Search,
+    /// Single-paper and batch lookups, author details.
+    Paper,
+    /// Citations, references and author paper lists.
+    CitationGraph,
+    /// Recommendations API.
+    Recommendations,
+    /// Datasets API: releases, signed file URLs and diffs.
+    Datasets,
+}
+
+impl CacheCategory {
+    /// Classify a request by its path relative to the base URL.
+    pub fn for_path(path: &str) -> Self {
+        if path.starts_with("/recommendations/") {
+            Self::Recommendations
+        } else if path.starts_with("/datasets/") {
+            Self::Datasets
+        } else if path.contains("/search") || path.ends_with("/autocomplete") {
+            Self::Search
+        } else if path.ends_with("/citations")
+            || path.ends_with("/references")
+            || path.ends_with("/papers")
+        {
+            Self::CitationGraph
+        } else {
+            Self::Paper
+        }
+    }
+}
+
+/// Time-to-live for each [`CacheCategory`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheTtls {
+    pub search: Duration,
+    pub paper: Duration,
+    pub citation_graph: Duration,
+    pub recommendations: Duration,
+    pub datasets: Duration,
+}
+
+impl Default for CacheTtls {
+    /// One day for search, graph and recommendations; a week for paper metadata; an
+    /// hour for the Datasets API, whose signed file URLs expire.
+    fn default() -> Self {
+        Self {
+            search: DAY,
+            paper: 7 * DAY,
+            citation_graph: DAY,
+            recommendations: DAY,
+            datasets: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+impl CacheTtls {
+    pub fn get(&self, category: CacheCategory) -> Duration {
+        match category {
+            CacheCategory::Search => self.search,
+            CacheCategory::Paper => self.paper,
+            CacheCategory::CitationGraph => self.citation_graph,
+            CacheCategory::Recommendations => self.recommendations,
+            CacheCategory::Datasets => self.datasets,
+        }
+    }
+
+    pub fn set(&mut self, category: CacheCategory, ttl: Duration) {
+        match category {
+            CacheCategory::Search => self.search = ttl,
+            CacheCategory::Paper => self.paper = ttl,
+            CacheCategory::CitationGraph => self.citation_graph = ttl,
+            CacheCategory::Recommendations => self.recommendations = ttl,
+            CacheCategory::Datasets => self.datasets = ttl,
+        }
+    }
+}
+
+/// What a cache file holds. The request is kept alongside the body so the
+/// directory can be inspected by hand.
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry {
+    method: String,
+    path: String,
+    params: Vec<(String, String)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<serde_json::Value>,
+    stored_at_ms: u64,
+    response: serde_json::Value,
+}
+
+/// A directory of cached responses, see the [module docs](self).
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use semantic_scholar::{cache::{CacheCategory, ResponseCache}, SemanticScholarClient};
+///
+/// let cache = ResponseCache::open(".s2-cache")?
+///     .ttl(CacheCategory::Search, Duration::from_secs(3600));
+/// let client = SemanticScholarClient::builder().cache(cache).build()?;
+/// # Ok::<(), semantic_scholar::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    dir: PathBuf,
+    ttls: CacheTtls,
+    mode: CacheMode,
+}
+
+impl ResponseCache {
+    /// Use `dir` as the cache directory, creating it if needed.
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self, Error> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| Error::Cache(format!("creating {}: {e}", dir.display())))?;
+        Ok(Self {
+            dir,
+            ttls: CacheTtls::default(),
+            mode: CacheMode::default(),
+        })
+    }
+
+    pub fn ttl(mut self, category: CacheCategory, ttl: Duration) -> Self {
+        self.ttls.set(category, ttl);
+        self
+    }
+
+    pub fn ttls(mut self, ttls: CacheTtls) -> Self {
+        self.ttls = ttls;
+        self
+    }
+
+    pub fn mode(mut self, mode: CacheMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Shorthand for `mode(CacheMode::Offline)`.
+    pub fn offline(self) -> Self {
+        self.mode(CacheMode::Offline)
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn current_mode(&self) -> CacheMode {
+        self.mode
+    }
+
+    /// Delete every cached response.
+    pub fn clear(&self) -> Result<(), Error> {
+        let entries = std::fs::read_dir(&self.dir)
+            .map_err(|e| Error::Cache(format!("reading {}: {e}", self.dir.display())))?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                std::fs::remove_file(&path)
+                    .map_err(|e| Error::Cache(format!("removing {}: {e}", path.display())))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Look up a stored response. Expired entries are ignored unless offline.
+    pub(crate) async fn get(&self, key: &CacheKey<'_>) -> Option<serde_json::Value> {
+        let bytes = tokio::fs::read(self.file_for(key)).await.ok()?;
+        let entry: Entry = match serde_json::from_slice(&bytes) {
+            Ok(entry) => entry,
+            Err(e) => {
+                tracing::warn!(path = key.path, error = %e, "ignoring unreadable cache entry");
+                return None;
+            }
+        };
+        if self.mode != CacheMode::Offline {
+            let ttl = self.ttls.get(CacheCategory::for_path(key.path));
+            let age = now_ms().saturating_sub(entry.stored_at_ms);
+            if u128::from(age) >= ttl.as_millis() {
+                return None;
+            }
+        }
+        Some(entry.response)
+    }
+
+    /// Store a response. Failures are logged, never returned: a broken cache must not
+    /// fail a request that succeeded.
+    pub(crate) async fn put(&self, key: &CacheKey<'_>, response: &serde_json::Value) {
+        let entry = Entry {
+            method: key.method.to_string(),
+            path: key.path.to_string(),
+            params: key.params.to_vec(),
+            body: key.body.cloned(),
+            stored_at_ms: now_ms(),
+            response: response.clone(),
+        };
+        let path = self.file_for(key);
+        // Concurrent writers of one key (tasks or processes) each get their own temp
+        // file, so the rename always installs a complete entry.
+        let tmp = path.with_extension(format!(
+            "{}.{}.tmp",
+            std::process::id(),
+            TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let result = async {
+            let bytes = serde_json::to_vec(&entry)?;
+            tokio::fs::write(&tmp, bytes).await?;
+            tokio::fs::rename(&tmp, &path).await?;
+            Ok::<_, Box<dyn std::error::Error + Send + Sync>>(())
+        }
+        .await;
+        if let Err(e) = result {
+            tracing::warn!(path = key.path, error = %e, "failed to write cache entry");
+            let _ = tokio::fs::remove_file(&tmp).await;
+        }
+    }
+
+    fn file_for(&self, key: &CacheKey<'_>) -> PathBuf {
+        self.dir.join(format!("{}.json", key.digest()))
+    }
+}
+
+/// The parts of a request that identify its response.
+pub(crate) struct CacheKey<'a> {
+    pub method: &'a str,
+    pub path: &'a str,
+    pub params: &'a [(String, String)],
+    pub body: Option<&'a serde_json::Value>,
+}
+
+impl CacheKey<'_> {
+    pub(crate) fn digest(&self) -> String {
+        let mut params: Vec<_> = self.params.iter().collect();
+        params.sort();
+        let mut hasher = Sha256::new();
+        hasher.update(self.method.as_bytes());
+        hasher.update([0]);
+        hasher.update(self.path.as_bytes());
+        for (k, v) in params {
+            hasher.update([0]);
+            hasher.update(k.as_bytes());
+            hasher.update([b'=']);
+            hasher.update(v.as_bytes());
+        }
+        if let Some(body) = self.body {
+            hasher.update([0]);
+            hasher.update(body.to_string().as_bytes());
+        }
+        hasher
+            .finalize()
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect()
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64)
+}
@@ -1,187 +1,651 @@
 use std::time::Duration;
 
+use serde::de::DeserializeOwned;
 use tokio::time::sleep;
 
 use crate::{
-    error::Error,
+    cache::{CacheKey, CacheMode, ResponseCache},
+    error::{Error, RequestContext},
+    fields::{AuthorFieldSet, Endpoint, FieldSet},
+    fixtures::{FixtureMode, Fixtures},
+    paper_id::{encode_path_segment, PaperId},
+    query::SearchFilters,
+    rate_limit::RateLimiter,
+    retry::{self, RetryPolicy},
     types::{
-        BulkSearchResponse, CitationsResponse, Paper, RecommendationsResponse, ReferencesResponse,
-        SearchResponse,
+        Author, AuthorPapersResponse, AutocompleteResponse, AuthorSearchResponse, BulkSearchResponse,
+        CitationsResponse, Paper, PaperMatch, PaperMatchResponse, RecommendationsResponse,
+        ReferencesResponse, SearchResponse, SnippetSearchResponse,
     },
 };
 
-const BASE_URL: &str = "https://api.semanticscholar.org";
-const MAX_RETRIES: u32 = 3;
+const DEFAULT_BASE_URL: &str = "https://api.semanticscholar.org";
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+/// Maximum number of IDs `POST /paper/batch` accepts per request.
+pub const BATCH_LIMIT: usize = 500;
+/// Largest page relevance-ranked search serves.
+pub const SEARCH_PAGE_LIMIT: u32 = 100;
+/// Relevance-ranked search only serves results up to this `offset + limit`.
+pub const SEARCH_RESULT_LIMIT: u32 = 1000;
+const DEFAULT_USER_AGENT: &str = concat!("semantic-scholar-rs/", env!("CARGO_PKG_VERSION"));
+
+/// A successful JSON response and the request it answered.
+pub(crate) struct JsonResponse {
+    pub value: serde_json::Value,
+    context: RequestContext,
+}
+
+impl JsonResponse {
+    /// Deserialize the body, attaching the request to any schema mismatch.
+    pub(crate) fn decode<T: DeserializeOwned>(self) -> Result<T, Error> {
+        serde_json::from_value(self.value).map_err(|source| Error::Json {
+            source,
+            context: Box::new(self.context),
+        })
+    }
+}
 
 /// Async client for the Semantic Scholar Academic Graph, Recommendations, and Datasets APIs.
 ///
 /// Without an API key requests share the unauthenticated rate-limit pool. With a free
 /// key (`x-api-key` header) you get 1 req/s dedicated. Set the `SEMANTIC_SCHOLAR_API_KEY`
 /// env var or pass it to [`SemanticScholarClient::new`].
+///
+/// Use [`SemanticScholarClient::builder`] to override the base URL, timeouts or retry policy,
+/// or to enable a client-side rate limiter shared by every clone of the client, an
+/// on-disk [`ResponseCache`] or record/replay [`Fixtures`].
 #[derive(Clone)]
 pub struct SemanticScholarClient {
     http: reqwest::Client,
+    base_url: String,
+    retry: RetryPolicy,
+    limiter: Option<RateLimiter>,
+    cache: Option<ResponseCache>,
+    fixtures: Option<Fixtures>,
 }
 
-impl SemanticScholarClient {
-    pub fn new(api_key: Option<&str>) -> Self {
+// ─── Builder ─────────────────────────────────────────────────────────────────
+
+pub struct SemanticScholarClientBuilder {
+    api_key: Option<String>,
+    base_url: String,
+    timeout: Duration,
+    connect_timeout: Option<Duration>,
+    user_agent: String,
+    retry: RetryPolicy,
+    limiter: Option<RateLimiter>,
+    cache: Option<ResponseCache>,
+    fixtures: Option<Fixtures>,
+}
+
+impl Default for SemanticScholarClientBuilder {
+    fn default() -> Self {
+        Self {
+            api_key: None,
+            base_url: DEFAULT_BASE_URL.into(),
+            timeout: DEFAULT_TIMEOUT,
+            connect_timeout: None,
+            user_agent: DEFAULT_USER_AGENT.into(),
+            retry: RetryPolicy::default(),
+            limiter: None,
+            cache: None,
+            fixtures: None,
+        }
+    }
+}
+
+impl SemanticScholarClientBuilder {
+    /// Send `key` as the `x-api-key` header on every request.
+    pub fn api_key(mut self, key: impl Into<String>) -> Self {
+        self.api_key = Some(key.into());
+        self
+    }
+
+    /// Override the API base URL (default: `https://api.semanticscholar.org`).
+    /// Primarily useful in tests to point at a local mock server.
+    pub fn base_url(mut self, url: &str) -> Self {
+        self.base_url = url.trim_end_matches('/').into();
+        self
+    }
+
+    /// Total per-request timeout (default: 30s).
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Timeout for establishing the TCP/TLS connection only.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Replace the whole retry policy.
+    pub fn retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Shorthand for adjusting only [`RetryPolicy::max_retries`].
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.retry.max_retries = max_retries;
+        self
+    }
+
+    /// Throttle requests client-side to `requests_per_second`, allowing bursts of up
+    /// to `burst` back-to-back requests. A keyed account should use `rate_limit(1.0, 1)`.
+    ///
+    /// # Panics
+    /// If `requests_per_second` is not positive or `burst` is zero.
+    pub fn rate_limit(mut self, requests_per_second: f64, burst: u32) -> Self {
+        self.limiter = Some(RateLimiter::new(requests_per_second, burst));
+        self
+    }
+
+    /// Share an existing limiter, e.g. between clients with different settings
+    /// that draw on the same API key.
+    pub fn rate_limiter(mut self, limiter: RateLimiter) -> Self {
+        self.limiter = Some(limiter);
+        self
+    }
+
+    /// Serve repeated requests from `cache` and store new responses in it.
+    pub fn cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Record responses to, or replay them from, fixture files. Takes precedence
+    /// over the response cache.
+    pub fn fixtures(mut self, fixtures: Fixtures) -> Self {
+        self.fixtures = Some(fixtures);
+        self
+    }
+
+    /// Build the client. Fails if the API key or user agent is not a valid header
+    /// value, or if the underlying HTTP client cannot be constructed.
+    pub fn build(self) -> Result<SemanticScholarClient, Error> {
         let mut headers = reqwest::header::HeaderMap::new();
-        if let Some(key) = api_key {
-            if let Ok(val) = reqwest::header::HeaderValue::from_str(key) {
-                headers.insert("x-api-key", val);
-            }
+        if let Some(key) = &self.api_key {
+            let val = reqwest::header::HeaderValue::from_str(key)
+                .map_err(|_| Error::Config("API key is not a valid header value".into()))?;
+            headers.insert("x-api-key", val);
         }
-        let http = reqwest::Client::builder()
+        let user_agent = reqwest::header::HeaderValue::from_str(&self.user_agent)
+            .map_err(|_| Error::Config("user agent is not a valid header value".into()))?;
+
+        let mut http = reqwest::Client::builder()
             .default_headers(headers)
-            .timeout(Duration::from_secs(30))
+            .user_agent(user_agent)
+            .timeout(self.timeout);
+        if let Some(t) = self.connect_timeout {
+            http = http.connect_timeout(t);
+        }
+
+        let http = http
             .build()
-            .expect("failed to build reqwest client");
-        Self { http }
+            .map_err(|e| Error::Config(format!("cannot build HTTP client: {e}")))?;
+        Ok(SemanticScholarClient {
+            http,
+            base_url: self.base_url,
+            retry: self.retry,
+            limiter: self.limiter,
+            cache: self.cache,
+            fixtures: self.fixtures,
+        })
+    }
+}
+
+// ─── Client ──────────────────────────────────────────────────────────────────
+
+impl SemanticScholarClient {
+    /// Client with default settings against the public API.
+    ///
+    /// # Panics
+    /// If the HTTP client cannot be built. Use [`SemanticScholarClient::builder`] to
+    /// handle that case as an error instead.
+    pub fn new(api_key: Option<&str>) -> Self {
+        let mut builder = Self::builder();
+        // Keys that are not valid header values are ignored rather than rejected.
+        if let Some(key) = api_key.filter(|k| reqwest::header::HeaderValue::from_str(k).is_ok()) {
+            builder = builder.api_key(key);
+        }
+        builder.build().expect("failed to build reqwest client")
+    }
+
+    pub fn builder() -> SemanticScholarClientBuilder {
+        SemanticScholarClientBuilder::default()
+    }
+
+    /// The base URL requests are sent to, without a trailing slash.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// The client-side rate limiter, if one was configured.
+    pub fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.limiter.as_ref()
+    }
+
+    /// The response cache, if one was configured.
+    pub fn cache(&self) -> Option<&ResponseCache> {
+        self.cache.as_ref()
+    }
+
+    /// Low-level GET, see [`SemanticScholarClient::send_json`].
+    pub(crate) async fn get_json(
+        &self,
+        url: &str,
+        params: Vec<(String, String)>,
+    ) -> Result<JsonResponse, Error> {
+        self.send_json(reqwest::Method::GET, url, params, None).await
+    }
+
+    /// Low-level POST with a JSON body, see [`SemanticScholarClient::send_json`].
+    pub(crate) async fn post_json(
+        &self,
+        url: &str,
+        params: Vec<(String, String)>,
+        body: &serde_json::Value,
+    ) -> Result<JsonResponse, Error> {
+        self.send_json(reqwest::Method::POST, url, params, Some(body))
+            .await
+    }
+
+    /// Answer from fixtures or the response cache if possible, otherwise send the
+    /// request and record or cache a successful response.
+    async fn send_json(
+        &self,
+        method: reqwest::Method,
+        url: &str,
+        params: Vec<(String, String)>,
+        body: Option<&serde_json::Value>,
+    ) -> Result<JsonResponse, Error> {
+        let (fixtures, cache) = match (&self.fixtures, &self.cache) {
+            (None, None) => return self.send_uncached(method, url, params, body).await,
+            pair => pair,
+        };
+        let key = CacheKey {
+            method: method.as_str(),
+            path: url.strip_prefix(self.base_url.as_str()).unwrap_or(url),
+            params: &params,
+            body,
+        };
+
+        if let Some(fixtures) = fixtures {
+            return match fixtures.mode() {
+                FixtureMode::Replay => Ok(JsonResponse {
+                    value: fixtures.load(&key).await?,
+                    context: self.request_context(&method, url, None),
+                }),
+                FixtureMode::Record => {
+                    let resp = self
+                        .send_uncached(method.clone(), url, params.clone(), body)
+                        .await?;
+                    fixtures.save(&key, &resp.value).await?;
+                    Ok(resp)
+                }
+            };
+        }
+
+        let Some(cache) = cache else {
+            return self.send_uncached(method, url, params, body).await;
+        };
+        if let Some(hit) = cache.get(&key).await {
+            tracing::debug!(path = key.path, "Semantic Scholar cache hit");
+            return Ok(JsonResponse {
+                value: hit,
+                context: self.request_context(&method, url, None),
+            });
+        }
+        if cache.current_mode() == CacheMode::Offline {
+            return Err(Error::CacheMiss(key.path.to_string()));
+        }
+        let resp = self
+            .send_uncached(method.clone(), url, params.clone(), body)
+            .await?;
+        cache.put(&key, &resp.value).await;
+        Ok(resp)
     }
 
-    /// Low-level GET with exponential-backoff retry on 429.
-    async fn get_json(
+    /// Send a request with retry on 429, transient 5xx and connect errors/timeouts.
+    ///
+    /// Honors `Retry-After` when the server sends it; otherwise backs off per the
+    /// client's [`RetryPolicy`].
+    async fn send_uncached(
         &self,
+        method: reqwest::Method,
         url: &str,
         params: Vec<(String, String)>,
-    ) -> Result<serde_json::Value, Error> {
+        body: Option<&serde_json::Value>,
+    ) -> Result<JsonResponse, Error> {
         let mut retries = 0u32;
         loop {
-            let resp = self.http.get(url).query(&params).send().await?;
+            if let Some(limiter) = &self.limiter {
+                limiter.acquire().await;
+            }
+            let mut req = self.http.request(method.clone(), url).query(&params);
+            if let Some(body) = body {
+                req = req.json(body);
+            }
+            let resp = match req.send().await {
+                Ok(resp) => resp,
+                Err(e)
+                    if retries < self.retry.max_retries && self.retry.should_retry_error(&e) =>
+                {
+                    let wait = self.retry.delay(retries, None);
+                    tracing::warn!(
+                        retries,
+                        wait_secs = wait.as_secs_f64(),
+                        error = %e,
+                        "Semantic Scholar request failed, retrying"
+                    );
+                    sleep(wait).await;
+                    retries += 1;
+                    continue;
+                }
+                Err(e) => {
+                    return Err(Error::Http {
+                        context: Box::new(self.request_context(&method, url, None)),
+                        source: e,
+                    })
+                }
+            };
             let status = resp.status();
+            let context = self.request_context(&method, url, Some(resp.headers()));
 
-            if status.as_u16() == 429 {
-                if retries >= MAX_RETRIES {
-                    return Err(Error::RateLimited {
-                        retry_after: 2u64.pow(retries),
-                    });
+            if self.retry.should_retry_status(status) {
+                let retry_after = retry::parse_retry_after(resp.headers());
+                if retries >= self.retry.max_retries {
+                    if status.as_u16() == 429 {
+                        let wait = retry_after.unwrap_or_else(|| self.retry.backoff(retries));
+                        return Err(Error::RateLimited {
+                            context: Box::new(context),
+                            retry_after: wait.as_secs(),
+                        });
+                    }
+                    let body = resp.text().await.unwrap_or_default();
+                    return Err(Error::from_status(context, status.as_u16(), &body));
                 }
-                let wait_secs = 2u64.pow(retries);
+                let wait = self.retry.delay(retries, retry_after);
                 tracing::warn!(
                     retries,
-                    wait_secs,
-                    "Semantic Scholar rate limited (429), backing off"
+                    status = status.as_u16(),
+                    wait_secs = wait.as_secs_f64(),
+                    "Semantic Scholar transient error, backing off"
                 );
-                sleep(Duration::from_secs(wait_secs)).await;
+                sleep(wait).await;
                 retries += 1;
                 continue;
             }
 
             if !status.is_success() {
-                let message = resp.text().await.unwrap_or_default();
-                return Err(Error::Api {
-                    status: status.as_u16(),
-                    message,
-                });
+                let body = resp.text().await.unwrap_or_default();
+                return Err(Error::from_status(context, status.as_u16(), &body));
             }
 
-            return Ok(resp.json().await?);
+            return match resp.json().await {
+                Ok(value) => Ok(JsonResponse { value, context }),
+                Err(source) => Err(Error::Http {
+                    context: Box::new(context),
+                    source,
+                }),
+            };
+        }
+    }
+
+    /// Describe a request for error reporting, with the gateway's request ID when
+    /// response headers are available.
+    fn request_context(
+        &self,
+        method: &reqwest::Method,
+        url: &str,
+        headers: Option<&reqwest::header::HeaderMap>,
+    ) -> RequestContext {
+        let request_id = headers.and_then(|h| {
+            ["x-amzn-requestid", "x-request-id"]
+                .iter()
+                .find_map(|name| h.get(*name)?.to_str().ok())
+                .map(str::to_string)
+        });
+        RequestContext {
+            method: method.to_string(),
+            path: url.strip_prefix(self.base_url.as_str()).unwrap_or(url).to_string(),
+            request_id,
         }
     }
 
     /// **Bulk keyword search** — efficient large-scale discovery, up to 10M results.
     ///
-    /// Supports advanced query syntax: `"exact phrase"`, `+must`, `-exclude`, `term1 | term2`.
+    /// Supports advanced query syntax: `"exact phrase"`, `+must`, `-exclude`, `term1 | term2`,
+    /// `prefix*`; build it with [`BulkQuery`](crate::query::BulkQuery) to get the quoting right.
     ///
     /// # Arguments
     /// * `query` — search string
-    /// * `fields` — comma-separated fields (see [`crate::types::PAPER_FIELDS_FULL`])
-    /// * `year` — year filter: `"2023"`, `"2020-2025"`, `"2020-"`, `"-2023"`
-    /// * `min_citations` — only return papers with at least this many citations
+    /// * `fields` — a [`FieldSet`] for [`Endpoint::BulkSearch`], e.g. [`FieldSet::search`]
+    /// * `filters` — year, date, citation, type, field-of-study, venue and open-access filters
     /// * `sort` — sort key, e.g. `"citationCount:desc"`, `"publicationDate:desc"`, `"paperId:asc"`
     /// * `limit` — results per page (max 1000)
     pub async fn search_bulk(
         &self,
         query: &str,
-        fields: &str,
-        year: Option<&str>,
-        min_citations: Option<u32>,
+        fields: &FieldSet,
+        filters: &SearchFilters,
         sort: Option<&str>,
         limit: u32,
     ) -> Result<BulkSearchResponse, Error> {
-        let url = format!("{BASE_URL}/graph/v1/paper/search/bulk");
+        let url = format!("{}/graph/v1/paper/search/bulk", self.base_url);
+        let fields = fields.value_for(Endpoint::BulkSearch)?;
+        let params = bulk_search_params(query, fields, filters, sort, limit);
+        self.get_json(&url, params).await?.decode()
+    }
+
+    /// **Relevance-ranked search** — richer ranking signal, max 1000 results.
+    ///
+    /// Use for precise targeted queries where ranking quality matters more than scale.
+    /// Takes the same filters as [`search_bulk`](Self::search_bulk) but no sort order
+    /// and plain-text queries only. Page with `offset`/`next`, or use
+    /// [`search_stream`](Self::search_stream).
+    ///
+    /// # Arguments
+    /// * `query` — plain-text search string
+    /// * `fields` — a [`FieldSet`] for [`Endpoint::Search`], e.g. [`FieldSet::search`]
+    /// * `filters` — year, date, citation, type, field-of-study, venue and open-access filters
+    /// * `limit` — results per page (max [`SEARCH_PAGE_LIMIT`])
+    /// * `offset` — index of the first result; `offset + limit` may not exceed [`SEARCH_RESULT_LIMIT`]
+    pub async fn search(
+        &self,
+        query: &str,
+        fields: &FieldSet,
+        filters: &SearchFilters,
+        limit: u32,
+        offset: u32,
+    ) -> Result<SearchResponse, Error> {
+        let url = format!("{}/graph/v1/paper/search", self.base_url);
+        let fields = fields.value_for(Endpoint::Search)?;
         let mut params = vec![
             ("query".into(), query.to_string()),
             ("fields".into(), fields.to_string()),
             ("limit".into(), limit.to_string()),
+            ("offset".into(), offset.to_string()),
+        ];
+        filters.push_params(&mut params);
+        self.get_json(&url, params).await?.decode()
+    }
+
+    /// **Snippet search** — passages from paper titles, abstracts and full text that
+    /// best match `query`, each with its section and character offsets.
+    ///
+    /// Use it to ground claims (effect sizes, protocols, outcome measures) in the text
+    /// that states them rather than in a truncated abstract.
+    ///
+    /// # Arguments
+    /// * `query` — plain-text query; the advanced bulk syntax is not supported
+    /// * `paper_ids` — only search inside these papers (the API accepts up to 100); empty searches everything
+    /// * `year` — year filter: `"2023"`, `"2020-2025"`, `"2020-"`, `"-2023"`
+    /// * `min_citations` — only search papers with at least this many citations
+    /// * `limit` — number of snippets (max 1000)
+    pub async fn search_snippets(
+        &self,
+        query: &str,
+        paper_ids: &[PaperId],
+        year: Option<&str>,
+        min_citations: Option<u32>,
+        limit: u32,
+    ) -> Result<SnippetSearchResponse, Error> {
+        let url = format!("{}/graph/v1/snippet/search", self.base_url);
+        let mut params = vec![
+            ("query".into(), query.to_string()),
+            ("limit".into(), limit.to_string()),
         ];
+        if !paper_ids.is_empty() {
+            let ids: Vec<String> = paper_ids.iter().map(PaperId::to_api_string).collect();
+            params.push(("paperIds".into(), ids.join(",")));
+        }
         if let Some(y) = year {
             params.push(("year".into(), y.to_string()));
         }
         if let Some(mc) = min_citations {
             params.push(("minCitationCount".into(), mc.to_string()));
         }
-        if let Some(s) = sort {
-            params.push(("sort".into(), s.to_string()));
-        }
-        let val = self.get_json(&url, params).await?;
-        Ok(serde_json::from_value(val)?)
+        self.get_json(&url, params).await?.decode()
     }
 
-    /// **Relevance-ranked search** — richer ranking signal, max 1000 results.
+    /// Resolve a title to the single closest paper record.
     ///
-    /// Use for precise targeted queries where ranking quality matters more than scale.
-    pub async fn search(
+    /// Useful for checking that a paper cited by title (e.g. in an LLM-written report)
+    /// exists. Returns `Ok(None)` when the API finds no match.
+    pub async fn match_paper_by_title(
         &self,
-        query: &str,
-        fields: &str,
-        limit: u32,
-        offset: u32,
-    ) -> Result<SearchResponse, Error> {
-        let url = format!("{BASE_URL}/graph/v1/paper/search");
+        title: &str,
+        fields: &FieldSet,
+    ) -> Result<Option<PaperMatch>, Error> {
+        let url = format!("{}/graph/v1/paper/search/match", self.base_url);
+        let fields = fields.value_for(Endpoint::TitleMatch)?;
         let params = vec![
-            ("query".into(), query.to_string()),
+            ("query".into(), title.to_string()),
             ("fields".into(), fields.to_string()),
-            ("limit".into(), limit.to_string()),
-            ("offset".into(), offset.to_string()),
         ];
-        let val = self.get_json(&url, params).await?;
-        Ok(serde_json::from_value(val)?)
+        let resp: PaperMatchResponse = match self.get_json(&url, params).await {
+            Ok(resp) => resp.decode()?,
+            Err(Error::NotFound { .. }) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        Ok(resp.data.into_iter().next())
+    }
+
+    /// Title suggestions for a partial query, for search-as-you-type.
+    ///
+    /// Matches carry only an ID, title and author/year string; the API considers the
+    /// first 100 characters of `query`.
+    pub async fn autocomplete(&self, query: &str) -> Result<AutocompleteResponse, Error> {
+        let url = format!("{}/graph/v1/paper/autocomplete", self.base_url);
+        let params = vec![("query".into(), query.to_string())];
+        self.get_json(&url, params).await?.decode()
     }
 
     /// Get full details for a single paper.
     ///
-    /// `paper_id` accepts any format: S2PaperId, `DOI:10.xxx/yyy`, `arXiv:1705.10311`,
-    /// `PMID:12345`, `ACL:P19-1002`, etc.
-    pub async fn get_paper(&self, paper_id: &str, fields: &str) -> Result<Paper, Error> {
-        let url = format!("{BASE_URL}/graph/v1/paper/{paper_id}");
+    /// Parse `paper_id` from any supported form (S2 ID, DOI, arXiv, PMID, ...) with
+    /// [`PaperId::from_str`](std::str::FromStr).
+    pub async fn get_paper(&self, paper_id: &PaperId, fields: &FieldSet) -> Result<Paper, Error> {
+        let url = format!(
+            "{}/graph/v1/paper/{}",
+            self.base_url,
+            paper_id.path_segment()
+        );
+        let fields = fields.value_for(Endpoint::PaperDetail)?;
         let params = vec![("fields".into(), fields.to_string())];
-        let val = self.get_json(&url, params).await?;
-        Ok(serde_json::from_value(val)?)
+        self.get_json(&url, params).await?.decode()
+    }
+
+    /// Details for many papers at once via `POST /graph/v1/paper/batch`.
+    ///
+    /// The result is aligned with `paper_ids`: IDs the API could not resolve come back
+    /// as `None`. Inputs above the endpoint's 500-ID limit are split into several
+    /// requests transparently.
+    pub async fn get_papers_batch(
+        &self,
+        paper_ids: &[PaperId],
+        fields: &FieldSet,
+    ) -> Result<Vec<Option<Paper>>, Error> {
+        let url = format!("{}/graph/v1/paper/batch", self.base_url);
+        let fields = fields.value_for(Endpoint::PaperBatch)?;
+        let mut papers = Vec::with_capacity(paper_ids.len());
+        for chunk in paper_ids.chunks(BATCH_LIMIT) {
+            let params = vec![("fields".into(), fields.to_string())];
+            let body = serde_json::json!({ "ids": chunk });
+            let page: Vec<Option<Paper>> = self.post_json(&url, params, &body).await?.decode()?;
+            if page.len() != chunk.len() {
+                return Err(Error::Api {
+                    context: Box::new(self.request_context(&reqwest::Method::POST, &url, None)),
+                    status: 200,
+                    message: format!(
+                        "batch endpoint returned {} entries for {} ids",
+                        page.len(),
+                        chunk.len()
+                    ),
+                });
+            }
+            papers.extend(page);
+        }
+        Ok(papers)
     }
 
     /// Papers that **cite** this paper (forward citations).
     ///
-    /// `fields` controls nested paper attributes returned inside each `citingPaper`.
+    /// `fields` (for [`Endpoint::Citations`]) controls nested paper attributes returned
+    /// inside each `citingPaper`; see [`FieldSet::with_edge_fields`] for the edge attributes.
+    /// Pass the previous page's `next` as `offset` to continue, or use
+    /// [`SemanticScholarClient::citations_stream`].
     pub async fn get_citations(
         &self,
-        paper_id: &str,
-        fields: &str,
+        paper_id: &PaperId,
+        fields: &FieldSet,
         limit: u32,
+        offset: u32,
     ) -> Result<CitationsResponse, Error> {
-        let url = format!("{BASE_URL}/graph/v1/paper/{paper_id}/citations");
+        let url = format!(
+            "{}/graph/v1/paper/{}/citations",
+            self.base_url,
+            paper_id.path_segment()
+        );
+        let fields = fields.value_for(Endpoint::Citations)?;
         let params = vec![
             ("fields".into(), fields.to_string()),
             ("limit".into(), limit.to_string()),
+            ("offset".into(), offset.to_string()),
         ];
-        let val = self.get_json(&url, params).await?;
-        Ok(serde_json::from_value(val)?)
+        self.get_json(&url, params).await?.decode()
     }
 
     /// Papers this paper **references** (backward citations).
+    ///
+    /// Paginates like [`SemanticScholarClient::get_citations`].
     pub async fn get_references(
         &self,
-        paper_id: &str,
-        fields: &str,
+        paper_id: &PaperId,
+        fields: &FieldSet,
         limit: u32,
+        offset: u32,
     ) -> Result<ReferencesResponse, Error> {
-        let url = format!("{BASE_URL}/graph/v1/paper/{paper_id}/references");
+        let url = format!(
+            "{}/graph/v1/paper/{}/references",
+            self.base_url,
+            paper_id.path_segment()
+        );
+        let fields = fields.value_for(Endpoint::References)?;
         let params = vec![
             ("fields".into(), fields.to_string()),
             ("limit".into(), limit.to_string()),
+            ("offset".into(), offset.to_string()),
         ];
-        let val = self.get_json(&url, params).await?;
-        Ok(serde_json::from_value(val)?)
+        self.get_json(&url, params).await?.decode()
     }
 
     /// Papers **similar** to the given paper (Recommendations API).
@@ -189,17 +653,126 @@ impl SemanticScholarClient {
     /// Uses SPECTER2 embeddings and citation graph signals under the hood.
     pub async fn get_recommendations(
         &self,
-        paper_id: &str,
-        fields: &str,
+        paper_id: &PaperId,
+        fields: &FieldSet,
+        limit: u32,
+    ) -> Result<RecommendationsResponse, Error> {
+        let url = format!(
+            "{}/recommendations/v1/papers/forpaper/{}",
+            self.base_url,
+            paper_id.path_segment()
+        );
+        let fields = fields.value_for(Endpoint::Recommendations)?;
+        let params = vec![
+            ("fields".into(), fields.to_string()),
+            ("limit".into(), limit.to_string()),
+        ];
+        self.get_json(&url, params).await?.decode()
+    }
+
+    /// Papers similar to a **set** of positive examples and unlike a set of negative ones.
+    ///
+    /// Uses `POST /recommendations/v1/papers/`, e.g. "more like these three TF-CBT RCTs,
+    /// less like this adult PTSD study". `negative_ids` may be empty.
+    pub async fn get_recommendations_multi(
+        &self,
+        positive_ids: &[PaperId],
+        negative_ids: &[PaperId],
+        fields: &FieldSet,
         limit: u32,
     ) -> Result<RecommendationsResponse, Error> {
-        let url =
-            format!("{BASE_URL}/recommendations/v1/papers/forpaper/{paper_id}");
+        let url = format!("{}/recommendations/v1/papers/", self.base_url);
+        let fields = fields.value_for(Endpoint::Recommendations)?;
+        let params = vec![
+            ("fields".into(), fields.to_string()),
+            ("limit".into(), limit.to_string()),
+        ];
+        let body = serde_json::json!({
+            "positivePaperIds": positive_ids,
+            "negativePaperIds": negative_ids,
+        });
+        self.post_json(&url, params, &body).await?.decode()
+    }
+
+    /// Search authors by name.
+    ///
+    /// `fields` controls which author attributes come back, e.g.
+    /// [`AuthorFieldSet::default`] for [`crate::types::AUTHOR_FIELDS`].
+    pub async fn search_authors(
+        &self,
+        query: &str,
+        fields: &AuthorFieldSet,
+        limit: u32,
+        offset: u32,
+    ) -> Result<AuthorSearchResponse, Error> {
+        let url = format!("{}/graph/v1/author/search", self.base_url);
+        let params = vec![
+            ("query".into(), query.to_string()),
+            ("fields".into(), fields.to_string()),
+            ("limit".into(), limit.to_string()),
+            ("offset".into(), offset.to_string()),
+        ];
+        self.get_json(&url, params).await?.decode()
+    }
+
+    /// Profile and bibliometrics (h-index, paper and citation counts, affiliations)
+    /// for a single author.
+    pub async fn get_author(
+        &self,
+        author_id: &str,
+        fields: &AuthorFieldSet,
+    ) -> Result<Author, Error> {
+        let url = format!(
+            "{}/graph/v1/author/{}",
+            self.base_url,
+            encode_path_segment(author_id)
+        );
+        let params = vec![("fields".into(), fields.to_string())];
+        self.get_json(&url, params).await?.decode()
+    }
+
+    /// Papers written by an author.
+    ///
+    /// Pass the previous page's `next` as `offset` to continue, or use
+    /// [`SemanticScholarClient::author_papers_stream`].
+    pub async fn get_author_papers(
+        &self,
+        author_id: &str,
+        fields: &FieldSet,
+        limit: u32,
+        offset: u32,
+    ) -> Result<AuthorPapersResponse, Error> {
+        let url = format!(
+            "{}/graph/v1/author/{}/papers",
+            self.base_url,
+            encode_path_segment(author_id)
+        );
+        let fields = fields.value_for(Endpoint::AuthorPapers)?;
         let params = vec![
             ("fields".into(), fields.to_string()),
             ("limit".into(), limit.to_string()),
+            ("offset".into(), offset.to_string()),
         ];
-        let val = self.get_json(&url, params).await?;
-        Ok(serde_json::from_value(val)?)
+        self.get_json(&url, params).await?.decode()
+    }
+}
+
+/// Query parameters shared by [`SemanticScholarClient::search_bulk`] and its stream.
+pub(crate) fn bulk_search_params(
+    query: &str,
+    fields: &str,
+    filters: &SearchFilters,
+    sort: Option<&str>,
+    limit: u32,
+) -> Vec<(String, String)> {
+    let mut params = vec![
+        ("query".into(), query.to_string()),
+        ("fields".into(), fields.to_string()),
+        ("limit".into(), limit.to_string()),
+    ];
+    filters.push_params(&mut params);
+    if let Some(s) = sort {
+        params.push(("sort".into(), s.to_string()));
     }
+    params
 }
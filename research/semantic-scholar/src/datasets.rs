@@ -0,0 +1,343 @@
+//! Datasets API client and readers for the downloaded shards.
+//!
+//! Semantic Scholar publishes the whole corpus as weekly releases of gzipped JSONL
+//! shards. [`SemanticScholarClient::list_releases`] and friends return the release
+//! metadata and the signed, short-lived URLs of each dataset's files; the `papers`,
+//! `abstracts` and `tldrs` shards can then be read into [`Paper`]s with
+//! [`read_papers`]. Records from the three datasets share `corpusid`, so abstracts and
+//! TLDRs can be attached to papers with [`ShardRecord::merge_into`].
+//!
+//! Fetching file URLs and diffs requires an API key.
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Lines},
+    marker::PhantomData,
+    path::Path,
+};
+
+use flate2::bufread::MultiGzDecoder;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    client::SemanticScholarClient,
+    error::Error,
+    paper_id::encode_path_segment,
+    types::{Author, ExternalIds, Journal, Paper, S2FieldOfStudy, Tldr},
+};
+
+/// Release ID that always resolves to the newest release.
+pub const LATEST_RELEASE: &str = "latest";
+
+/// A release and the datasets it contains, from `/datasets/v1/release/{release_id}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Release {
+    pub release_id: String,
+    #[serde(rename = "README")]
+    pub readme: Option<String>,
+    pub datasets: Vec<DatasetSummary>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatasetSummary {
+    /// e.g. `papers`, `abstracts`, `tldrs`, `citations`, `s2orc`.
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(rename = "README")]
+    pub readme: Option<String>,
+}
+
+/// One dataset in a release with signed download URLs for its shards.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Dataset {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(rename = "README")]
+    pub readme: Option<String>,
+    /// Pre-signed shard URLs; they expire after a few hours.
+    pub files: Vec<String>,
+}
+
+/// Incremental updates that take a dataset from `start_release` to `end_release`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatasetDiffs {
+    pub dataset: String,
+    pub start_release: String,
+    pub end_release: String,
+    /// One entry per consecutive pair of releases, oldest first.
+    pub diffs: Vec<DatasetDiff>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatasetDiff {
+    pub from_release: String,
+    pub to_release: String,
+    /// Shards of records to insert or replace, keyed by `corpusid`.
+    pub update_files: Vec<String>,
+    /// Shards of records to remove, keyed by `corpusid`.
+    pub delete_files: Vec<String>,
+}
+
+impl SemanticScholarClient {
+    /// IDs of every available release (`YYYY-MM-DD`), oldest first.
+    pub async fn list_releases(&self) -> Result<Vec<String>, Error> {
+        let url = format!("{}/datasets/v1/release/", self.base_url());
+        self.get_json(&url, Vec::new()).await?.decode()
+    }
+
+    /// Datasets in a release. Pass [`LATEST_RELEASE`] for the newest one.
+    pub async fn get_release(&self, release_id: &str) -> Result<Release, Error> {
+        let url = format!(
+            "{}/datasets/v1/release/{}",
+            self.base_url(),
+            encode_path_segment(release_id)
+        );
+        self.get_json(&url, Vec::new()).await?.decode()
+    }
+
+    /// Signed download URLs for one dataset of a release. Requires an API key.
+    pub async fn get_dataset(&self, release_id: &str, dataset: &str) -> Result<Dataset, Error> {
+        let url = format!(
+            "{}/datasets/v1/release/{}/dataset/{}",
+            self.base_url(),
+            encode_path_segment(release_id),
+            encode_path_segment(dataset)
+        );
+        self.get_json(&url, Vec::new()).await?.decode()
+    }
+
+    /// Update and delete shards that bring `dataset` from `start_release` up to
+    /// `end_release` (which may be [`LATEST_RELEASE`]). Requires an API key.
+    pub async fn get_dataset_diffs(
+        &self,
+        start_release: &str,
+        end_release: &str,
+        dataset: &str,
+    ) -> Result<DatasetDiffs, Error> {
+        let url = format!(
+            "{}/datasets/v1/diffs/{}/to/{}/{}",
+            self.base_url(),
+            encode_path_segment(start_release),
+            encode_path_segment(end_release),
+            encode_path_segment(dataset)
+        );
+        self.get_json(&url, Vec::new()).await?.decode()
+    }
+}
+
+/// A record type found in a dataset shard.
+pub trait ShardRecord: DeserializeOwned {
+    fn corpus_id(&self) -> u64;
+
+    /// Copy this record's data onto `paper`, which should have the same corpus ID.
+    fn merge_into(self, paper: &mut Paper);
+
+    /// A [`Paper`] holding only this record's data and its corpus ID.
+    fn into_paper(self) -> Paper
+    where
+        Self: Sized,
+    {
+        let mut paper = Paper {
+            external_ids: Some(ExternalIds {
+                corpus_id: Some(self.corpus_id()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        self.merge_into(&mut paper);
+        paper
+    }
+}
+
+/// A line of the `papers` dataset. Field names are lower-case, unlike the Graph API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PaperRecord {
+    pub corpusid: u64,
+    pub externalids: Option<RecordExternalIds>,
+    pub url: Option<String>,
+    pub title: Option<String>,
+    pub authors: Option<Vec<Author>>,
+    pub venue: Option<String>,
+    pub year: Option<u32>,
+    pub referencecount: Option<u64>,
+    pub citationcount: Option<u64>,
+    pub influentialcitationcount: Option<u64>,
+    pub isopenaccess: Option<bool>,
+    pub s2fieldsofstudy: Option<Vec<S2FieldOfStudy>>,
+    pub publicationtypes: Option<Vec<String>>,
+    pub publicationdate: Option<String>,
+    pub journal: Option<Journal>,
+}
+
+/// `externalids` as written in the datasets, where `CorpusId` is a string.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RecordExternalIds {
+    #[serde(rename = "DOI")]
+    pub doi: Option<String>,
+    #[serde(rename = "PubMed")]
+    pub pubmed: Option<String>,
+    #[serde(rename = "PubMedCentral")]
+    pub pubmed_central: Option<String>,
+    #[serde(rename = "ArXiv")]
+    pub arxiv: Option<String>,
+    #[serde(rename = "MAG")]
+    pub mag: Option<String>,
+    #[serde(rename = "ACL")]
+    pub acl: Option<String>,
+    #[serde(rename = "DBLP")]
+    pub dblp: Option<String>,
+}
+
+impl ShardRecord for PaperRecord {
+    fn corpus_id(&self) -> u64 {
+        self.corpusid
+    }
+
+    fn merge_into(self, paper: &mut Paper) {
+        let ext = self.externalids.unwrap_or_default();
+        paper.external_ids = Some(ExternalIds {
+            doi: ext.doi,
+            pubmed: ext.pubmed,
+            pubmed_central: ext.pubmed_central,
+            arxiv: ext.arxiv,
+            mag: ext.mag,
+            acl: ext.acl,
+            dblp: ext.dblp,
+            corpus_id: Some(self.corpusid),
+        });
+        // The Graph API derives `fieldsOfStudy` from the externally sourced categories.
+        paper.fields_of_study = self.s2fieldsofstudy.as_ref().map(|fields| {
+            let mut names: Vec<String> = Vec::new();
+            let external = fields
+                .iter()
+                .filter(|f| f.source.as_deref() == Some("external"))
+                .filter_map(|f| f.category.clone());
+            for name in external {
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+            names
+        });
+        paper.url = self.url;
+        paper.title = self.title;
+        paper.authors = self.authors;
+        paper.venue = self.venue;
+        paper.year = self.year;
+        paper.reference_count = self.referencecount;
+        paper.citation_count = self.citationcount;
+        paper.influential_citation_count = self.influentialcitationcount;
+        paper.is_open_access = self.isopenaccess;
+        paper.s2_fields_of_study = self.s2fieldsofstudy;
+        paper.publication_types = self.publicationtypes;
+        paper.publication_date = self.publicationdate;
+        paper.journal = self.journal;
+    }
+}
+
+/// A line of the `abstracts` dataset.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AbstractRecord {
+    pub corpusid: u64,
+    #[serde(rename = "abstract")]
+    pub abstract_text: Option<String>,
+}
+
+impl ShardRecord for AbstractRecord {
+    fn corpus_id(&self) -> u64 {
+        self.corpusid
+    }
+
+    fn merge_into(self, paper: &mut Paper) {
+        paper.abstract_text = self.abstract_text;
+    }
+}
+
+/// A line of the `tldrs` dataset.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TldrRecord {
+    pub corpusid: u64,
+    pub model: Option<String>,
+    pub text: Option<String>,
+}
+
+impl ShardRecord for TldrRecord {
+    fn corpus_id(&self) -> u64 {
+        self.corpusid
+    }
+
+    fn merge_into(self, paper: &mut Paper) {
+        paper.tldr = Some(Tldr {
+            model: self.model,
+            text: self.text,
+        });
+    }
+}
+
+/// Iterator over the records of a JSONL stream, one per non-blank line.
+///
+/// Reading is blocking; wrap large shards in `tokio::task::spawn_blocking`.
+pub struct JsonlReader<T, R> {
+    lines: Lines<R>,
+    source: String,
+    line: usize,
+    record: PhantomData<fn() -> T>,
+}
+
+impl<T: DeserializeOwned, R: BufRead> JsonlReader<T, R> {
+    /// `source` names the stream in error messages, e.g. the shard's path.
+    pub fn new(reader: R, source: impl Into<String>) -> Self {
+        Self {
+            lines: reader.lines(),
+            source: source.into(),
+            line: 0,
+            record: PhantomData,
+        }
+    }
+}
+
+impl<T: DeserializeOwned, R: BufRead> Iterator for JsonlReader<T, R> {
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.line += 1;
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => return Some(Err(self.error(e))),
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            return Some(serde_json::from_str(&line).map_err(|e| self.error(e)));
+        }
+    }
+}
+
+impl<T, R> JsonlReader<T, R> {
+    fn error(&self, e: impl std::fmt::Display) -> Error {
+        Error::Dataset(format!("{}:{}: {e}", self.source, self.line))
+    }
+}
+
+/// Open a downloaded shard, gzipped (as served) or already decompressed.
+pub fn open_shard<T: DeserializeOwned>(
+    path: impl AsRef<Path>,
+) -> Result<JsonlReader<T, Box<dyn BufRead + Send>>, Error> {
+    let path = path.as_ref();
+    let io_err = |e: std::io::Error| Error::Dataset(format!("{}: {e}", path.display()));
+    let mut file = BufReader::new(File::open(path).map_err(io_err)?);
+    let gzipped = file.fill_buf().map_err(io_err)?.starts_with(&[0x1f, 0x8b]);
+    let reader: Box<dyn BufRead + Send> = if gzipped {
+        Box::new(BufReader::new(MultiGzDecoder::new(file)))
+    } else {
+        Box::new(file)
+    };
+    Ok(JsonlReader::new(reader, path.display().to_string()))
+}
+
+/// Read a shard of `R` records as [`Paper`]s, e.g. `read_papers::<PaperRecord>(path)`.
+pub fn read_papers<R: ShardRecord>(
+    path: impl AsRef<Path>,
+) -> Result<impl Iterator<Item = Result<Paper, Error>>, Error> {
+    Ok(open_shard::<R>(path)?.map(|record| record.map(ShardRecord::into_paper)))
+}
@@ -1,16 +1,223 @@
+use std::fmt;
+
 use thiserror::Error;
 
+/// The request a failure belongs to, e.g. `GET /graph/v1/paper/DOI:10.1037/a0012345`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestContext {
+    pub method: String,
+    /// URL path relative to the client's base URL.
+    pub path: String,
+    /// Request ID from the API gateway (`x-amzn-requestid`), quote it when reporting
+    /// problems to Semantic Scholar.
+    pub request_id: Option<String>,
+}
+
+impl fmt::Display for RequestContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.method, self.path)?;
+        if let Some(id) = &self.request_id {
+            write!(f, " (request id {id})")?;
+        }
+        Ok(())
+    }
+}
+
+/// Errors from the client. Failed requests carry a [`RequestContext`] and, for HTTP
+/// error statuses, the message Semantic Scholar sent back.
 #[derive(Debug, Error)]
 pub enum Error {
-    #[error("HTTP error: {0}")]
-    Http(#[from] reqwest::Error),
+    #[error("{context}: HTTP error: {source}")]
+    Http {
+        context: Box<RequestContext>,
+        #[source]
+        source: reqwest::Error,
+    },
+
+    /// 404 — unknown paper or author ID, or no title match.
+    #[error("{context}: not found: {message}")]
+    NotFound {
+        context: Box<RequestContext>,
+        message: String,
+    },
+
+    /// 400 — invalid parameters or field names.
+    #[error("{context}: bad request: {message}")]
+    BadRequest {
+        context: Box<RequestContext>,
+        message: String,
+    },
+
+    /// 401 or 403 — missing, invalid or insufficient API key.
+    #[error("{context}: unauthorized ({status}): {message}")]
+    Unauthorized {
+        context: Box<RequestContext>,
+        status: u16,
+        message: String,
+    },
+
+    /// 5xx, after any retries the policy allowed.
+    #[error("{context}: server error {status}: {message}")]
+    ServerError {
+        context: Box<RequestContext>,
+        status: u16,
+        message: String,
+    },
+
+    /// Any other unexpected status or response shape.
+    #[error("{context}: API error {status}: {message}")]
+    Api {
+        context: Box<RequestContext>,
+        status: u16,
+        message: String,
+    },
+
+    #[error("{context}: rate limited (429): retry after {retry_after}s")]
+    RateLimited {
+        context: Box<RequestContext>,
+        retry_after: u64,
+    },
+
+    /// A successful response that doesn't match the expected schema.
+    #[error("{context}: unexpected response shape: {source}")]
+    Json {
+        context: Box<RequestContext>,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("Invalid client configuration: {0}")]
+    Config(String),
+
+    #[error("Invalid field selection: {0}")]
+    InvalidFields(String),
 
-    #[error("API error {status}: {message}")]
-    Api { status: u16, message: String },
+    #[error("Invalid search filter: {0}")]
+    InvalidFilter(String),
 
-    #[error("Rate limited (429): retry after {retry_after}s")]
-    RateLimited { retry_after: u64 },
+    #[error("Invalid export format: {0}")]
+    InvalidFormat(String),
+
+    #[error("Unrecognised paper ID `{0}`")]
+    InvalidPaperId(String),
+
+    #[error("Ambiguous paper ID `{0}`: add a prefix such as PMID:, CorpusId: or MAG:")]
+    AmbiguousPaperId(String),
+
+    #[error("Response cache error: {0}")]
+    Cache(String),
+
+    #[error("Not in the offline cache: {0}")]
+    CacheMiss(String),
+
+    #[error("Fixture error: {0}")]
+    Fixture(String),
+
+    #[error("Dataset error: {0}")]
+    Dataset(String),
+}
+
+impl Error {
+    /// Classify a non-success response by status.
+    pub(crate) fn from_status(context: RequestContext, status: u16, body: &str) -> Self {
+        let context = Box::new(context);
+        let mut message = api_message(body);
+        if message.is_empty() {
+            let reason = reqwest::StatusCode::from_u16(status)
+                .ok()
+                .and_then(|s| s.canonical_reason());
+            message = reason.unwrap_or("no details").to_string();
+        }
+        match status {
+            400 => Self::BadRequest { context, message },
+            401 | 403 => Self::Unauthorized {
+                context,
+                status,
+                message,
+            },
+            404 => Self::NotFound { context, message },
+            500..=599 => Self::ServerError {
+                context,
+                status,
+                message,
+            },
+            _ => Self::Api {
+                context,
+                status,
+                message,
+            },
+        }
+    }
+
+    /// The request that failed, if the error came from one.
+    pub fn context(&self) -> Option<&RequestContext> {
+        match self {
+            Self::Http { context, .. }
+            | Self::NotFound { context, .. }
+            | Self::BadRequest { context, .. }
+            | Self::Unauthorized { context, .. }
+            | Self::ServerError { context, .. }
+            | Self::Api { context, .. }
+            | Self::RateLimited { context, .. }
+            | Self::Json { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+
+    /// HTTP status of the failed response, if there was one.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Self::NotFound { .. } => Some(404),
+            Self::BadRequest { .. } => Some(400),
+            Self::RateLimited { .. } => Some(429),
+            Self::Unauthorized { status, .. }
+            | Self::ServerError { status, .. }
+            | Self::Api { status, .. } => Some(*status),
+            Self::Http { source, .. } => source.status().map(|s| s.as_u16()),
+            _ => None,
+        }
+    }
+
+    /// Semantic Scholar's explanation of a failed response, if it gave one.
+    pub fn api_message(&self) -> Option<&str> {
+        match self {
+            Self::NotFound { message, .. }
+            | Self::BadRequest { message, .. }
+            | Self::Unauthorized { message, .. }
+            | Self::ServerError { message, .. }
+            | Self::Api { message, .. } => Some(message),
+            _ => None,
+        }
+    }
+
+    /// Whether sending the same request later may succeed: rate limiting, transient
+    /// server errors, timeouts and connection failures.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::RateLimited { .. } => true,
+            Self::ServerError { status, .. } => matches!(status, 500 | 502 | 503 | 504),
+            Self::Http { source, .. } => source.is_timeout() || source.is_connect(),
+            _ => false,
+        }
+    }
+}
 
-    #[error("Deserialization error: {0}")]
-    Json(#[from] serde_json::Error),
+/// Pull the human-readable message out of an error body. Semantic Scholar answers
+/// `{"error": "..."}` and its gateway `{"message": "..."}`; anything else is kept as
+/// text, shortened.
+fn api_message(body: &str) -> String {
+    const MAX_CHARS: usize = 500;
+    if let Ok(serde_json::Value::Object(obj)) = serde_json::from_str(body) {
+        for key in ["error", "message"] {
+            if let Some(msg) = obj.get(key).and_then(|m| m.as_str()) {
+                return msg.to_string();
+            }
+        }
+    }
+    let body = body.trim();
+    if body.chars().count() > MAX_CHARS {
+        body.chars().take(MAX_CHARS).collect::<String>() + "…"
+    } else {
+        body.to_string()
+    }
 }
@@ -0,0 +1,569 @@
+//! Render papers for reference managers and spreadsheets.
+//!
+//! [`ExportFormat`] covers the formats Zotero, EndNote and Mendeley import (BibTeX,
+//! RIS, CSL-JSON) plus CSV and Markdown tables for reading results. Every format
+//! works with whatever fields were requested: missing metadata is left out rather
+//! than written as empty tags.
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use serde_json::{json, Map, Value};
+
+use crate::{error::Error, types::Paper};
+
+/// An export format for a list of papers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExportFormat {
+    Bibtex,
+    Ris,
+    CslJson,
+    Csv,
+    Markdown,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 5] = [
+        Self::Bibtex,
+        Self::Ris,
+        Self::CslJson,
+        Self::Csv,
+        Self::Markdown,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Bibtex => "bibtex",
+            Self::Ris => "ris",
+            Self::CslJson => "csl-json",
+            Self::Csv => "csv",
+            Self::Markdown => "markdown",
+        }
+    }
+
+    /// Render `papers` in this format.
+    pub fn render(self, papers: &[Paper]) -> String {
+        match self {
+            Self::Bibtex => to_bibtex(papers),
+            Self::Ris => to_ris(papers),
+            Self::CslJson => {
+                let mut out = serde_json::to_string_pretty(&to_csl_json(papers))
+                    .expect("CSL-JSON is plain JSON");
+                out.push('\n');
+                out
+            }
+            Self::Csv => Table::papers(papers).to_csv(),
+            Self::Markdown => Table::papers(papers).to_markdown(),
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = Error;
+
+    /// Case-insensitive; `csl`, `csljson`, `md` and `bib` are accepted too.
+    fn from_str(s: &str) -> Result<Self, Error> {
+        match s.to_ascii_lowercase().as_str() {
+            "bibtex" | "bib" => Ok(Self::Bibtex),
+            "ris" => Ok(Self::Ris),
+            "csl-json" | "csljson" | "csl" => Ok(Self::CslJson),
+            "csv" => Ok(Self::Csv),
+            "markdown" | "md" => Ok(Self::Markdown),
+            _ => Err(Error::InvalidFormat(format!(
+                "unknown export format `{s}` (expected one of {})",
+                Self::ALL.map(Self::as_str).join(", ")
+            ))),
+        }
+    }
+}
+
+/// Rows of text cells under a header, written as CSV or a Markdown table.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Table {
+    pub header: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new<I, S>(header: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            header: header.into_iter().map(Into::into).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Append a row; missing cells are written empty.
+    pub fn row<I, S>(mut self, cells: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.rows.push(cells.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// One row per paper: ID, title, authors, year, venue, citations, DOI and URL.
+    pub fn papers(papers: &[Paper]) -> Self {
+        let header = [
+            "paper_id",
+            "title",
+            "authors",
+            "year",
+            "venue",
+            "citations",
+            "doi",
+            "url",
+        ];
+        papers.iter().fold(Self::new(header), |table, p| {
+            table.row([
+                p.paper_id.clone().unwrap_or_default(),
+                p.title.clone().unwrap_or_default(),
+                author_names(p).join("; "),
+                p.year.map(|y| y.to_string()).unwrap_or_default(),
+                venue(p).unwrap_or_default().to_string(),
+                p.citation_count.map(|c| c.to_string()).unwrap_or_default(),
+                p.doi().unwrap_or_default().to_string(),
+                p.url.clone().unwrap_or_default(),
+            ])
+        })
+    }
+
+    /// RFC 4180 CSV with CRLF line endings, quoting cells only where needed.
+    pub fn to_csv(&self) -> String {
+        let line = |cells: &[String]| {
+            let cells: Vec<String> = (0..self.header.len())
+                .map(|i| csv_cell(cells.get(i).map_or("", String::as_str)))
+                .collect();
+            cells.join(",") + "\r\n"
+        };
+        std::iter::once(line(&self.header))
+            .chain(self.rows.iter().map(|r| line(r)))
+            .collect()
+    }
+
+    /// A GitHub-flavoured Markdown table.
+    pub fn to_markdown(&self) -> String {
+        let line = |cells: &[String]| {
+            let cells: Vec<String> = (0..self.header.len())
+                .map(|i| markdown_cell(cells.get(i).map_or("", String::as_str)))
+                .collect();
+            format!("| {} |\n", cells.join(" | "))
+        };
+        let rule = format!("|{}\n", " --- |".repeat(self.header.len()));
+        line(&self.header) + &rule + &self.rows.iter().map(|r| line(r)).collect::<String>()
+    }
+}
+
+fn csv_cell(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+fn markdown_cell(cell: &str) -> String {
+    cell.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace('|', "\\|")
+}
+
+/// BibTeX entries keyed `surnameYEARfirstword`, with `a`, `b`, … appended to repeats.
+pub fn to_bibtex(papers: &[Paper]) -> String {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let mut out = String::new();
+    for paper in papers {
+        let base = cite_key(paper);
+        let n = seen.entry(base.clone()).or_default();
+        let key = match *n {
+            0 => base,
+            n => format!("{base}{}", suffix(n)),
+        };
+        *n += 1;
+
+        let kind = Kind::of(paper);
+        let mut fields: Vec<(&str, String)> = Vec::new();
+        if let Some(title) = &paper.title {
+            fields.push(("title", bibtex_escape(title)));
+        }
+        let authors: Vec<String> = author_names(paper)
+            .iter()
+            .map(|name| match split_name(name) {
+                (family, Some(given)) => format!("{family}, {given}"),
+                (family, None) => family.to_string(),
+            })
+            .collect();
+        if !authors.is_empty() {
+            fields.push(("author", bibtex_escape(&authors.join(" and "))));
+        }
+        if let Some(year) = paper.year {
+            fields.push(("year", year.to_string()));
+        }
+        if let Some(venue) = venue(paper) {
+            let container = match kind {
+                Kind::Conference | Kind::Chapter => "booktitle",
+                _ => "journal",
+            };
+            fields.push((container, bibtex_escape(venue)));
+        }
+        if let Some(journal) = &paper.journal {
+            if let Some(volume) = non_empty(&journal.volume) {
+                fields.push(("volume", bibtex_escape(volume)));
+            }
+            if let Some(pages) = non_empty(&journal.pages) {
+                let pages = match split_pages(pages) {
+                    (start, Some(end)) => format!("{start}--{end}"),
+                    (start, None) => start.to_string(),
+                };
+                fields.push(("pages", bibtex_escape(&pages)));
+            }
+        }
+        if let Some(doi) = paper.doi() {
+            fields.push(("doi", bibtex_verbatim(doi)));
+        }
+        if let Some(url) = &paper.url {
+            fields.push(("url", bibtex_verbatim(url)));
+        }
+        if let Some(ids) = &paper.external_ids {
+            if let Some(arxiv) = &ids.arxiv {
+                fields.push(("eprint", bibtex_verbatim(arxiv)));
+                fields.push(("archiveprefix", "arXiv".into()));
+            }
+            if let Some(pmid) = &ids.pubmed {
+                fields.push(("pmid", bibtex_verbatim(pmid)));
+            }
+        }
+        if let Some(text) = &paper.abstract_text {
+            fields.push(("abstract", bibtex_escape(text)));
+        }
+
+        out.push_str(&format!("@{}{{{key},\n", kind.bibtex()));
+        for (name, value) in fields {
+            out.push_str(&format!("  {name} = {{{value}}},\n"));
+        }
+        out.push_str("}\n\n");
+    }
+    out
+}
+
+/// RIS records, one `TY` … `ER` block per paper.
+pub fn to_ris(papers: &[Paper]) -> String {
+    let mut out = String::new();
+    for paper in papers {
+        let mut tag = |tag: &str, value: &str| {
+            let value = value.split_whitespace().collect::<Vec<_>>().join(" ");
+            if !value.is_empty() {
+                out.push_str(&format!("{tag}  - {value}\r\n"));
+            }
+        };
+        tag("TY", Kind::of(paper).ris());
+        if let Some(title) = &paper.title {
+            tag("TI", title);
+        }
+        for name in author_names(paper) {
+            match split_name(name) {
+                (family, Some(given)) => tag("AU", &format!("{family}, {given}")),
+                (family, None) => tag("AU", family),
+            }
+        }
+        if let Some(year) = paper.year {
+            tag("PY", &year.to_string());
+        }
+        if let Some(parts) = date_parts(paper).filter(|p| p.len() > 1) {
+            let [y, m, d] = [parts.first(), parts.get(1), parts.get(2)]
+                .map(|p| p.map(|n| format!("{n:02}")).unwrap_or_default());
+            tag("DA", &format!("{y}/{m}/{d}/"));
+        }
+        if let Some(venue) = venue(paper) {
+            tag("T2", venue);
+        }
+        if let Some(journal) = &paper.journal {
+            if let Some(volume) = non_empty(&journal.volume) {
+                tag("VL", volume);
+            }
+            if let Some(pages) = non_empty(&journal.pages) {
+                let (start, end) = split_pages(pages);
+                tag("SP", start);
+                if let Some(end) = end {
+                    tag("EP", end);
+                }
+            }
+        }
+        if let Some(doi) = paper.doi() {
+            tag("DO", doi);
+        }
+        if let Some(url) = &paper.url {
+            tag("UR", url);
+        }
+        if let Some(pmid) = paper
+            .external_ids
+            .as_ref()
+            .and_then(|ids| ids.pubmed.as_deref())
+        {
+            tag("AN", &format!("PMID:{pmid}"));
+        }
+        for field in paper.fields_of_study.iter().flatten() {
+            tag("KW", field);
+        }
+        if let Some(text) = &paper.abstract_text {
+            tag("AB", text);
+        }
+        out.push_str("ER  - \r\n\r\n");
+    }
+    out
+}
+
+/// A CSL-JSON array, the format Zotero and citeproc processors read natively.
+pub fn to_csl_json(papers: &[Paper]) -> Value {
+    let items = papers.iter().enumerate().map(|(i, paper)| {
+        let mut item = Map::new();
+        let id = paper
+            .paper_id
+            .clone()
+            .unwrap_or_else(|| format!("item-{}", i + 1));
+        item.insert("id".into(), json!(id));
+        item.insert("type".into(), json!(Kind::of(paper).csl()));
+        let mut set = |key: &str, value: Option<Value>| {
+            if let Some(value) = value {
+                item.insert(key.into(), value);
+            }
+        };
+        set("title", paper.title.as_ref().map(|t| json!(t)));
+        let authors: Vec<Value> = author_names(paper)
+            .iter()
+            .map(|name| match split_name(name) {
+                (family, Some(given)) => json!({ "family": family, "given": given }),
+                (literal, None) => json!({ "literal": literal }),
+            })
+            .collect();
+        set("author", (!authors.is_empty()).then(|| json!(authors)));
+        set(
+            "issued",
+            date_parts(paper).map(|p| json!({ "date-parts": [p] })),
+        );
+        set("container-title", venue(paper).map(|v| json!(v)));
+        let journal = paper.journal.as_ref();
+        set(
+            "volume",
+            journal.and_then(|j| non_empty(&j.volume)).map(|v| json!(v)),
+        );
+        set(
+            "page",
+            journal.and_then(|j| non_empty(&j.pages)).map(|p| json!(p)),
+        );
+        set("DOI", paper.doi().map(|d| json!(d)));
+        set("URL", paper.url.as_ref().map(|u| json!(u)));
+        let ids = paper.external_ids.as_ref();
+        set(
+            "PMID",
+            ids.and_then(|i| i.pubmed.as_ref()).map(|p| json!(p)),
+        );
+        set(
+            "PMCID",
+            ids.and_then(|i| i.pubmed_central.as_ref())
+                .map(|p| json!(format!("PMC{p}"))),
+        );
+        set("abstract", paper.abstract_text.as_ref().map(|a| json!(a)));
+        Value::Object(item)
+    });
+    Value::Array(items.collect())
+}
+
+/// Reference type derived from `publicationTypes`, falling back on whether a
+/// journal is known.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Article,
+    Conference,
+    Book,
+    Chapter,
+    Dataset,
+    Other,
+}
+
+impl Kind {
+    fn of(paper: &Paper) -> Self {
+        let has = |kind| paper.has_publication_type(kind);
+        if has("BookSection") {
+            Self::Chapter
+        } else if has("Book") {
+            Self::Book
+        } else if has("Conference") {
+            Self::Conference
+        } else if has("Dataset") {
+            Self::Dataset
+        } else if paper.publication_types.iter().flatten().next().is_some()
+            || paper.journal.as_ref().is_some_and(|j| j.name.is_some())
+        {
+            // Reviews, trials, case reports and the like are journal articles.
+            Self::Article
+        } else {
+            Self::Other
+        }
+    }
+
+    fn bibtex(self) -> &'static str {
+        match self {
+            Self::Article => "article",
+            Self::Conference => "inproceedings",
+            Self::Book => "book",
+            Self::Chapter => "incollection",
+            Self::Dataset | Self::Other => "misc",
+        }
+    }
+
+    fn ris(self) -> &'static str {
+        match self {
+            Self::Article => "JOUR",
+            Self::Conference => "CONF",
+            Self::Book => "BOOK",
+            Self::Chapter => "CHAP",
+            Self::Dataset => "DATA",
+            Self::Other => "GEN",
+        }
+    }
+
+    fn csl(self) -> &'static str {
+        match self {
+            Self::Article => "article-journal",
+            Self::Conference => "paper-conference",
+            Self::Book => "book",
+            Self::Chapter => "chapter",
+            Self::Dataset => "dataset",
+            Self::Other => "article",
+        }
+    }
+}
+
+fn author_names(paper: &Paper) -> Vec<&str> {
+    paper
+        .authors
+        .iter()
+        .flatten()
+        .filter_map(|a| a.name.as_deref())
+        .filter(|n| !n.trim().is_empty())
+        .collect()
+}
+
+/// Split a display name into family and given names. Lower-case particles stay with
+/// the family name (`Ludwig van Beethoven` → `van Beethoven`, `Ludwig`); a single
+/// word has no given name.
+fn split_name(name: &str) -> (&str, Option<&str>) {
+    let name = name.trim();
+    let words: Vec<(usize, &str)> = name
+        .split_whitespace()
+        .map(|w| (w.as_ptr() as usize - name.as_ptr() as usize, w))
+        .collect();
+    let Some(mut family) = words.len().checked_sub(1).filter(|&i| i > 0) else {
+        return (name, None);
+    };
+    while family > 1 && words[family - 1].1.starts_with(char::is_lowercase) {
+        family -= 1;
+    }
+    let start = words[family].0;
+    (&name[start..], Some(name[..start].trim_end()))
+}
+
+fn venue(paper: &Paper) -> Option<&str> {
+    paper
+        .journal
+        .as_ref()
+        .and_then(|j| non_empty(&j.name))
+        .or_else(|| non_empty(&paper.venue))
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// `[year, month, day]` from `publicationDate`, or just `[year]`.
+fn date_parts(paper: &Paper) -> Option<Vec<u32>> {
+    let from_date = paper.publication_date.as_deref().and_then(|date| {
+        date.split('-')
+            .map(|part| part.parse().ok())
+            .collect::<Option<Vec<u32>>>()
+    });
+    from_date
+        .filter(|parts| !parts.is_empty())
+        .or_else(|| Some(vec![paper.year?]))
+}
+
+fn split_pages(pages: &str) -> (&str, Option<&str>) {
+    match pages.split_once(['-', '–']) {
+        Some((start, end)) => (start.trim(), Some(end.trim_start_matches('-').trim())),
+        None => (pages, None),
+    }
+}
+
+fn cite_key(paper: &Paper) -> String {
+    let ascii = |s: &str| -> String {
+        s.chars()
+            .filter(char::is_ascii_alphanumeric)
+            .map(|c| c.to_ascii_lowercase())
+            .collect()
+    };
+    let surname = author_names(paper)
+        .first()
+        .map(|name| ascii(split_name(name).0))
+        .unwrap_or_default();
+    let year = paper.year.map(|y| y.to_string()).unwrap_or_default();
+    let word = paper
+        .title
+        .as_deref()
+        .unwrap_or_default()
+        .split(|c: char| !c.is_alphanumeric())
+        .map(ascii)
+        .find(|w| w.len() > 3 && !w.chars().all(|c| c.is_ascii_digit()))
+        .unwrap_or_default();
+    let key = format!("{surname}{year}{word}");
+    if key.is_empty() {
+        ascii(paper.paper_id.as_deref().unwrap_or("paper"))
+    } else {
+        key
+    }
+}
+
+/// `a`, `b`, … `z`, `aa`, `ab`, … for the `n`th repeat of a key.
+fn suffix(mut n: usize) -> String {
+    let mut out = Vec::new();
+    while n > 0 {
+        n -= 1;
+        out.push(b'a' + (n % 26) as u8);
+        n /= 26;
+    }
+    out.reverse();
+    String::from_utf8(out).expect("ASCII letters")
+}
+
+/// Escape LaTeX special characters in free text.
+fn bibtex_escape(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => out.push_str("\\textbackslash{}"),
+            '~' => out.push_str("\\textasciitilde{}"),
+            '^' => out.push_str("\\textasciicircum{}"),
+            '{' | '}' | '&' | '%' | '$' | '#' | '_' => {
+                out.push('\\');
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// DOIs, URLs and IDs are copied as-is; only braces, which would end the field, go.
+fn bibtex_verbatim(text: &str) -> String {
+    text.replace(['{', '}'], "")
+}
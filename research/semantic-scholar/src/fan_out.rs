@@ -0,0 +1,100 @@
+//! Run one request per paper for many papers with bounded concurrency.
+//!
+//! [`SemanticScholarClient::fetch_many`] replaces hand-written `join_all` loops: it
+//! keeps at most `concurrency` requests in flight, reports progress as each paper
+//! finishes and returns one `Result` per paper, so a single unknown ID does not sink
+//! the rest. Every request still goes through the client's rate limiter, retry
+//! policy and cache.
+use std::future::Future;
+
+use futures::{stream, StreamExt};
+
+use crate::{client::SemanticScholarClient, error::Error, paper_id::PaperId};
+
+/// Requests in flight when the caller has no better number.
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Reported to the progress callback after each paper finishes.
+#[derive(Debug, Clone, Copy)]
+pub struct Progress<'a> {
+    /// The paper that just finished.
+    pub id: &'a PaperId,
+    /// Whether its operation succeeded.
+    pub ok: bool,
+    /// Papers finished so far, this one included.
+    pub completed: usize,
+    /// Papers whose operation failed so far.
+    pub failed: usize,
+    pub total: usize,
+}
+
+impl SemanticScholarClient {
+    /// Run `op` for every ID with at most `concurrency` operations in flight.
+    ///
+    /// Results come back in the order of `ids`, each paired with its ID.
+    /// `on_progress` runs after every completion, in completion order.
+    ///
+    /// ```no_run
+    /// # async fn run(client: semantic_scholar::SemanticScholarClient, ids: Vec<semantic_scholar::PaperId>) {
+    /// use semantic_scholar::{fan_out::DEFAULT_CONCURRENCY, fields::Endpoint, FieldSet};
+    ///
+    /// let fields = FieldSet::parse(Endpoint::Citations, "title").unwrap();
+    /// let results = client
+    ///     .fetch_many(
+    ///         &ids,
+    ///         DEFAULT_CONCURRENCY,
+    ///         |client, id| {
+    ///             let fields = fields.clone();
+    ///             async move { client.get_citations(&id, &fields, 100, 0).await }
+    ///         },
+    ///         |p| eprintln!("{}/{} {}", p.completed, p.total, p.id),
+    ///     )
+    ///     .await;
+    /// for (id, result) in results {
+    ///     match result {
+    ///         Ok(page) => println!("{id}: {} citing papers", page.data.len()),
+    ///         Err(e) => eprintln!("{id}: {e}"),
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub async fn fetch_many<T, F, Fut>(
+        &self,
+        ids: &[PaperId],
+        concurrency: usize,
+        op: F,
+        mut on_progress: impl FnMut(Progress<'_>),
+    ) -> Vec<(PaperId, Result<T, Error>)>
+    where
+        F: Fn(SemanticScholarClient, PaperId) -> Fut,
+        Fut: Future<Output = Result<T, Error>>,
+    {
+        let total = ids.len();
+        let mut results: Vec<Option<Result<T, Error>>> = (0..total).map(|_| None).collect();
+        let mut in_flight = stream::iter(ids.iter().enumerate())
+            .map(|(i, id)| {
+                let fut = op(self.clone(), id.clone());
+                async move { (i, fut.await) }
+            })
+            .buffer_unordered(concurrency.max(1));
+
+        let (mut completed, mut failed) = (0, 0);
+        while let Some((i, result)) = in_flight.next().await {
+            completed += 1;
+            failed += usize::from(result.is_err());
+            on_progress(Progress {
+                id: &ids[i],
+                ok: result.is_ok(),
+                completed,
+                failed,
+                total,
+            });
+            results[i] = Some(result);
+        }
+
+        ids.iter()
+            .cloned()
+            .zip(results.into_iter().flatten())
+            .collect()
+    }
+}
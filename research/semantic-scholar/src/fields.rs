@@ -0,0 +1,686 @@
+//! Typed `fields=` selection.
+//!
+//! Each endpoint accepts a different subset of paper fields — `tldr` is rejected by
+//! search and by the nested-paper endpoints, for example. A [`FieldSet`] is tied to
+//! an [`Endpoint`] and refuses fields that endpoint would answer with a 400; the
+//! client methods take the `FieldSet` for their own endpoint and reject any other.
+//! The author search and profile endpoints take an [`AuthorFieldSet`] instead.
+use std::{fmt, str::FromStr};
+
+use crate::error::Error;
+
+/// A top-level attribute of a paper that can be requested via `fields=`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PaperField {
+    PaperId,
+    CorpusId,
+    Url,
+    Title,
+    Abstract,
+    Venue,
+    PublicationVenue,
+    Year,
+    ReferenceCount,
+    CitationCount,
+    InfluentialCitationCount,
+    IsOpenAccess,
+    OpenAccessPdf,
+    FieldsOfStudy,
+    S2FieldsOfStudy,
+    PublicationTypes,
+    PublicationDate,
+    Journal,
+    CitationStyles,
+    ExternalIds,
+    Authors,
+    Citations,
+    References,
+    Embedding,
+    Tldr,
+}
+
+impl PaperField {
+    pub const ALL: [PaperField; 25] = [
+        Self::PaperId,
+        Self::CorpusId,
+        Self::Url,
+        Self::Title,
+        Self::Abstract,
+        Self::Venue,
+        Self::PublicationVenue,
+        Self::Year,
+        Self::ReferenceCount,
+        Self::CitationCount,
+        Self::InfluentialCitationCount,
+        Self::IsOpenAccess,
+        Self::OpenAccessPdf,
+        Self::FieldsOfStudy,
+        Self::S2FieldsOfStudy,
+        Self::PublicationTypes,
+        Self::PublicationDate,
+        Self::Journal,
+        Self::CitationStyles,
+        Self::ExternalIds,
+        Self::Authors,
+        Self::Citations,
+        Self::References,
+        Self::Embedding,
+        Self::Tldr,
+    ];
+
+    /// The name the API uses for this field.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::PaperId => "paperId",
+            Self::CorpusId => "corpusId",
+            Self::Url => "url",
+            Self::Title => "title",
+            Self::Abstract => "abstract",
+            Self::Venue => "venue",
+            Self::PublicationVenue => "publicationVenue",
+            Self::Year => "year",
+            Self::ReferenceCount => "referenceCount",
+            Self::CitationCount => "citationCount",
+            Self::InfluentialCitationCount => "influentialCitationCount",
+            Self::IsOpenAccess => "isOpenAccess",
+            Self::OpenAccessPdf => "openAccessPdf",
+            Self::FieldsOfStudy => "fieldsOfStudy",
+            Self::S2FieldsOfStudy => "s2FieldsOfStudy",
+            Self::PublicationTypes => "publicationTypes",
+            Self::PublicationDate => "publicationDate",
+            Self::Journal => "journal",
+            Self::CitationStyles => "citationStyles",
+            Self::ExternalIds => "externalIds",
+            Self::Authors => "authors",
+            Self::Citations => "citations",
+            Self::References => "references",
+            Self::Embedding => "embedding",
+            Self::Tldr => "tldr",
+        }
+    }
+
+    /// Whether `endpoint` accepts this field.
+    pub fn allowed_in(self, endpoint: Endpoint) -> bool {
+        match endpoint {
+            Endpoint::PaperDetail | Endpoint::PaperBatch => true,
+            // Search endpoints have no per-paper enrichment: no TLDR, no influential
+            // count and no nested citation lists.
+            Endpoint::Search | Endpoint::BulkSearch | Endpoint::TitleMatch => !matches!(
+                self,
+                Self::Tldr
+                    | Self::InfluentialCitationCount
+                    | Self::Citations
+                    | Self::References
+                    | Self::Embedding
+            ),
+            // Nested paper objects (citingPaper, citedPaper, recommendations, author
+            // papers) cannot expand further.
+            Endpoint::Citations
+            | Endpoint::References
+            | Endpoint::Recommendations
+            | Endpoint::AuthorPapers => !matches!(
+                self,
+                Self::Tldr | Self::Citations | Self::References | Self::Embedding
+            ),
+        }
+    }
+
+    /// Whether the API accepts `<field>.<subfield>` selectors such as
+    /// `authors.affiliations` or `citations.title` for this field.
+    pub fn has_subfields(self) -> bool {
+        matches!(
+            self,
+            Self::Authors | Self::Citations | Self::References | Self::Embedding
+        )
+    }
+}
+
+impl fmt::Display for PaperField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PaperField {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Self::ALL
+            .into_iter()
+            .find(|f| f.as_str() == s)
+            .ok_or_else(|| Error::InvalidFields(format!("unknown paper field `{s}`")))
+    }
+}
+
+/// An attribute of a citation edge, requested alongside the nested paper fields
+/// from the citations and references endpoints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EdgeField {
+    Contexts,
+    Intents,
+    IsInfluential,
+}
+
+impl EdgeField {
+    pub const ALL: [EdgeField; 3] = [Self::Contexts, Self::Intents, Self::IsInfluential];
+
+    /// The name the API uses for this field.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Contexts => "contexts",
+            Self::Intents => "intents",
+            Self::IsInfluential => "isInfluential",
+        }
+    }
+
+    /// Whether `endpoint` accepts this field.
+    pub fn allowed_in(self, endpoint: Endpoint) -> bool {
+        matches!(endpoint, Endpoint::Citations | Endpoint::References)
+    }
+}
+
+impl fmt::Display for EdgeField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for EdgeField {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Self::ALL
+            .into_iter()
+            .find(|f| f.as_str() == s)
+            .ok_or_else(|| Error::InvalidFields(format!("unknown citation edge field `{s}`")))
+    }
+}
+
+/// One entry of a `fields=` list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Selector {
+    Paper(PaperField),
+    /// `<field>.<subfield>`, e.g. `authors.affiliations`.
+    Nested(PaperField, String),
+    Edge(EdgeField),
+}
+
+impl Selector {
+    fn parse(s: &str) -> Result<Self, Error> {
+        if let Some((field, sub)) = s.split_once('.') {
+            let field: PaperField = field.parse()?;
+            let valid_sub =
+                !sub.is_empty() && sub.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !field.has_subfields() || !valid_sub {
+                return Err(Error::InvalidFields(format!("unknown nested field `{s}`")));
+            }
+            return Ok(Self::Nested(field, sub.to_string()));
+        }
+        match s.parse::<PaperField>() {
+            Ok(field) => Ok(Self::Paper(field)),
+            Err(e) => s.parse::<EdgeField>().map(Self::Edge).map_err(|_| e),
+        }
+    }
+
+    fn check(&self, endpoint: Endpoint) -> Result<(), Error> {
+        let allowed = match self {
+            Self::Paper(f) | Self::Nested(f, _) => f.allowed_in(endpoint),
+            Self::Edge(f) => f.allowed_in(endpoint),
+        };
+        if allowed {
+            Ok(())
+        } else {
+            Err(Error::InvalidFields(format!(
+                "`{self}` is not available from {endpoint}"
+            )))
+        }
+    }
+
+    fn paper_field(&self) -> Option<PaperField> {
+        match self {
+            Self::Paper(f) | Self::Nested(f, _) => Some(*f),
+            Self::Edge(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Paper(field) => write!(f, "{field}"),
+            Self::Nested(field, sub) => write!(f, "{field}.{sub}"),
+            Self::Edge(field) => write!(f, "{field}"),
+        }
+    }
+}
+
+/// An endpoint that takes a paper `fields=` parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endpoint {
+    /// `/graph/v1/paper/search`
+    Search,
+    /// `/graph/v1/paper/search/bulk`
+    BulkSearch,
+    /// `/graph/v1/paper/search/match`
+    TitleMatch,
+    /// `/graph/v1/paper/{id}`
+    PaperDetail,
+    /// `POST /graph/v1/paper/batch`
+    PaperBatch,
+    /// `/graph/v1/paper/{id}/citations`
+    Citations,
+    /// `/graph/v1/paper/{id}/references`
+    References,
+    /// `/recommendations/v1/papers/...`
+    Recommendations,
+    /// `/graph/v1/author/{id}/papers`
+    AuthorPapers,
+}
+
+impl fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Search => "/paper/search",
+            Self::BulkSearch => "/paper/search/bulk",
+            Self::TitleMatch => "/paper/search/match",
+            Self::PaperDetail => "/paper/{id}",
+            Self::PaperBatch => "/paper/batch",
+            Self::Citations => "/paper/{id}/citations",
+            Self::References => "/paper/{id}/references",
+            Self::Recommendations => "/recommendations",
+            Self::AuthorPapers => "/author/{id}/papers",
+        })
+    }
+}
+
+/// A validated, comma-joined `fields=` value for one endpoint.
+///
+/// ```
+/// use semantic_scholar::fields::{Endpoint, FieldSet, PaperField};
+///
+/// let fields = FieldSet::builder(Endpoint::Search)
+///     .field(PaperField::Title)
+///     .field(PaperField::Year)
+///     .build()
+///     .unwrap();
+/// assert_eq!(fields.as_str(), "title,year");
+///
+/// // `tldr` is only served by the single-paper and batch endpoints.
+/// assert!(FieldSet::new(Endpoint::Search, [PaperField::Tldr]).is_err());
+///
+/// // Nested subfields and citation edge attributes parse too.
+/// let cites = FieldSet::parse(Endpoint::Citations, "intents,title,authors.name").unwrap();
+/// assert_eq!(cites.as_str(), "intents,title,authors.name");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSet {
+    endpoint: Endpoint,
+    selectors: Vec<Selector>,
+    fields: Vec<PaperField>,
+    edge_fields: Vec<EdgeField>,
+    joined: String,
+}
+
+impl FieldSet {
+    /// Validate `fields` against `endpoint`. Duplicates are dropped, order is kept.
+    pub fn new(
+        endpoint: Endpoint,
+        fields: impl IntoIterator<Item = PaperField>,
+    ) -> Result<Self, Error> {
+        Self::from_selectors(endpoint, fields.into_iter().map(Selector::Paper))
+    }
+
+    fn from_selectors(
+        endpoint: Endpoint,
+        selectors: impl IntoIterator<Item = Selector>,
+    ) -> Result<Self, Error> {
+        let mut unique: Vec<Selector> = Vec::new();
+        for selector in selectors {
+            selector.check(endpoint)?;
+            if !unique.contains(&selector) {
+                unique.push(selector);
+            }
+        }
+        if unique.is_empty() {
+            return Err(Error::InvalidFields("no fields selected".into()));
+        }
+        let mut fields = Vec::new();
+        let mut edge_fields = Vec::new();
+        for selector in &unique {
+            match selector {
+                Selector::Edge(f) => edge_fields.push(*f),
+                other => {
+                    let f = other.paper_field().expect("non-edge selector");
+                    if !fields.contains(&f) {
+                        fields.push(f);
+                    }
+                }
+            }
+        }
+        let joined = unique
+            .iter()
+            .map(Selector::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        Ok(Self {
+            endpoint,
+            selectors: unique,
+            fields,
+            edge_fields,
+            joined,
+        })
+    }
+
+    /// Parse a comma-separated list such as `"title,year,authors.affiliations"` for
+    /// `endpoint`. Citation edge fields (`contexts`, `intents`, `isInfluential`) are
+    /// accepted for the citations and references endpoints.
+    pub fn parse(endpoint: Endpoint, fields: &str) -> Result<Self, Error> {
+        let parsed = fields
+            .split(',')
+            .map(str::trim)
+            .filter(|f| !f.is_empty())
+            .map(Selector::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::from_selectors(endpoint, parsed)
+    }
+
+    pub fn builder(endpoint: Endpoint) -> FieldSetBuilder {
+        FieldSetBuilder {
+            endpoint,
+            selectors: Vec::new(),
+        }
+    }
+
+    /// Same fields as [`crate::types::SEARCH_FIELDS`].
+    pub fn search(endpoint: Endpoint) -> Result<Self, Error> {
+        Self::parse(endpoint, crate::types::SEARCH_FIELDS)
+    }
+
+    /// Same fields as [`crate::types::PAPER_FIELDS_FULL`].
+    pub fn paper_full(endpoint: Endpoint) -> Result<Self, Error> {
+        Self::parse(endpoint, crate::types::PAPER_FIELDS_FULL)
+    }
+
+    /// Same fields as [`crate::types::PAPER_FIELDS_BRIEF`], for nested paper endpoints.
+    pub fn paper_brief(endpoint: Endpoint) -> Result<Self, Error> {
+        Self::parse(endpoint, crate::types::PAPER_FIELDS_BRIEF)
+    }
+
+    /// The same selection re-validated for another endpoint.
+    pub fn for_endpoint(&self, endpoint: Endpoint) -> Result<Self, Error> {
+        Self::from_selectors(endpoint, self.selectors.iter().cloned())
+    }
+
+    /// Prepend every [`EdgeField`], for the citations and references endpoints.
+    pub fn with_edge_fields(&self) -> Result<Self, Error> {
+        let edges = EdgeField::ALL.into_iter().map(Selector::Edge);
+        Self::from_selectors(self.endpoint, edges.chain(self.selectors.iter().cloned()))
+    }
+
+    pub fn endpoint(&self) -> Endpoint {
+        self.endpoint
+    }
+
+    /// Top-level paper fields, including those selected only through a subfield.
+    pub fn fields(&self) -> &[PaperField] {
+        &self.fields
+    }
+
+    pub fn edge_fields(&self) -> &[EdgeField] {
+        &self.edge_fields
+    }
+
+    pub fn contains(&self, field: PaperField) -> bool {
+        self.fields.contains(&field)
+    }
+
+    /// The comma-joined `fields=` value.
+    pub fn as_str(&self) -> &str {
+        &self.joined
+    }
+
+    /// The `fields=` value, if this set was built for `endpoint`.
+    pub(crate) fn value_for(&self, endpoint: Endpoint) -> Result<&str, Error> {
+        if self.endpoint == endpoint {
+            Ok(&self.joined)
+        } else {
+            Err(Error::InvalidFields(format!(
+                "fields were selected for {}, not {endpoint}",
+                self.endpoint
+            )))
+        }
+    }
+}
+
+impl fmt::Display for FieldSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.joined)
+    }
+}
+
+impl AsRef<str> for FieldSet {
+    fn as_ref(&self) -> &str {
+        &self.joined
+    }
+}
+
+pub struct FieldSetBuilder {
+    endpoint: Endpoint,
+    selectors: Vec<Selector>,
+}
+
+impl FieldSetBuilder {
+    pub fn field(mut self, field: PaperField) -> Self {
+        self.selectors.push(Selector::Paper(field));
+        self
+    }
+
+    pub fn fields(mut self, fields: impl IntoIterator<Item = PaperField>) -> Self {
+        self.selectors
+            .extend(fields.into_iter().map(Selector::Paper));
+        self
+    }
+
+    /// A `<field>.<subfield>` selector such as `authors.affiliations`.
+    pub fn subfield(mut self, field: PaperField, subfield: &str) -> Self {
+        self.selectors
+            .push(Selector::Nested(field, subfield.to_string()));
+        self
+    }
+
+    pub fn edge_field(mut self, field: EdgeField) -> Self {
+        self.selectors.push(Selector::Edge(field));
+        self
+    }
+
+    /// Validate the selection, see [`FieldSet::new`].
+    pub fn build(self) -> Result<FieldSet, Error> {
+        let selectors = self
+            .selectors
+            .into_iter()
+            .map(|s| match s {
+                // Route subfields through the parser so they get the same checks.
+                Selector::Nested(..) => Selector::parse(&s.to_string()),
+                other => Ok(other),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        FieldSet::from_selectors(self.endpoint, selectors)
+    }
+}
+
+/// A top-level attribute of an author that can be requested via `fields=` from
+/// `/author/search` and `/author/{id}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AuthorField {
+    AuthorId,
+    ExternalIds,
+    Url,
+    Name,
+    Affiliations,
+    Homepage,
+    PaperCount,
+    CitationCount,
+    HIndex,
+    Papers,
+}
+
+impl AuthorField {
+    pub const ALL: [AuthorField; 10] = [
+        Self::AuthorId,
+        Self::ExternalIds,
+        Self::Url,
+        Self::Name,
+        Self::Affiliations,
+        Self::Homepage,
+        Self::PaperCount,
+        Self::CitationCount,
+        Self::HIndex,
+        Self::Papers,
+    ];
+
+    /// The name the API uses for this field.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::AuthorId => "authorId",
+            Self::ExternalIds => "externalIds",
+            Self::Url => "url",
+            Self::Name => "name",
+            Self::Affiliations => "affiliations",
+            Self::Homepage => "homepage",
+            Self::PaperCount => "paperCount",
+            Self::CitationCount => "citationCount",
+            Self::HIndex => "hIndex",
+            Self::Papers => "papers",
+        }
+    }
+}
+
+impl fmt::Display for AuthorField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AuthorField {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Self::ALL
+            .into_iter()
+            .find(|f| f.as_str() == s)
+            .ok_or_else(|| Error::InvalidFields(format!("unknown author field `{s}`")))
+    }
+}
+
+/// A validated, comma-joined `fields=` value for the author search and profile
+/// endpoints.
+///
+/// `papers.<field>` selects fields of the author's papers, which follow the
+/// [`Endpoint::AuthorPapers`] rules.
+///
+/// ```
+/// use semantic_scholar::fields::AuthorFieldSet;
+///
+/// let fields = AuthorFieldSet::parse("name,hIndex,papers.title").unwrap();
+/// assert_eq!(fields.as_str(), "name,hIndex,papers.title");
+/// assert!(AuthorFieldSet::parse("name,papers.tldr").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthorFieldSet {
+    fields: Vec<AuthorField>,
+    joined: String,
+}
+
+impl AuthorFieldSet {
+    /// Validate `fields`. Duplicates are dropped, order is kept.
+    pub fn new(fields: impl IntoIterator<Item = AuthorField>) -> Result<Self, Error> {
+        Self::from_selectors(fields.into_iter().map(|f| (f, None)))
+    }
+
+    /// Parse a comma-separated list such as `"name,affiliations,papers.year"`.
+    pub fn parse(fields: &str) -> Result<Self, Error> {
+        let parsed = fields
+            .split(',')
+            .map(str::trim)
+            .filter(|f| !f.is_empty())
+            .map(|s| match s.split_once('.') {
+                Some(("papers", sub)) => {
+                    let paper = sub
+                        .parse::<PaperField>()
+                        .map_err(|_| Error::InvalidFields(format!("unknown nested field `{s}`")))?;
+                    if !paper.allowed_in(Endpoint::AuthorPapers) {
+                        return Err(Error::InvalidFields(format!(
+                            "`{s}` is not available from author endpoints"
+                        )));
+                    }
+                    Ok((AuthorField::Papers, Some(paper)))
+                }
+                Some(_) => Err(Error::InvalidFields(format!("unknown nested field `{s}`"))),
+                None => Ok((s.parse()?, None)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::from_selectors(parsed)
+    }
+
+    fn from_selectors(
+        selectors: impl IntoIterator<Item = (AuthorField, Option<PaperField>)>,
+    ) -> Result<Self, Error> {
+        let mut unique = Vec::new();
+        for selector in selectors {
+            if !unique.contains(&selector) {
+                unique.push(selector);
+            }
+        }
+        if unique.is_empty() {
+            return Err(Error::InvalidFields("no fields selected".into()));
+        }
+        let mut fields = Vec::new();
+        for (field, _) in &unique {
+            if !fields.contains(field) {
+                fields.push(*field);
+            }
+        }
+        let joined = unique
+            .iter()
+            .map(|(field, paper)| match paper {
+                Some(paper) => format!("{field}.{paper}"),
+                None => field.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        Ok(Self { fields, joined })
+    }
+
+    /// Top-level author fields, including `papers` when only a subfield was selected.
+    pub fn fields(&self) -> &[AuthorField] {
+        &self.fields
+    }
+
+    pub fn contains(&self, field: AuthorField) -> bool {
+        self.fields.contains(&field)
+    }
+
+    /// The comma-joined `fields=` value.
+    pub fn as_str(&self) -> &str {
+        &self.joined
+    }
+}
+
+impl Default for AuthorFieldSet {
+    /// Same fields as [`crate::types::AUTHOR_FIELDS`].
+    fn default() -> Self {
+        Self::parse(crate::types::AUTHOR_FIELDS).expect("AUTHOR_FIELDS is valid")
+    }
+}
+
+impl fmt::Display for AuthorFieldSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.joined)
+    }
+}
+
+impl AsRef<str> for AuthorFieldSet {
+    fn as_ref(&self) -> &str {
+        &self.joined
+    }
+}
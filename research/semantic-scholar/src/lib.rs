@@ -1,7 +1,26 @@
+pub mod cache;
 pub mod client;
+pub mod datasets;
 pub mod error;
+pub mod export;
+pub mod fan_out;
+pub mod fields;
+pub mod fixtures;
+pub mod pagination;
+pub mod paper_id;
+pub mod query;
+pub mod rate_limit;
+pub mod retry;
+pub mod snowball;
 pub mod types;
 
-pub use client::SemanticScholarClient;
-pub use error::Error;
+pub use cache::{CacheMode, ResponseCache};
+pub use client::{SemanticScholarClient, SemanticScholarClientBuilder};
+pub use error::{Error, RequestContext};
+pub use export::ExportFormat;
+pub use fields::{AuthorFieldSet, FieldSet, PaperField};
+pub use paper_id::PaperId;
+pub use query::{BulkQuery, FieldOfStudy, PublicationType, SearchFilters};
+pub use rate_limit::RateLimiter;
+pub use retry::RetryPolicy;
 pub use types::*;
@@ -0,0 +1,358 @@
+//! `Stream`-returning wrappers that follow continuation tokens and offsets.
+//!
+//! Each stream fetches pages lazily, yields items one at a time and stops after
+//! `max_results` items, when the API reports no further page, or at the first error.
+use std::future::Future;
+
+use futures::{
+    stream::{self, BoxStream},
+    StreamExt, TryStreamExt,
+};
+
+use crate::{
+    client::{bulk_search_params, SemanticScholarClient, SEARCH_PAGE_LIMIT, SEARCH_RESULT_LIMIT},
+    error::Error,
+    fields::{Endpoint, FieldSet},
+    paper_id::PaperId,
+    query::SearchFilters,
+    types::{
+        AuthorPapersResponse, BulkSearchResponse, CitationItem, CitationsResponse, Paper,
+        ReferenceItem, ReferencesResponse, SearchResponse,
+    },
+};
+
+/// Largest page the bulk, citations, references and author-papers endpoints accept.
+const MAX_PAGE_SIZE: usize = 1000;
+
+/// Where the next page starts.
+enum Cursor {
+    Start,
+    Token(String),
+    Offset(u64),
+    Done,
+}
+
+struct PageState {
+    client: SemanticScholarClient,
+    cursor: Cursor,
+    remaining: usize,
+}
+
+impl PageState {
+    fn new(client: &SemanticScholarClient, max_results: usize) -> Self {
+        Self {
+            client: client.clone(),
+            cursor: if max_results == 0 { Cursor::Done } else { Cursor::Start },
+            remaining: max_results,
+        }
+    }
+
+    fn page_size(&self) -> u32 {
+        self.remaining.min(MAX_PAGE_SIZE) as u32
+    }
+
+    /// Trim `items` to the remaining budget and work out where to go next.
+    fn advance<T>(&mut self, mut items: Vec<T>, next: Cursor) -> Vec<T> {
+        items.truncate(self.remaining);
+        self.remaining -= items.len();
+        self.cursor = if self.remaining == 0 || items.is_empty() {
+            Cursor::Done
+        } else {
+            next
+        };
+        items
+    }
+}
+
+/// Turn a stream of pages into a stream of items.
+fn flatten_pages<T: Send + 'static>(
+    pages: impl futures::Stream<Item = Result<Vec<T>, Error>> + Send + 'static,
+) -> BoxStream<'static, Result<T, Error>> {
+    pages
+        .map_ok(|page| stream::iter(page.into_iter().map(Ok)))
+        .try_flatten()
+        .boxed()
+}
+
+/// A response from an endpoint paginated with `offset`/`next`.
+trait OffsetPage: Send + 'static {
+    type Item: Send + 'static;
+
+    fn next_offset(&self) -> Option<u64>;
+
+    fn data_mut(&mut self) -> &mut Vec<Self::Item>;
+}
+
+impl OffsetPage for SearchResponse {
+    type Item = Paper;
+
+    fn next_offset(&self) -> Option<u64> {
+        self.next
+    }
+
+    fn data_mut(&mut self) -> &mut Vec<Paper> {
+        &mut self.data
+    }
+}
+
+impl OffsetPage for CitationsResponse {
+    type Item = CitationItem;
+
+    fn next_offset(&self) -> Option<u64> {
+        self.next
+    }
+
+    fn data_mut(&mut self) -> &mut Vec<CitationItem> {
+        &mut self.data
+    }
+}
+
+impl OffsetPage for ReferencesResponse {
+    type Item = ReferenceItem;
+
+    fn next_offset(&self) -> Option<u64> {
+        self.next
+    }
+
+    fn data_mut(&mut self) -> &mut Vec<ReferenceItem> {
+        &mut self.data
+    }
+}
+
+impl OffsetPage for AuthorPapersResponse {
+    type Item = Paper;
+
+    fn next_offset(&self) -> Option<u64> {
+        self.next
+    }
+
+    fn data_mut(&mut self) -> &mut Vec<Paper> {
+        &mut self.data
+    }
+}
+
+/// Walk `offset`/`next`, fetching each page with `page_fn(client, limit, offset)`.
+///
+/// Pages hold at most `page_limit` items, and no page reaches past `max_offset`
+/// results into the listing. Each page's `data` is trimmed to the `max_results` budget.
+fn offset_pages<R, F, Fut>(
+    client: &SemanticScholarClient,
+    max_results: usize,
+    page_limit: u32,
+    max_offset: u32,
+    page_fn: F,
+) -> BoxStream<'static, Result<R, Error>>
+where
+    R: OffsetPage,
+    F: Fn(SemanticScholarClient, u32, u32) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<R, Error>> + Send + 'static,
+{
+    stream::try_unfold(PageState::new(client, max_results), move |mut st| {
+        let offset = match std::mem::replace(&mut st.cursor, Cursor::Done) {
+            Cursor::Done => None,
+            Cursor::Offset(o) => Some(o.min(u32::MAX as u64) as u32),
+            Cursor::Start | Cursor::Token(_) => Some(0),
+        };
+        let request = offset.and_then(|offset| {
+            let limit = st
+                .page_size()
+                .min(page_limit)
+                .min(max_offset.saturating_sub(offset));
+            (limit > 0).then(|| page_fn(st.client.clone(), limit, offset))
+        });
+        async move {
+            let Some(request) = request else {
+                return Ok(None);
+            };
+            let mut resp = request.await?;
+            let next = resp.next_offset().map_or(Cursor::Done, Cursor::Offset);
+            let data = st.advance(std::mem::take(resp.data_mut()), next);
+            *resp.data_mut() = data;
+            Ok(Some((resp, st)))
+        }
+    })
+    .boxed()
+}
+
+/// [`offset_pages`], one item at a time.
+fn offset_stream<R, F, Fut>(
+    client: &SemanticScholarClient,
+    max_results: usize,
+    page_limit: u32,
+    max_offset: u32,
+    page_fn: F,
+) -> BoxStream<'static, Result<R::Item, Error>>
+where
+    R: OffsetPage,
+    F: Fn(SemanticScholarClient, u32, u32) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<R, Error>> + Send + 'static,
+{
+    let pages = offset_pages(client, max_results, page_limit, max_offset, page_fn);
+    flatten_pages(pages.map_ok(|mut resp| std::mem::take(resp.data_mut())))
+}
+
+impl SemanticScholarClient {
+    /// Stream every match of a bulk search, following the continuation `token`.
+    ///
+    /// Arguments mirror [`SemanticScholarClient::search_bulk`], except that `max_results`
+    /// caps the total number of papers rather than the page size.
+    pub fn search_bulk_stream(
+        &self,
+        query: &str,
+        fields: &FieldSet,
+        filters: &SearchFilters,
+        sort: Option<&str>,
+        max_results: usize,
+    ) -> BoxStream<'static, Result<Paper, Error>> {
+        let query = query.to_string();
+        let fields = fields.clone();
+        let filters = filters.clone();
+        let sort = sort.map(String::from);
+
+        let pages = stream::try_unfold(PageState::new(self, max_results), move |mut st| {
+            let (query, fields) = (query.clone(), fields.clone());
+            let (filters, sort) = (filters.clone(), sort.clone());
+            async move {
+                let mut params = bulk_search_params(
+                    &query,
+                    fields.value_for(Endpoint::BulkSearch)?,
+                    &filters,
+                    sort.as_deref(),
+                    st.page_size(),
+                );
+                match std::mem::replace(&mut st.cursor, Cursor::Done) {
+                    Cursor::Done => return Ok(None),
+                    Cursor::Token(token) => params.push(("token".into(), token)),
+                    Cursor::Start | Cursor::Offset(_) => {}
+                }
+                let url = format!("{}/graph/v1/paper/search/bulk", st.client.base_url());
+                let resp: BulkSearchResponse = st.client.get_json(&url, params).await?.decode()?;
+                let next = resp.token.map_or(Cursor::Done, Cursor::Token);
+                let page = st.advance(resp.data, next);
+                Ok(Some((page, st)))
+            }
+        });
+        flatten_pages(pages)
+    }
+
+    /// Stream relevance-ranked matches, walking `offset`/`next`.
+    ///
+    /// Arguments mirror [`SemanticScholarClient::search`]. The endpoint serves at most
+    /// [`SEARCH_RESULT_LIMIT`] results, so `max_results` is capped there.
+    pub fn search_stream(
+        &self,
+        query: &str,
+        fields: &FieldSet,
+        filters: &SearchFilters,
+        max_results: usize,
+    ) -> BoxStream<'static, Result<Paper, Error>> {
+        flatten_pages(
+            self.search_pages(query, fields, filters, max_results)
+                .map_ok(|page| page.data),
+        )
+    }
+
+    /// [`SemanticScholarClient::search_stream`] a page at a time, for callers that
+    /// also want each page's `total`.
+    pub fn search_pages(
+        &self,
+        query: &str,
+        fields: &FieldSet,
+        filters: &SearchFilters,
+        max_results: usize,
+    ) -> BoxStream<'static, Result<SearchResponse, Error>> {
+        let query = query.to_string();
+        let fields = fields.clone();
+        let filters = filters.clone();
+        let max_results = max_results.min(SEARCH_RESULT_LIMIT as usize);
+        offset_pages(
+            self,
+            max_results,
+            SEARCH_PAGE_LIMIT,
+            SEARCH_RESULT_LIMIT,
+            move |client, limit, offset| {
+                let (query, fields, filters) = (query.clone(), fields.clone(), filters.clone());
+                async move {
+                    client
+                        .search(&query, &fields, &filters, limit, offset)
+                        .await
+                }
+            },
+        )
+    }
+
+    /// Stream papers citing `paper_id`, walking `offset`/`next` up to `max_results` items.
+    pub fn citations_stream(
+        &self,
+        paper_id: &PaperId,
+        fields: &FieldSet,
+        max_results: usize,
+    ) -> BoxStream<'static, Result<CitationItem, Error>> {
+        let paper_id = paper_id.clone();
+        let fields = fields.clone();
+        offset_stream(
+            self,
+            max_results,
+            MAX_PAGE_SIZE as u32,
+            u32::MAX,
+            move |client, limit, offset| {
+                let (paper_id, fields) = (paper_id.clone(), fields.clone());
+                async move {
+                    client
+                        .get_citations(&paper_id, &fields, limit, offset)
+                        .await
+                }
+            },
+        )
+    }
+
+    /// Stream papers referenced by `paper_id`, walking `offset`/`next` up to `max_results` items.
+    pub fn references_stream(
+        &self,
+        paper_id: &PaperId,
+        fields: &FieldSet,
+        max_results: usize,
+    ) -> BoxStream<'static, Result<ReferenceItem, Error>> {
+        let paper_id = paper_id.clone();
+        let fields = fields.clone();
+        offset_stream(
+            self,
+            max_results,
+            MAX_PAGE_SIZE as u32,
+            u32::MAX,
+            move |client, limit, offset| {
+                let (paper_id, fields) = (paper_id.clone(), fields.clone());
+                async move {
+                    client
+                        .get_references(&paper_id, &fields, limit, offset)
+                        .await
+                }
+            },
+        )
+    }
+
+    /// Stream an author's papers, walking `offset`/`next` up to `max_results` items.
+    pub fn author_papers_stream(
+        &self,
+        author_id: &str,
+        fields: &FieldSet,
+        max_results: usize,
+    ) -> BoxStream<'static, Result<Paper, Error>> {
+        let author_id = author_id.to_string();
+        let fields = fields.clone();
+        offset_stream(
+            self,
+            max_results,
+            MAX_PAGE_SIZE as u32,
+            u32::MAX,
+            move |client, limit, offset| {
+                let (author_id, fields) = (author_id.clone(), fields.clone());
+                async move {
+                    client
+                        .get_author_papers(&author_id, &fields, limit, offset)
+                        .await
+                }
+            },
+        )
+    }
+}
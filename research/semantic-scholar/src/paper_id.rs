@@ -0,0 +1,249 @@
+//! Typed paper identifiers.
+//!
+//! The Graph API accepts many ID schemes in the URL path (`DOI:…`, `arXiv:…`,
+//! `PMID:…`, …). [`PaperId`] parses the forms people actually paste — bare DOIs,
+//! `https://doi.org/…` links, PubMed and arXiv URLs — into one of those schemes and
+//! percent-encodes it so a DOI containing `/` stays a single path segment.
+//! Bare values are only accepted when their shape is unambiguous (40-char hex S2
+//! ID, DOI, `PMC…`, arXiv); a bare number needs a scheme prefix.
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PaperId {
+    /// Semantic Scholar's own 40-character hex paper ID.
+    S2(String),
+    CorpusId(u64),
+    /// A DOI without any `doi:` or `https://doi.org/` prefix, e.g. `10.1037/a0012345`.
+    Doi(String),
+    /// An arXiv ID such as `1705.10311` or `hep-th/9901001`.
+    ArXiv(String),
+    Pmid(String),
+    /// A PubMed Central ID without the `PMC` prefix.
+    Pmcid(String),
+    Acl(String),
+    Mag(String),
+    /// A paper URL on a site Semantic Scholar recognises (ACM, bioRxiv, …).
+    Url(String),
+}
+
+impl PaperId {
+    /// The form the API expects, e.g. `DOI:10.1037/a0012345` or `PMID:19380436`.
+    pub fn to_api_string(&self) -> String {
+        match self {
+            Self::S2(id) => id.clone(),
+            Self::CorpusId(id) => format!("CorpusId:{id}"),
+            Self::Doi(doi) => format!("DOI:{doi}"),
+            Self::ArXiv(id) => format!("ARXIV:{id}"),
+            Self::Pmid(id) => format!("PMID:{id}"),
+            Self::Pmcid(id) => format!("PMCID:{id}"),
+            Self::Acl(id) => format!("ACL:{id}"),
+            Self::Mag(id) => format!("MAG:{id}"),
+            Self::Url(url) => format!("URL:{url}"),
+        }
+    }
+
+    /// [`PaperId::to_api_string`] percent-encoded for use as one URL path segment.
+    pub fn path_segment(&self) -> String {
+        encode_path_segment(&self.to_api_string())
+    }
+}
+
+impl fmt::Display for PaperId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_api_string())
+    }
+}
+
+impl FromStr for PaperId {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Error> {
+        let s = input.trim();
+        let invalid = || Error::InvalidPaperId(input.to_string());
+        if s.is_empty() || s.chars().any(char::is_whitespace) {
+            return Err(invalid());
+        }
+
+        // Links people paste from a browser.
+        if let Some(rest) = strip_url(s) {
+            let lower = rest.to_ascii_lowercase();
+            for host in ["doi.org/", "dx.doi.org/"] {
+                if lower.starts_with(host) {
+                    return non_empty(&rest[host.len()..])
+                        .map(|d| Self::Doi(d.into()))
+                        .ok_or_else(invalid);
+                }
+            }
+            for host in ["arxiv.org/abs/", "arxiv.org/pdf/"] {
+                if lower.starts_with(host) {
+                    let id = rest[host.len()..].trim_end_matches(".pdf");
+                    return non_empty(id)
+                        .map(|a| Self::ArXiv(a.into()))
+                        .ok_or_else(invalid);
+                }
+            }
+            if let Some(id) = lower.strip_prefix("pubmed.ncbi.nlm.nih.gov/") {
+                return digits(id.trim_end_matches('/'))
+                    .map(Self::Pmid)
+                    .ok_or_else(invalid);
+            }
+            if lower.starts_with("www.semanticscholar.org/paper/")
+                || lower.starts_with("semanticscholar.org/paper/")
+            {
+                let last = rest
+                    .trim_end_matches('/')
+                    .rsplit('/')
+                    .next()
+                    .unwrap_or_default();
+                if is_s2_hex(last) {
+                    return Ok(Self::S2(last.to_ascii_lowercase()));
+                }
+                return Err(invalid());
+            }
+            return Ok(Self::Url(s.to_string()));
+        }
+
+        // Bare DOIs come before schemes: SICI-style DOIs contain colons of their own.
+        if is_bare_doi(s) {
+            return Ok(Self::Doi(s.into()));
+        }
+
+        // `SCHEME:value` forms, case-insensitive on the scheme.
+        if let Some((scheme, value)) = s.split_once(':') {
+            let value = non_empty(value).ok_or_else(invalid)?;
+            return match scheme.to_ascii_lowercase().as_str() {
+                "doi" => Ok(Self::Doi(value.into())),
+                "arxiv" => Ok(Self::ArXiv(value.into())),
+                "pmid" | "pubmed" => digits(value).map(Self::Pmid).ok_or_else(invalid),
+                "pmcid" | "pmc" => pmc_digits(value).map(Self::Pmcid).ok_or_else(invalid),
+                "corpusid" | "corpus" => value.parse().map(Self::CorpusId).map_err(|_| invalid()),
+                "acl" => Ok(Self::Acl(value.into())),
+                "mag" => Ok(Self::Mag(value.into())),
+                "url" => Ok(Self::Url(value.into())),
+                _ => Err(invalid()),
+            };
+        }
+
+        // Bare values with a recognisable shape.
+        if has_pmc_prefix(s) {
+            return pmc_digits(s).map(Self::Pmcid).ok_or_else(invalid);
+        }
+        if is_s2_hex(s) {
+            return Ok(Self::S2(s.to_ascii_lowercase()));
+        }
+        if is_arxiv_id(s) {
+            return Ok(Self::ArXiv(s.into()));
+        }
+        // A bare number could be a PMID, CorpusId or MAG ID; guessing would
+        // silently fetch the wrong paper.
+        if digits(s).is_some() {
+            return Err(Error::AmbiguousPaperId(input.to_string()));
+        }
+        Err(invalid())
+    }
+}
+
+impl Serialize for PaperId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_api_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for PaperId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Strip `http://` / `https://` (and a leading `www.` for DOI links).
+fn strip_url(s: &str) -> Option<&str> {
+    let lower = s.to_ascii_lowercase();
+    let rest = if lower.starts_with("https://") {
+        &s[8..]
+    } else if lower.starts_with("http://") {
+        &s[7..]
+    } else {
+        return None;
+    };
+    Some(if rest.to_ascii_lowercase().starts_with("www.doi.org/") {
+        &rest[4..]
+    } else {
+        rest
+    })
+}
+
+fn non_empty(s: &str) -> Option<&str> {
+    let s = s.trim();
+    (!s.is_empty()).then_some(s)
+}
+
+fn digits(s: &str) -> Option<String> {
+    (!s.is_empty() && s.chars().all(|c| c.is_ascii_digit())).then(|| s.to_string())
+}
+
+/// `10.<registrant>/<suffix>`, where the registrant is dot-separated digits.
+fn is_bare_doi(s: &str) -> bool {
+    let Some((prefix, suffix)) = s.split_once('/') else {
+        return false;
+    };
+    prefix
+        .strip_prefix("10.")
+        .is_some_and(|registrant| registrant.split('.').all(|part| digits(part).is_some()))
+        && !suffix.is_empty()
+}
+
+/// Case-insensitive `PMC` prefix check that never slices inside a multi-byte char.
+fn has_pmc_prefix(s: &str) -> bool {
+    s.len() > 3 && s.get(..3).is_some_and(|p| p.eq_ignore_ascii_case("pmc"))
+}
+
+fn pmc_digits(s: &str) -> Option<String> {
+    let s = if has_pmc_prefix(s) { &s[3..] } else { s };
+    digits(s)
+}
+
+/// New-style (`1705.10311`, `1706.03762v5`) or old-style (`hep-th/9901001`) arXiv IDs.
+fn is_arxiv_id(s: &str) -> bool {
+    let s = match s.rfind('v') {
+        Some(i) if i > 0 && digits(&s[i + 1..]).is_some() => &s[..i],
+        _ => s,
+    };
+    if let Some((yymm, num)) = s.split_once('.') {
+        return yymm.len() == 4
+            && digits(yymm).is_some()
+            && (4..=5).contains(&num.len())
+            && digits(num).is_some();
+    }
+    if let Some((archive, num)) = s.split_once('/') {
+        return !archive.is_empty()
+            && archive
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c == '-' || c == '.')
+            && num.len() == 7
+            && digits(num).is_some();
+    }
+    false
+}
+
+fn is_s2_hex(s: &str) -> bool {
+    s.len() == 40 && s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Percent-encode everything except RFC 3986 unreserved characters and `:`.
+pub(crate) fn encode_path_segment(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for b in s.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b':' => {
+                out.push(b as char)
+            }
+            _ => out.push_str(&format!("%{b:02X}")),
+        }
+    }
+    out
+}
@@ -0,0 +1,442 @@
+//! Bulk search query syntax and typed search filters.
+//!
+//! `/paper/search/bulk` parses its `query` with a small boolean syntax: terms are
+//! ANDed, `|` is OR, `-` negates, `"…"` makes a phrase, `*` matches a prefix and
+//! parentheses group. [`BulkQuery`] builds such strings without hand-quoting, and
+//! [`SearchFilters`] carries the filters both search endpoints accept.
+use std::{fmt, str::FromStr};
+
+use crate::error::Error;
+
+/// Characters with a meaning in the bulk query syntax.
+const OPERATORS: &[char] = &['+', '|', '-', '"', '*', '(', ')', '~', '\\'];
+
+/// A bulk search query built from clauses that are ANDed together.
+///
+/// ```
+/// use semantic_scholar::query::BulkQuery;
+///
+/// let q = BulkQuery::new()
+///     .phrase("trauma-focused")
+///     .any_of([BulkQuery::new().prefix("child"), BulkQuery::new().prefix("adolescen")])
+///     .exclude("adult");
+/// assert_eq!(q.build(), r#""trauma-focused" (child* | adolescen*) -adult"#);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BulkQuery {
+    clauses: Vec<String>,
+}
+
+impl BulkQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A plain term. Words containing operator characters (`TF-CBT`) are quoted so
+    /// they match literally; several words become several ANDed terms.
+    pub fn term(mut self, text: &str) -> Self {
+        self.clauses
+            .extend(text.split_whitespace().filter_map(literal));
+        self
+    }
+
+    /// An exact phrase.
+    pub fn phrase(mut self, text: &str) -> Self {
+        if let Some(p) = quoted(text) {
+            self.clauses.push(p);
+        }
+        self
+    }
+
+    /// A term that must match, written with an explicit `+`.
+    pub fn require(mut self, text: &str) -> Self {
+        if let Some(t) = single(text) {
+            self.clauses.push(format!("+{t}"));
+        }
+        self
+    }
+
+    /// A term or phrase that must not match.
+    pub fn exclude(mut self, text: &str) -> Self {
+        if let Some(t) = single(text) {
+            self.clauses.push(format!("-{t}"));
+        }
+        self
+    }
+
+    /// Words starting with `stem`. Operator characters are dropped from the stem, since
+    /// a quoted phrase cannot carry a wildcard.
+    pub fn prefix(mut self, stem: &str) -> Self {
+        let stem: String = stem
+            .chars()
+            .map(|c| if OPERATORS.contains(&c) { ' ' } else { c })
+            .collect();
+        let words: Vec<&str> = stem.split_whitespace().collect();
+        if let Some((last, rest)) = words.split_last() {
+            self.clauses.extend(rest.iter().map(|w| w.to_string()));
+            self.clauses.push(format!("{last}*"));
+        }
+        self
+    }
+
+    /// A group matching any of `alternatives`; empty alternatives are skipped.
+    pub fn any_of(mut self, alternatives: impl IntoIterator<Item = BulkQuery>) -> Self {
+        let alts: Vec<BulkQuery> = alternatives.into_iter().filter(|q| !q.is_empty()).collect();
+        match alts.as_slice() {
+            [] => {}
+            [only] if only.clauses.len() == 1 => self.clauses.push(only.build()),
+            _ => {
+                let alts: Vec<String> = alts.iter().map(BulkQuery::build).collect();
+                self.clauses.push(format!("({})", alts.join(" | ")));
+            }
+        }
+        self
+    }
+
+    /// Append another query's clauses.
+    pub fn and(mut self, other: BulkQuery) -> Self {
+        self.clauses.extend(other.clauses);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.clauses.is_empty()
+    }
+
+    /// The `query=` string.
+    pub fn build(&self) -> String {
+        self.clauses.join(" ")
+    }
+}
+
+impl fmt::Display for BulkQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.build())
+    }
+}
+
+/// One word, quoted when it contains operator characters; `None` if quoting leaves
+/// nothing, as for a lone `"`.
+fn literal(word: &str) -> Option<String> {
+    if word.contains(OPERATORS) {
+        quoted(word)
+    } else {
+        Some(word.to_string())
+    }
+}
+
+/// `text` as a phrase. Quotes and backslashes cannot be escaped inside a phrase, so
+/// they are replaced by spaces; `None` if nothing is left.
+fn quoted(text: &str) -> Option<String> {
+    let cleaned: String = text
+        .chars()
+        .map(|c| if matches!(c, '"' | '\\') { ' ' } else { c })
+        .collect();
+    let words: Vec<&str> = cleaned.split_whitespace().collect();
+    (!words.is_empty()).then(|| format!("\"{}\"", words.join(" ")))
+}
+
+/// A single operand: a literal word, or a phrase when `text` has several words.
+fn single(text: &str) -> Option<String> {
+    let mut words = text.split_whitespace();
+    match (words.next(), words.next()) {
+        (None, _) => None,
+        (Some(w), None) => literal(w),
+        _ => quoted(text),
+    }
+}
+
+/// A `publicationTypes` filter value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PublicationType {
+    Review,
+    JournalArticle,
+    CaseReport,
+    ClinicalTrial,
+    Conference,
+    Dataset,
+    Editorial,
+    LettersAndComments,
+    MetaAnalysis,
+    News,
+    Study,
+    Book,
+    BookSection,
+}
+
+impl PublicationType {
+    pub const ALL: [PublicationType; 13] = [
+        Self::Review,
+        Self::JournalArticle,
+        Self::CaseReport,
+        Self::ClinicalTrial,
+        Self::Conference,
+        Self::Dataset,
+        Self::Editorial,
+        Self::LettersAndComments,
+        Self::MetaAnalysis,
+        Self::News,
+        Self::Study,
+        Self::Book,
+        Self::BookSection,
+    ];
+
+    /// The name the API uses for this type.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Review => "Review",
+            Self::JournalArticle => "JournalArticle",
+            Self::CaseReport => "CaseReport",
+            Self::ClinicalTrial => "ClinicalTrial",
+            Self::Conference => "Conference",
+            Self::Dataset => "Dataset",
+            Self::Editorial => "Editorial",
+            Self::LettersAndComments => "LettersAndComments",
+            Self::MetaAnalysis => "MetaAnalysis",
+            Self::News => "News",
+            Self::Study => "Study",
+            Self::Book => "Book",
+            Self::BookSection => "BookSection",
+        }
+    }
+}
+
+impl fmt::Display for PublicationType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PublicationType {
+    type Err = Error;
+
+    /// Case-insensitive, so `meta-analysis` and `MetaAnalysis` both parse.
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let key = normalize(s);
+        Self::ALL
+            .into_iter()
+            .find(|t| normalize(t.as_str()) == key)
+            .ok_or_else(|| Error::InvalidFilter(format!("unknown publication type `{s}`")))
+    }
+}
+
+/// A `fieldsOfStudy` filter value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FieldOfStudy {
+    ComputerScience,
+    Medicine,
+    Chemistry,
+    Biology,
+    MaterialsScience,
+    Physics,
+    Geology,
+    Psychology,
+    Art,
+    History,
+    Geography,
+    Sociology,
+    Business,
+    PoliticalScience,
+    Economics,
+    Philosophy,
+    Mathematics,
+    Engineering,
+    EnvironmentalScience,
+    AgriculturalAndFoodSciences,
+    Education,
+    Law,
+    Linguistics,
+}
+
+impl FieldOfStudy {
+    pub const ALL: [FieldOfStudy; 23] = [
+        Self::ComputerScience,
+        Self::Medicine,
+        Self::Chemistry,
+        Self::Biology,
+        Self::MaterialsScience,
+        Self::Physics,
+        Self::Geology,
+        Self::Psychology,
+        Self::Art,
+        Self::History,
+        Self::Geography,
+        Self::Sociology,
+        Self::Business,
+        Self::PoliticalScience,
+        Self::Economics,
+        Self::Philosophy,
+        Self::Mathematics,
+        Self::Engineering,
+        Self::EnvironmentalScience,
+        Self::AgriculturalAndFoodSciences,
+        Self::Education,
+        Self::Law,
+        Self::Linguistics,
+    ];
+
+    /// The name the API uses for this field.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ComputerScience => "Computer Science",
+            Self::Medicine => "Medicine",
+            Self::Chemistry => "Chemistry",
+            Self::Biology => "Biology",
+            Self::MaterialsScience => "Materials Science",
+            Self::Physics => "Physics",
+            Self::Geology => "Geology",
+            Self::Psychology => "Psychology",
+            Self::Art => "Art",
+            Self::History => "History",
+            Self::Geography => "Geography",
+            Self::Sociology => "Sociology",
+            Self::Business => "Business",
+            Self::PoliticalScience => "Political Science",
+            Self::Economics => "Economics",
+            Self::Philosophy => "Philosophy",
+            Self::Mathematics => "Mathematics",
+            Self::Engineering => "Engineering",
+            Self::EnvironmentalScience => "Environmental Science",
+            Self::AgriculturalAndFoodSciences => "Agricultural and Food Sciences",
+            Self::Education => "Education",
+            Self::Law => "Law",
+            Self::Linguistics => "Linguistics",
+        }
+    }
+}
+
+impl fmt::Display for FieldOfStudy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for FieldOfStudy {
+    type Err = Error;
+
+    /// Case-insensitive, ignoring spaces and dashes: `computer-science` parses.
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let key = normalize(s);
+        Self::ALL
+            .into_iter()
+            .find(|f| normalize(f.as_str()) == key)
+            .ok_or_else(|| Error::InvalidFilter(format!("unknown field of study `{s}`")))
+    }
+}
+
+fn normalize(s: &str) -> String {
+    s.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Filters accepted by both paper search endpoints. Empty filters add no parameters.
+///
+/// ```
+/// use semantic_scholar::query::{FieldOfStudy, PublicationType, SearchFilters};
+///
+/// let filters = SearchFilters::new()
+///     .year("2015-")
+///     .publication_type(PublicationType::MetaAnalysis)
+///     .publication_type(PublicationType::Review)
+///     .field_of_study(FieldOfStudy::Psychology)
+///     .open_access_pdf();
+/// assert_eq!(filters.publication_types.len(), 2);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchFilters {
+    /// `"2023"`, `"2020-2025"`, `"2020-"`, `"-2023"`.
+    pub year: Option<String>,
+    /// Date range with optional month and day: `"2019-03-05:2020-06"`, `"2020:"`.
+    pub publication_date_or_year: Option<String>,
+    pub min_citations: Option<u32>,
+    /// Papers of any of these types.
+    pub publication_types: Vec<PublicationType>,
+    /// Papers in any of these fields.
+    pub fields_of_study: Vec<FieldOfStudy>,
+    /// Venue names or ISO4 abbreviations; papers from any of them.
+    pub venues: Vec<String>,
+    /// Only papers with a public PDF.
+    pub open_access_pdf: bool,
+}
+
+impl SearchFilters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn year(mut self, year: impl Into<String>) -> Self {
+        self.year = Some(year.into());
+        self
+    }
+
+    pub fn publication_date_or_year(mut self, range: impl Into<String>) -> Self {
+        self.publication_date_or_year = Some(range.into());
+        self
+    }
+
+    pub fn min_citations(mut self, min: u32) -> Self {
+        self.min_citations = Some(min);
+        self
+    }
+
+    pub fn publication_type(mut self, t: PublicationType) -> Self {
+        if !self.publication_types.contains(&t) {
+            self.publication_types.push(t);
+        }
+        self
+    }
+
+    pub fn field_of_study(mut self, f: FieldOfStudy) -> Self {
+        if !self.fields_of_study.contains(&f) {
+            self.fields_of_study.push(f);
+        }
+        self
+    }
+
+    pub fn venue(mut self, venue: impl Into<String>) -> Self {
+        self.venues.push(venue.into());
+        self
+    }
+
+    pub fn open_access_pdf(mut self) -> Self {
+        self.open_access_pdf = true;
+        self
+    }
+
+    /// Whether no filter is set.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Append the query parameters for these filters.
+    pub(crate) fn push_params(&self, params: &mut Vec<(String, String)>) {
+        let join = |items: Vec<&str>| items.join(",");
+        if let Some(y) = &self.year {
+            params.push(("year".into(), y.clone()));
+        }
+        if let Some(d) = &self.publication_date_or_year {
+            params.push(("publicationDateOrYear".into(), d.clone()));
+        }
+        if let Some(mc) = self.min_citations {
+            params.push(("minCitationCount".into(), mc.to_string()));
+        }
+        if !self.publication_types.is_empty() {
+            let types = self.publication_types.iter().map(|t| t.as_str()).collect();
+            params.push(("publicationTypes".into(), join(types)));
+        }
+        if !self.fields_of_study.is_empty() {
+            let fields = self.fields_of_study.iter().map(|f| f.as_str()).collect();
+            params.push(("fieldsOfStudy".into(), join(fields)));
+        }
+        if !self.venues.is_empty() {
+            let venues = self.venues.iter().map(String::as_str).collect();
+            params.push(("venue".into(), join(venues)));
+        }
+        if self.open_access_pdf {
+            // A presence flag: the API ignores its value.
+            params.push(("openAccessPdf".into(), String::new()));
+        }
+    }
+}
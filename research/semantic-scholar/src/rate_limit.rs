@@ -0,0 +1,76 @@
+//! Client-side token-bucket rate limiting.
+//!
+//! A keyed Semantic Scholar account is allowed 1 req/s. Rather than bursting and
+//! relying on 429 backoff, the client can wait for a token before every request.
+//! The bucket lives behind an `Arc`, so every clone of a client draws from it.
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::time::{sleep, Instant};
+
+/// A token bucket refilled at `requests_per_second`, holding at most `burst` tokens.
+///
+/// Cloning yields a handle to the same bucket.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    rate: f64,
+    burst: f64,
+    state: Arc<Mutex<Bucket>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// May go negative: each waiter reserves its token up front, which keeps
+    /// concurrent callers in FIFO order without holding the lock while sleeping.
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    /// # Panics
+    /// If `requests_per_second` is not positive or `burst` is zero.
+    pub fn new(requests_per_second: f64, burst: u32) -> Self {
+        assert!(
+            requests_per_second > 0.0,
+            "requests_per_second must be positive"
+        );
+        assert!(burst > 0, "burst must be at least 1");
+        Self {
+            rate: requests_per_second,
+            burst: f64::from(burst),
+            state: Arc::new(Mutex::new(Bucket {
+                tokens: f64::from(burst),
+                last_refill: Instant::now(),
+            })),
+        }
+    }
+
+    /// Take one token, sleeping until it is available.
+    pub async fn acquire(&self) {
+        let wait = self.reserve();
+        if !wait.is_zero() {
+            tracing::debug!(
+                wait_secs = wait.as_secs_f64(),
+                "waiting for Semantic Scholar rate limiter"
+            );
+            sleep(wait).await;
+        }
+    }
+
+    /// Reserve one token and return how long the caller must wait before using it.
+    fn reserve(&self) -> Duration {
+        let mut bucket = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.burst);
+        bucket.last_refill = now;
+        bucket.tokens -= 1.0;
+        if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / self.rate)
+        }
+    }
+}
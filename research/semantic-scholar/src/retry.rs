@@ -0,0 +1,110 @@
+//! Retry policy and helpers shared by every request the client sends.
+//!
+//! Transient failures — 429, 500/502/503/504, connect errors and timeouts — are
+//! retried with exponential backoff plus jitter. When the server sends a
+//! `Retry-After` header its value takes precedence over the computed backoff, up to
+//! the policy's `max_backoff`.
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::{Duration, SystemTime},
+};
+
+use reqwest::{header::HeaderMap, StatusCode};
+
+/// Retry behaviour for transient failures.
+///
+/// The wait before retry `n` (0-based) is `initial_backoff * multiplier^n`, capped at
+/// `max_backoff`, plus up to `jitter` × that amount of random delay. The default
+/// reproduces the historical 1s, 2s, 4s curve with 10% jitter on top.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt; `0` disables retrying.
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub multiplier: u32,
+    /// Fraction of the backoff added as random delay, `0.0` to disable.
+    pub jitter: f64,
+    /// Also retry 500/502/503/504 responses and connect errors/timeouts.
+    pub retry_transient: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            multiplier: 2,
+            jitter: 0.1,
+            retry_transient: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    /// Backoff before retry number `attempt` (0-based), without jitter.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = self.multiplier.max(1).saturating_pow(attempt);
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+
+    /// Wait before retry number `attempt`: the server's `Retry-After` if it sent one,
+    /// otherwise [`RetryPolicy::backoff`] plus jitter.
+    ///
+    /// `Retry-After` is clamped to `max_backoff`, so a proxy asking for a day (or a
+    /// far-future date) cannot park the client that long.
+    pub fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        match retry_after {
+            Some(wait) => wait.min(self.max_backoff),
+            None => {
+                let base = self.backoff(attempt);
+                base + base.mul_f64(self.jitter.clamp(0.0, 1.0) * random_unit())
+            }
+        }
+    }
+
+    /// Whether a response with this status should be retried under this policy.
+    pub fn should_retry_status(&self, status: StatusCode) -> bool {
+        match status.as_u16() {
+            429 => true,
+            500 | 502 | 503 | 504 => self.retry_transient,
+            _ => false,
+        }
+    }
+
+    /// Whether a transport-level error should be retried under this policy.
+    pub fn should_retry_error(&self, err: &reqwest::Error) -> bool {
+        self.retry_transient && (err.is_timeout() || err.is_connect())
+    }
+}
+
+/// Parse a `Retry-After` header, either delta-seconds or an HTTP-date.
+pub fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(reqwest::header::RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = httpdate::parse_http_date(value).ok()?;
+    Some(
+        at.duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+/// A value in `[0, 1)` — good enough for jitter without pulling in an RNG crate.
+fn random_unit() -> f64 {
+    let bits = RandomState::new().build_hasher().finish();
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
@@ -0,0 +1,469 @@
+//! Forward and backward citation snowballing.
+//!
+//! Starting from seed papers, [`SemanticScholarClient::snowball`] follows citations
+//! (forward: papers citing the frontier) and references (backward: papers the
+//! frontier cites) for a number of hops, keeps the papers and edges that pass the
+//! [`SnowballOptions`] filters and returns the result as a [`CitationGraph`]. Papers
+//! are deduplicated by Semantic Scholar ID, so a paper reached along several paths
+//! appears once, at the hop it was first found.
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write as _,
+    str::FromStr,
+};
+
+use futures::TryStreamExt;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::SemanticScholarClient,
+    error::Error,
+    fan_out::DEFAULT_CONCURRENCY,
+    fields::{Endpoint, FieldSet},
+    paper_id::PaperId,
+    types::{CitationIntent, Paper},
+};
+
+/// Which edges to follow from each paper.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Direction {
+    /// Papers citing the frontier.
+    Forward,
+    /// Papers the frontier cites.
+    Backward,
+    #[default]
+    Both,
+}
+
+impl Direction {
+    fn forward(self) -> bool {
+        matches!(self, Self::Forward | Self::Both)
+    }
+
+    fn backward(self) -> bool {
+        matches!(self, Self::Backward | Self::Both)
+    }
+}
+
+impl FromStr for Direction {
+    type Err = Error;
+
+    /// `forward` (or `citations`), `backward` (or `references`) or `both`.
+    fn from_str(s: &str) -> Result<Self, Error> {
+        match s.to_ascii_lowercase().as_str() {
+            "forward" | "citations" => Ok(Self::Forward),
+            "backward" | "references" => Ok(Self::Backward),
+            "both" => Ok(Self::Both),
+            _ => Err(Error::InvalidFilter(format!(
+                "unknown direction `{s}` (expected forward, backward or both)"
+            ))),
+        }
+    }
+}
+
+/// How far to expand and what to keep. Seeds are always kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnowballOptions {
+    /// Expansion rounds; 1 keeps only the seeds' direct neighbours.
+    pub hops: u32,
+    pub direction: Direction,
+    /// Year filter on discovered papers: `"2023"`, `"2020-2025"`, `"2020-"`, `"-2023"`.
+    pub year: Option<String>,
+    pub min_citations: Option<u64>,
+    /// Only follow edges Semantic Scholar marks as influential.
+    pub influential_only: bool,
+    /// Only follow edges with any of these intents; empty follows all.
+    pub intents: Vec<CitationIntent>,
+    /// Citations or references read per paper and direction.
+    pub max_edges_per_paper: usize,
+    /// Stop adding papers once the graph holds this many.
+    pub max_papers: usize,
+    /// Paper fields to fetch, selected for [`Endpoint::Citations`] and re-validated
+    /// for the seed lookup and references; must include `year` and `citationCount`
+    /// for those filters. Citation edge fields are added automatically.
+    pub fields: FieldSet,
+    /// Papers expanded at once.
+    pub concurrency: usize,
+}
+
+impl Default for SnowballOptions {
+    fn default() -> Self {
+        Self {
+            hops: 1,
+            direction: Direction::Both,
+            year: None,
+            min_citations: None,
+            influential_only: false,
+            intents: Vec::new(),
+            max_edges_per_paper: 1000,
+            max_papers: 500,
+            fields: FieldSet::paper_brief(Endpoint::Citations)
+                .expect("PAPER_FIELDS_BRIEF is valid for citations"),
+            concurrency: DEFAULT_CONCURRENCY,
+        }
+    }
+}
+
+/// A paper in the graph.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnowballNode {
+    /// Semantic Scholar paper ID, also the node ID used by edges.
+    pub id: String,
+    /// 0 for seeds, otherwise the hop at which the paper was first reached.
+    pub hop: u32,
+    pub paper: Paper,
+}
+
+/// `citing` cites `cited`, both node IDs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnowballEdge {
+    pub citing: String,
+    pub cited: String,
+    pub intents: Vec<CitationIntent>,
+    pub is_influential: bool,
+    pub contexts: Vec<String>,
+}
+
+/// Result of a snowball search.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CitationGraph {
+    /// Seeds first, then papers in the order they were found.
+    pub nodes: Vec<SnowballNode>,
+    pub edges: Vec<SnowballEdge>,
+    /// Seeds Semantic Scholar did not recognise or returned without a `paperId`.
+    pub unresolved_seeds: Vec<PaperId>,
+    /// Papers whose citations or references could not be read. Their neighbours
+    /// are missing, but the rest of the graph is complete.
+    pub failed_expansions: Vec<ExpansionFailure>,
+}
+
+/// A paper in the graph that could not be expanded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExpansionFailure {
+    /// Node ID of the paper.
+    pub id: String,
+    /// The hop whose expansion failed.
+    pub hop: u32,
+    pub error: String,
+}
+
+/// A neighbour of an expanded paper and the edge that led to it.
+struct Link {
+    paper: Paper,
+    /// Whether `paper` cites the expanded paper (forward) rather than the reverse.
+    cites_expanded: bool,
+    intents: Vec<CitationIntent>,
+    is_influential: bool,
+    contexts: Vec<String>,
+}
+
+impl SemanticScholarClient {
+    /// Expand `seeds` through citations and references; see the [module docs](self).
+    ///
+    /// Seeds the API answers with 404, or with a paper lacking a `paperId`, are listed
+    /// in [`CitationGraph::unresolved_seeds`]; any other seed lookup error aborts the
+    /// search. A paper whose neighbours fail to load is recorded in
+    /// [`CitationGraph::failed_expansions`] and the search carries on without it.
+    pub async fn snowball(
+        &self,
+        seeds: &[PaperId],
+        options: &SnowballOptions,
+    ) -> Result<CitationGraph, Error> {
+        let years = options.year.as_deref().map(parse_year_range).transpose()?;
+        let mut graph = CitationGraph::default();
+        let mut known: HashSet<String> = HashSet::new();
+        let mut edge_keys: HashSet<(String, String)> = HashSet::new();
+
+        let fields = options.fields.for_endpoint(Endpoint::PaperDetail)?;
+        let edge_fields = EdgeFields {
+            citations: options
+                .fields
+                .for_endpoint(Endpoint::Citations)?
+                .with_edge_fields()?,
+            references: options
+                .fields
+                .for_endpoint(Endpoint::References)?
+                .with_edge_fields()?,
+        };
+        let resolved = self
+            .fetch_many(
+                seeds,
+                options.concurrency,
+                |client, id| {
+                    let fields = fields.clone();
+                    async move { client.get_paper(&id, &fields).await }
+                },
+                |_| {},
+            )
+            .await;
+        let mut frontier = Vec::new();
+        for (seed, result) in resolved {
+            match result {
+                Ok(paper) => {
+                    let Some(id) = paper.paper_id.clone() else {
+                        graph.unresolved_seeds.push(seed);
+                        continue;
+                    };
+                    if known.insert(id.clone()) {
+                        frontier.push(PaperId::S2(id.clone()));
+                        graph.nodes.push(SnowballNode { id, hop: 0, paper });
+                    }
+                }
+                Err(Error::NotFound { .. }) => graph.unresolved_seeds.push(seed),
+                Err(e) => return Err(e),
+            }
+        }
+
+        for hop in 1..=options.hops {
+            if frontier.is_empty() {
+                break;
+            }
+            let expanded = self
+                .fetch_many(
+                    &frontier,
+                    options.concurrency,
+                    |client, id| neighbours(client, id, edge_fields.clone(), options.clone()),
+                    |_| {},
+                )
+                .await;
+
+            let mut next = Vec::new();
+            for (id, links) in expanded {
+                let expanded_id = id.to_api_string();
+                let links = match links {
+                    Ok(links) => links,
+                    Err(e) => {
+                        graph.failed_expansions.push(ExpansionFailure {
+                            id: expanded_id,
+                            hop,
+                            error: e.to_string(),
+                        });
+                        continue;
+                    }
+                };
+                for link in links {
+                    if !keeps_edge(options, &link) {
+                        continue;
+                    }
+                    let Some(other) = link.paper.paper_id.clone() else {
+                        continue;
+                    };
+                    if !known.contains(&other) {
+                        if graph.nodes.len() >= options.max_papers
+                            || !keeps_paper(options, years, &link.paper)
+                        {
+                            continue;
+                        }
+                        known.insert(other.clone());
+                        next.push(PaperId::S2(other.clone()));
+                        graph.nodes.push(SnowballNode {
+                            id: other.clone(),
+                            hop,
+                            paper: link.paper,
+                        });
+                    }
+                    let (citing, cited) = if link.cites_expanded {
+                        (other, expanded_id.clone())
+                    } else {
+                        (expanded_id.clone(), other)
+                    };
+                    if edge_keys.insert((citing.clone(), cited.clone())) {
+                        graph.edges.push(SnowballEdge {
+                            citing,
+                            cited,
+                            intents: link.intents,
+                            is_influential: link.is_influential,
+                            contexts: link.contexts,
+                        });
+                    }
+                }
+            }
+            frontier = next;
+        }
+        Ok(graph)
+    }
+}
+
+/// Field selections for the two edge endpoints, including the edge attributes.
+#[derive(Clone)]
+struct EdgeFields {
+    citations: FieldSet,
+    references: FieldSet,
+}
+
+/// Citations and/or references of `id`, as links.
+async fn neighbours(
+    client: SemanticScholarClient,
+    id: PaperId,
+    fields: EdgeFields,
+    options: SnowballOptions,
+) -> Result<Vec<Link>, Error> {
+    let mut links = Vec::new();
+    if options.direction.forward() {
+        let items: Vec<_> = client
+            .citations_stream(&id, &fields.citations, options.max_edges_per_paper)
+            .try_collect()
+            .await?;
+        links.extend(items.into_iter().filter_map(|c| {
+            Some(Link {
+                paper: c.citing_paper?,
+                cites_expanded: true,
+                intents: c.intents.unwrap_or_default(),
+                is_influential: c.is_influential.unwrap_or(false),
+                contexts: c.contexts.unwrap_or_default(),
+            })
+        }));
+    }
+    if options.direction.backward() {
+        let items: Vec<_> = client
+            .references_stream(&id, &fields.references, options.max_edges_per_paper)
+            .try_collect()
+            .await?;
+        links.extend(items.into_iter().filter_map(|r| {
+            Some(Link {
+                paper: r.cited_paper?,
+                cites_expanded: false,
+                intents: r.intents.unwrap_or_default(),
+                is_influential: r.is_influential.unwrap_or(false),
+                contexts: r.contexts.unwrap_or_default(),
+            })
+        }));
+    }
+    Ok(links)
+}
+
+fn keeps_edge(options: &SnowballOptions, link: &Link) -> bool {
+    (!options.influential_only || link.is_influential)
+        && (options.intents.is_empty() || link.intents.iter().any(|i| options.intents.contains(i)))
+}
+
+fn keeps_paper(
+    options: &SnowballOptions,
+    years: Option<(Option<u32>, Option<u32>)>,
+    paper: &Paper,
+) -> bool {
+    let in_years = years.map_or(true, |(from, to)| {
+        paper
+            .year
+            .is_some_and(|y| from.map_or(true, |f| y >= f) && to.map_or(true, |t| y <= t))
+    });
+    let cited_enough = options
+        .min_citations
+        .map_or(true, |min| paper.citation_count.unwrap_or(0) >= min);
+    in_years && cited_enough
+}
+
+/// `"2019"`, `"2015-2020"`, `"2015-"` or `"-2020"` as inclusive bounds.
+fn parse_year_range(filter: &str) -> Result<(Option<u32>, Option<u32>), Error> {
+    let invalid = || Error::InvalidFilter(format!("invalid year range `{filter}`"));
+    let bound = |s: &str| -> Result<Option<u32>, Error> {
+        let s = s.trim();
+        if s.is_empty() {
+            Ok(None)
+        } else {
+            s.parse().map(Some).map_err(|_| invalid())
+        }
+    };
+    match filter.split_once('-') {
+        Some((from, to)) => Ok((bound(from)?, bound(to)?)),
+        None => {
+            let year = bound(filter)?.ok_or_else(invalid)?;
+            Ok((Some(year), Some(year)))
+        }
+    }
+}
+
+impl CitationGraph {
+    pub fn node(&self, id: &str) -> Option<&SnowballNode> {
+        self.nodes.iter().find(|n| n.id == id)
+    }
+
+    /// Number of edges into each node (citations within the graph).
+    pub fn in_degrees(&self) -> HashMap<&str, usize> {
+        let mut degrees = HashMap::new();
+        for edge in &self.edges {
+            *degrees.entry(edge.cited.as_str()).or_insert(0) += 1;
+        }
+        degrees
+    }
+
+    /// The graph as GraphML, for Gephi, Cytoscape or networkx. Edges point from the
+    /// citing to the cited paper.
+    pub fn to_graphml(&self) -> String {
+        let mut out = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n\
+             \x20 <key id=\"title\" for=\"node\" attr.name=\"title\" attr.type=\"string\"/>\n\
+             \x20 <key id=\"year\" for=\"node\" attr.name=\"year\" attr.type=\"int\"/>\n\
+             \x20 <key id=\"citations\" for=\"node\" attr.name=\"citationCount\" attr.type=\"long\"/>\n\
+             \x20 <key id=\"doi\" for=\"node\" attr.name=\"doi\" attr.type=\"string\"/>\n\
+             \x20 <key id=\"hop\" for=\"node\" attr.name=\"hop\" attr.type=\"int\"/>\n\
+             \x20 <key id=\"intents\" for=\"edge\" attr.name=\"intents\" attr.type=\"string\"/>\n\
+             \x20 <key id=\"influential\" for=\"edge\" attr.name=\"isInfluential\" attr.type=\"boolean\"/>\n\
+             \x20 <graph id=\"snowball\" edgedefault=\"directed\">\n",
+        );
+        for node in &self.nodes {
+            let _ = writeln!(out, "    <node id=\"{}\">", xml_escape(&node.id));
+            let p = &node.paper;
+            if let Some(title) = &p.title {
+                let _ = writeln!(
+                    out,
+                    "      <data key=\"title\">{}</data>",
+                    xml_escape(title)
+                );
+            }
+            if let Some(year) = p.year {
+                let _ = writeln!(out, "      <data key=\"year\">{year}</data>");
+            }
+            if let Some(count) = p.citation_count {
+                let _ = writeln!(out, "      <data key=\"citations\">{count}</data>");
+            }
+            if let Some(doi) = p.doi() {
+                let _ = writeln!(out, "      <data key=\"doi\">{}</data>", xml_escape(doi));
+            }
+            let _ = writeln!(out, "      <data key=\"hop\">{}</data>", node.hop);
+            out.push_str("    </node>\n");
+        }
+        for edge in &self.edges {
+            let _ = writeln!(
+                out,
+                "    <edge source=\"{}\" target=\"{}\">",
+                xml_escape(&edge.citing),
+                xml_escape(&edge.cited)
+            );
+            if !edge.intents.is_empty() {
+                let intents: Vec<&str> = edge.intents.iter().map(CitationIntent::as_str).collect();
+                let _ = writeln!(
+                    out,
+                    "      <data key=\"intents\">{}</data>",
+                    xml_escape(&intents.join(","))
+                );
+            }
+            let _ = writeln!(
+                out,
+                "      <data key=\"influential\">{}</data>",
+                edge.is_influential
+            );
+            out.push_str("    </edge>\n");
+        }
+        out.push_str("  </graph>\n</graphml>\n");
+        out
+    }
+}
+
+fn xml_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            // Control characters other than tab and newlines are not allowed in XML 1.0.
+            c if c.is_control() && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
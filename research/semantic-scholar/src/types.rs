@@ -1,20 +1,32 @@
+use std::{fmt, str::FromStr};
+
 use serde::{Deserialize, Serialize};
 
+use crate::error::Error;
+
 /// Fields supported by `/paper/search` and `/paper/search/bulk` endpoints.
 /// Note: `tldr` and `influentialCitationCount` are NOT available in search endpoints.
+/// Use [`crate::fields::FieldSet`] to build a custom selection that is checked up front.
 pub const SEARCH_FIELDS: &str =
-    "paperId,title,abstract,year,citationCount,\
-     openAccessPdf,authors,fieldsOfStudy,url,publicationDate,isOpenAccess,venue";
+    "paperId,title,abstract,year,citationCount,referenceCount,\
+     openAccessPdf,authors,fieldsOfStudy,s2FieldsOfStudy,url,publicationDate,isOpenAccess,venue,\
+     externalIds,publicationTypes,journal";
 
-/// Rich fields for `/paper/{id}` single-paper detail — includes tldr and influential count.
+/// Rich fields for `/paper/{id}` single-paper detail — includes tldr, influential count
+/// and pre-formatted citation styles.
 pub const PAPER_FIELDS_FULL: &str =
-    "paperId,title,abstract,year,citationCount,influentialCitationCount,\
-     tldr,openAccessPdf,authors,fieldsOfStudy,url,publicationDate,isOpenAccess,venue";
+    "paperId,title,abstract,year,citationCount,referenceCount,influentialCitationCount,\
+     tldr,openAccessPdf,authors,fieldsOfStudy,s2FieldsOfStudy,url,publicationDate,isOpenAccess,\
+     venue,externalIds,publicationTypes,journal,citationStyles";
 
 /// Lightweight fields for nested paper objects (citations, references, recommendations).
 /// Note: `tldr` is NOT available in citation/reference/recommendations endpoints either.
 pub const PAPER_FIELDS_BRIEF: &str =
-    "paperId,title,year,citationCount,authors,url";
+    "paperId,title,year,citationCount,authors,url,externalIds,publicationTypes";
+
+/// Fields for `/author/search` and `/author/{id}` — profile plus bibliometrics.
+pub const AUTHOR_FIELDS: &str =
+    "authorId,name,url,affiliations,homepage,paperCount,citationCount,hIndex";
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -34,6 +46,67 @@ pub struct Paper {
     pub venue: Option<String>,
     pub publication_date: Option<String>,
     pub is_open_access: Option<bool>,
+    pub reference_count: Option<u64>,
+    pub external_ids: Option<ExternalIds>,
+    /// Study design labels, e.g. `Review`, `MetaAnalysis`, `ClinicalTrial`, `JournalArticle`.
+    pub publication_types: Option<Vec<String>>,
+    pub journal: Option<Journal>,
+    pub s2_fields_of_study: Option<Vec<S2FieldOfStudy>>,
+    pub citation_styles: Option<CitationStyles>,
+}
+
+impl Paper {
+    /// DOI from `externalIds`, if requested and known.
+    pub fn doi(&self) -> Option<&str> {
+        self.external_ids.as_ref()?.doi.as_deref()
+    }
+
+    /// Whether `publicationTypes` contains `kind` (e.g. `"MetaAnalysis"`).
+    pub fn has_publication_type(&self, kind: &str) -> bool {
+        self.publication_types
+            .as_ref()
+            .is_some_and(|types| types.iter().any(|t| t == kind))
+    }
+}
+
+/// Identifiers of the paper in other catalogues.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ExternalIds {
+    #[serde(rename = "DOI")]
+    pub doi: Option<String>,
+    #[serde(rename = "PubMed")]
+    pub pubmed: Option<String>,
+    #[serde(rename = "PubMedCentral")]
+    pub pubmed_central: Option<String>,
+    #[serde(rename = "ArXiv")]
+    pub arxiv: Option<String>,
+    #[serde(rename = "MAG")]
+    pub mag: Option<String>,
+    #[serde(rename = "ACL")]
+    pub acl: Option<String>,
+    #[serde(rename = "DBLP")]
+    pub dblp: Option<String>,
+    #[serde(rename = "CorpusId")]
+    pub corpus_id: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Journal {
+    pub name: Option<String>,
+    pub volume: Option<String>,
+    pub pages: Option<String>,
+}
+
+/// A field of study with its provenance (`external` or `s2-fos-model`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct S2FieldOfStudy {
+    pub category: Option<String>,
+    pub source: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CitationStyles {
+    pub bibtex: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -49,11 +122,36 @@ pub struct OpenAccessPdf {
     pub status: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// An author. Nested in papers only `author_id` and `name` are populated; the
+/// remaining fields come from the author endpoints (see [`AUTHOR_FIELDS`]).
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Author {
     pub author_id: Option<String>,
     pub name: Option<String>,
+    pub url: Option<String>,
+    pub affiliations: Option<Vec<String>>,
+    pub homepage: Option<String>,
+    pub paper_count: Option<u64>,
+    pub citation_count: Option<u64>,
+    pub h_index: Option<u64>,
+}
+
+/// Response from `/graph/v1/author/search`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorSearchResponse {
+    pub total: Option<u64>,
+    pub offset: Option<u64>,
+    pub next: Option<u64>,
+    pub data: Vec<Author>,
+}
+
+/// Response from `/graph/v1/author/{id}/papers`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorPapersResponse {
+    pub offset: Option<u64>,
+    pub next: Option<u64>,
+    pub data: Vec<Paper>,
 }
 
 /// Response from `/graph/v1/paper/search/bulk` — up to 10M results, sorted by field.
@@ -65,6 +163,22 @@ pub struct BulkSearchResponse {
     pub data: Vec<Paper>,
 }
 
+/// Best title match from `/graph/v1/paper/search/match`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaperMatch {
+    /// How closely the title matched; higher is better.
+    pub match_score: Option<f64>,
+    #[serde(flatten)]
+    pub paper: Paper,
+}
+
+/// Response envelope for `/graph/v1/paper/search/match` (holds at most one match).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaperMatchResponse {
+    pub data: Vec<PaperMatch>,
+}
+
 /// Response from `/graph/v1/paper/search` — relevance-ranked, max 1000 results.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchResponse {
@@ -74,26 +188,105 @@ pub struct SearchResponse {
     pub data: Vec<Paper>,
 }
 
+/// Why one paper cites another, as classified by Semantic Scholar.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum CitationIntent {
+    /// Cited as background or related work.
+    Background,
+    /// The citing paper uses the cited paper's methods, tools or data.
+    Methodology,
+    /// The citing paper compares with or extends the cited paper's results.
+    Result,
+    /// A label this client does not know yet, kept verbatim.
+    Other(String),
+}
+
+impl CitationIntent {
+    pub const KNOWN: [CitationIntent; 3] = [Self::Background, Self::Methodology, Self::Result];
+
+    /// The name the API uses for this intent.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Background => "background",
+            Self::Methodology => "methodology",
+            Self::Result => "result",
+            Self::Other(other) => other,
+        }
+    }
+}
+
+impl From<String> for CitationIntent {
+    fn from(s: String) -> Self {
+        match s.to_ascii_lowercase().as_str() {
+            "background" => Self::Background,
+            "methodology" | "method" => Self::Methodology,
+            "result" | "results" => Self::Result,
+            _ => Self::Other(s),
+        }
+    }
+}
+
+impl From<CitationIntent> for String {
+    fn from(intent: CitationIntent) -> Self {
+        intent.as_str().to_string()
+    }
+}
+
+impl fmt::Display for CitationIntent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for CitationIntent {
+    type Err = Error;
+
+    /// Accepts only the known intents, for use as a filter.
+    fn from_str(s: &str) -> Result<Self, Error> {
+        match Self::from(s.to_string()) {
+            Self::Other(_) => Err(Error::InvalidFilter(format!(
+                "unknown citation intent `{s}` (expected background, methodology or result)"
+            ))),
+            intent => Ok(intent),
+        }
+    }
+}
+
 /// One entry in `/paper/{id}/citations` — the paper doing the citing.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CitationItem {
     pub citing_paper: Option<Paper>,
-    pub intents: Option<Vec<String>>,
+    pub intents: Option<Vec<CitationIntent>>,
     pub is_influential: Option<bool>,
+    /// Sentences of the citing paper that contain the citation.
     pub contexts: Option<Vec<String>>,
 }
 
+impl CitationItem {
+    pub fn has_intent(&self, intent: &CitationIntent) -> bool {
+        self.intents.iter().flatten().any(|i| i == intent)
+    }
+}
+
 /// One entry in `/paper/{id}/references` — the paper being cited.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ReferenceItem {
     pub cited_paper: Option<Paper>,
-    pub intents: Option<Vec<String>>,
+    pub intents: Option<Vec<CitationIntent>>,
     pub is_influential: Option<bool>,
+    /// Sentences of the citing paper that contain the citation.
     pub contexts: Option<Vec<String>>,
 }
 
+impl ReferenceItem {
+    pub fn has_intent(&self, intent: &CitationIntent) -> bool {
+        self.intents.iter().flatten().any(|i| i == intent)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CitationsResponse {
     pub data: Vec<CitationItem>,
@@ -114,3 +307,131 @@ pub struct ReferencesResponse {
 pub struct RecommendationsResponse {
     pub recommended_papers: Vec<Paper>,
 }
+
+/// Response from `/graph/v1/snippet/search` — passages from paper bodies, best first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnippetSearchResponse {
+    pub data: Vec<SnippetMatch>,
+    pub retrieval_version: Option<String>,
+}
+
+/// One hit from snippet search: the passage and the paper it came from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnippetMatch {
+    pub snippet: Snippet,
+    /// Relevance to the query; higher is better.
+    pub score: Option<f64>,
+    pub paper: SnippetPaper,
+}
+
+/// A passage of up to ~500 words from a paper's title, abstract or body.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Snippet {
+    pub text: String,
+    /// `"title"`, `"abstract"` or `"body"`.
+    pub snippet_kind: Option<String>,
+    /// Heading of the section the passage sits in, for body snippets.
+    pub section: Option<String>,
+    /// Character offsets of the passage within the paper's full text.
+    pub snippet_offset: Option<TextSpan>,
+    pub annotations: Option<SnippetAnnotations>,
+}
+
+/// A `[start, end)` character range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct TextSpan {
+    pub start: u64,
+    pub end: u64,
+}
+
+/// Sentence boundaries and citation markers inside a [`Snippet`], relative to its text.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SnippetAnnotations {
+    pub sentences: Option<Vec<TextSpan>>,
+    pub ref_mentions: Option<Vec<RefMention>>,
+}
+
+/// An in-text citation, resolved to the cited paper when Semantic Scholar could match it.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RefMention {
+    pub start: u64,
+    pub end: u64,
+    pub matched_paper_corpus_id: Option<String>,
+}
+
+/// The paper a [`Snippet`] belongs to. Snippet search identifies papers by corpus ID only.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SnippetPaper {
+    pub corpus_id: Option<String>,
+    pub title: Option<String>,
+    pub authors: Option<Vec<String>>,
+    pub open_access_info: Option<OpenAccessInfo>,
+}
+
+impl SnippetPaper {
+    /// `CorpusId:…` for looking the paper up with the other endpoints.
+    pub fn paper_id(&self) -> Option<crate::PaperId> {
+        self.corpus_id.as_deref()?.parse().ok().map(crate::PaperId::CorpusId)
+    }
+}
+
+/// Licence information for the text a snippet was taken from.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct OpenAccessInfo {
+    pub license: Option<String>,
+    pub status: Option<String>,
+    pub disclaimer: Option<String>,
+}
+
+/// Response from `/graph/v1/paper/autocomplete`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct AutocompleteResponse {
+    pub matches: Vec<AutocompleteMatch>,
+}
+
+/// A title suggestion for a partial query.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AutocompleteMatch {
+    /// Semantic Scholar paper ID.
+    pub id: String,
+    pub title: Option<String>,
+    /// Display string such as `"Cohen et al., 2012"`; see [`Self::authors`] and [`Self::year`].
+    pub authors_year: Option<String>,
+}
+
+impl AutocompleteMatch {
+    pub fn paper_id(&self) -> crate::PaperId {
+        crate::PaperId::S2(self.id.clone())
+    }
+
+    /// The author part of `authorsYear`, e.g. `"Cohen et al."`.
+    pub fn authors(&self) -> Option<&str> {
+        let text = self.authors_year.as_deref()?.trim();
+        let authors = match split_year(text) {
+            Some((authors, _)) => authors,
+            None => text,
+        };
+        (!authors.is_empty()).then_some(authors)
+    }
+
+    /// The year part of `authorsYear`.
+    pub fn year(&self) -> Option<u32> {
+        split_year(self.authors_year.as_deref()?.trim()).map(|(_, year)| year)
+    }
+}
+
+/// Split `"Cohen et al., 2012"` (or a bare `"2012"`) into authors and year.
+fn split_year(text: &str) -> Option<(&str, u32)> {
+    let (authors, year) = text.rsplit_once(',').unwrap_or(("", text));
+    let year = year.trim();
+    if year.len() != 4 {
+        return None;
+    }
+    Some((authors.trim(), year.parse().ok()?))
+}
@@ -0,0 +1,183 @@
+/// Tests for the author endpoints against an in-process axum mock.
+mod common;
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use common::fields_for;
+use futures::TryStreamExt;
+use semantic_scholar::{
+    fields::{AuthorFieldSet, Endpoint},
+    types::AUTHOR_FIELDS,
+    SemanticScholarClient,
+};
+use serde_json::{json, Value};
+
+const AUTHOR_PAPERS: usize = 12;
+const SERVER_PAGE: usize = 5;
+
+/// Path and query string of every request received, in order.
+type Requests = Arc<Mutex<Vec<(String, HashMap<String, String>)>>>;
+
+fn author(id: &str) -> Value {
+    json!({
+        "authorId": id,
+        "name": "Judith A. Cohen",
+        "affiliations": ["Allegheny General Hospital"],
+        "paperCount": 250,
+        "citationCount": 30000,
+        "hIndex": 70,
+    })
+}
+
+async fn search_handler(
+    State(reqs): State<Requests>,
+    Query(q): Query<HashMap<String, String>>,
+) -> Json<Value> {
+    reqs.lock().unwrap().push(("search".into(), q));
+    Json(json!({ "total": 1, "offset": 0, "data": [author("1741101")] }))
+}
+
+async fn author_handler(
+    State(reqs): State<Requests>,
+    Path(id): Path<String>,
+    Query(q): Query<HashMap<String, String>>,
+) -> Json<Value> {
+    reqs.lock().unwrap().push((id.clone(), q));
+    Json(author(&id))
+}
+
+async fn papers_handler(
+    State(reqs): State<Requests>,
+    Path(id): Path<String>,
+    Query(q): Query<HashMap<String, String>>,
+) -> Json<Value> {
+    let offset: usize = q.get("offset").map_or(0, |o| o.parse().unwrap());
+    let limit: usize = q["limit"].parse().unwrap();
+    reqs.lock().unwrap().push((format!("{id}/papers"), q));
+    let end = (offset + limit.min(SERVER_PAGE)).min(AUTHOR_PAPERS);
+    let next = (end < AUTHOR_PAPERS).then_some(end);
+    Json(json!({
+        "offset": offset,
+        "next": next,
+        "data": (offset..end)
+            .map(|i| json!({ "paperId": format!("p{i}"), "title": format!("TF-CBT study {i}") }))
+            .collect::<Vec<_>>(),
+    }))
+}
+
+async fn start_mock() -> (SemanticScholarClient, Requests) {
+    let reqs = Requests::default();
+    let app = Router::new()
+        .route("/graph/v1/author/search", get(search_handler))
+        .route("/graph/v1/author/:id", get(author_handler))
+        .route("/graph/v1/author/:id/papers", get(papers_handler))
+        .with_state(reqs.clone());
+
+    let client = common::client(&common::serve(app).await);
+    (client, reqs)
+}
+
+#[tokio::test]
+async fn search_authors_parses_bibliometrics() {
+    let (client, reqs) = start_mock().await;
+    let resp = client
+        .search_authors("Judith Cohen", &AuthorFieldSet::default(), 5, 0)
+        .await
+        .unwrap();
+
+    assert_eq!(resp.total, Some(1));
+    let a = &resp.data[0];
+    assert_eq!(a.author_id.as_deref(), Some("1741101"));
+    assert_eq!(a.h_index, Some(70));
+    assert_eq!(a.paper_count, Some(250));
+    assert_eq!(a.citation_count, Some(30000));
+    assert_eq!(
+        a.affiliations.as_ref().unwrap()[0],
+        "Allegheny General Hospital"
+    );
+
+    let reqs = reqs.lock().unwrap();
+    assert_eq!(reqs[0].1["query"], "Judith Cohen");
+    assert_eq!(reqs[0].1["fields"], AUTHOR_FIELDS);
+}
+
+#[tokio::test]
+async fn get_author_hits_author_path() {
+    let (client, reqs) = start_mock().await;
+    let a = client
+        .get_author("1741101", &AuthorFieldSet::default())
+        .await
+        .unwrap();
+
+    assert_eq!(a.name.as_deref(), Some("Judith A. Cohen"));
+    assert_eq!(reqs.lock().unwrap()[0].0, "1741101");
+}
+
+#[tokio::test]
+async fn author_id_is_encoded_as_one_segment() {
+    let (client, reqs) = start_mock().await;
+    client
+        .get_author("17/41 101", &AuthorFieldSet::default())
+        .await
+        .unwrap();
+    client
+        .get_author_papers(
+            "17/41 101",
+            &fields_for(Endpoint::AuthorPapers, "title"),
+            5,
+            0,
+        )
+        .await
+        .unwrap();
+
+    let reqs = reqs.lock().unwrap();
+    assert_eq!(reqs[0].0, "17/41 101");
+    assert_eq!(reqs[1].0, "17/41 101/papers");
+}
+
+#[tokio::test]
+async fn get_author_papers_sends_offset() {
+    let (client, reqs) = start_mock().await;
+    let resp = client
+        .get_author_papers(
+            "1741101",
+            &fields_for(Endpoint::AuthorPapers, "title"),
+            5,
+            10,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(resp.data.len(), 2);
+    assert_eq!(resp.next, None);
+    assert_eq!(reqs.lock().unwrap()[0].1["offset"], "10");
+}
+
+#[tokio::test]
+async fn author_papers_stream_walks_all_pages() {
+    let (client, reqs) = start_mock().await;
+    let papers: Vec<_> = client
+        .author_papers_stream("1741101", &fields_for(Endpoint::AuthorPapers, "title"), 100)
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(papers.len(), AUTHOR_PAPERS);
+    assert_eq!(reqs.lock().unwrap().len(), 3);
+}
+
+#[test]
+fn nested_author_without_bibliometrics_still_parses() {
+    let a: semantic_scholar::Author =
+        serde_json::from_value(json!({ "authorId": "1", "name": "A. Author" })).unwrap();
+    assert_eq!(a.name.as_deref(), Some("A. Author"));
+    assert!(a.h_index.is_none());
+}
@@ -0,0 +1,67 @@
+/// Tests for title autocomplete against an in-process axum mock.
+mod common;
+
+use std::collections::HashMap;
+
+use axum::{extract::Query, routing::get, Json, Router};
+use semantic_scholar::{types::AutocompleteMatch, PaperId, SemanticScholarClient};
+use serde_json::{json, Value};
+
+async fn autocomplete_handler(Query(q): Query<HashMap<String, String>>) -> Json<Value> {
+    let query = q.get("query").cloned().unwrap_or_default().to_lowercase();
+    let all = [
+        (
+            "a1b2",
+            "Trauma-focused cognitive behavioral therapy for children",
+            "Cohen et al., 2012",
+        ),
+        (
+            "c3d4",
+            "Trauma-focused CBT: a meta-analysis",
+            "de Arellano, 2014",
+        ),
+        ("e5f6", "Treating trauma in adolescents", ""),
+    ];
+    let matches: Vec<Value> = all
+        .iter()
+        .filter(|(_, title, _)| title.to_lowercase().starts_with(&query))
+        .map(|(id, title, ay)| json!({ "id": id, "title": title, "authorsYear": ay }))
+        .collect();
+    Json(json!({ "matches": matches }))
+}
+
+async fn start_mock() -> SemanticScholarClient {
+    let app = Router::new().route("/graph/v1/paper/autocomplete", get(autocomplete_handler));
+    common::client(&common::serve(app).await)
+}
+
+#[tokio::test]
+async fn partial_titles_resolve_to_paper_ids() {
+    let client = start_mock().await;
+
+    let resp = client.autocomplete("trauma-focused").await.unwrap();
+    assert_eq!(resp.matches.len(), 2);
+    let first = &resp.matches[0];
+    assert_eq!(first.paper_id(), PaperId::S2("a1b2".into()));
+    assert_eq!(first.authors(), Some("Cohen et al."));
+    assert_eq!(first.year(), Some(2012));
+
+    let none = client.autocomplete("zzz").await.unwrap();
+    assert!(none.matches.is_empty());
+}
+
+#[test]
+fn authors_year_splits_leniently() {
+    let m = |ay: Option<&str>| AutocompleteMatch {
+        id: "x".into(),
+        title: None,
+        authors_year: ay.map(String::from),
+    };
+    assert_eq!(m(Some("de Arellano, 2014")).authors(), Some("de Arellano"));
+    assert_eq!(m(Some("2019")).year(), Some(2019));
+    assert_eq!(m(Some("2019")).authors(), None);
+    assert_eq!(m(Some("Smith, Jones")).year(), None);
+    assert_eq!(m(Some("Smith, Jones")).authors(), Some("Smith, Jones"));
+    assert_eq!(m(Some("")).authors(), None);
+    assert_eq!(m(None).year(), None);
+}
@@ -0,0 +1,106 @@
+/// Tests for `get_papers_batch` against an in-process mock of `POST /paper/batch`.
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use axum::{extract::State, routing::post, Json, Router};
+use common::fields_for;
+use semantic_scholar::{client::BATCH_LIMIT, fields::Endpoint, PaperId, SemanticScholarClient};
+use serde_json::{json, Value};
+
+/// Bodies of every batch request received, in order.
+type Bodies = Arc<Mutex<Vec<Value>>>;
+
+/// Resolves every ID except those starting with `missing`.
+async fn batch_handler(State(bodies): State<Bodies>, Json(body): Json<Value>) -> Json<Value> {
+    bodies.lock().unwrap().push(body.clone());
+    let papers: Vec<Value> = body["ids"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|id| {
+            let id = id.as_str().unwrap();
+            if id.starts_with("missing") {
+                Value::Null
+            } else {
+                json!({ "paperId": id, "title": format!("Title of {id}") })
+            }
+        })
+        .collect();
+    Json(Value::Array(papers))
+}
+
+async fn start_mock() -> (SemanticScholarClient, Bodies) {
+    let bodies = Bodies::default();
+    let app = Router::new()
+        .route("/graph/v1/paper/batch", post(batch_handler))
+        .with_state(bodies.clone());
+
+    let client = common::client(&common::serve(app).await);
+    (client, bodies)
+}
+
+/// Mock-corpus S2 ID; short names keep the assertions readable.
+fn id(s: &str) -> PaperId {
+    PaperId::S2(s.into())
+}
+
+#[tokio::test]
+async fn batch_results_align_with_input() {
+    let (client, bodies) = start_mock().await;
+    let papers = client
+        .get_papers_batch(
+            &[id("a"), id("missing-1"), "DOI:10.1/x".parse().unwrap()],
+            &fields_for(Endpoint::PaperBatch, "title"),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(papers.len(), 3);
+    assert_eq!(papers[0].as_ref().unwrap().paper_id.as_deref(), Some("a"));
+    assert!(papers[1].is_none());
+    assert_eq!(
+        papers[2].as_ref().unwrap().title.as_deref(),
+        Some("Title of DOI:10.1/x")
+    );
+    assert_eq!(
+        bodies.lock().unwrap()[0]["ids"].as_array().unwrap().len(),
+        3
+    );
+}
+
+#[tokio::test]
+async fn batch_above_limit_is_chunked() {
+    let (client, bodies) = start_mock().await;
+    let ids: Vec<String> = (0..BATCH_LIMIT * 2 + 3).map(|i| format!("p{i}")).collect();
+    let refs: Vec<PaperId> = ids.iter().map(|s| id(s)).collect();
+
+    let papers = client
+        .get_papers_batch(&refs, &fields_for(Endpoint::PaperBatch, "title"))
+        .await
+        .unwrap();
+
+    assert_eq!(papers.len(), ids.len());
+    assert_eq!(
+        papers.last().unwrap().as_ref().unwrap().paper_id.as_deref(),
+        Some(ids.last().unwrap().as_str())
+    );
+    let sizes: Vec<usize> = bodies
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|b| b["ids"].as_array().unwrap().len())
+        .collect();
+    assert_eq!(sizes, [BATCH_LIMIT, BATCH_LIMIT, 3]);
+}
+
+#[tokio::test]
+async fn empty_batch_makes_no_requests() {
+    let (client, bodies) = start_mock().await;
+    let papers = client
+        .get_papers_batch(&[], &fields_for(Endpoint::PaperBatch, "title"))
+        .await
+        .unwrap();
+    assert!(papers.is_empty());
+    assert!(bodies.lock().unwrap().is_empty());
+}
@@ -0,0 +1,250 @@
+/// Tests for the on-disk `ResponseCache`: hits, TTL expiry, offline mode and keying.
+mod common;
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use axum::{
+    extract::{Path, State},
+    routing::{get, post},
+    Json, Router,
+};
+use common::{fields_for, id};
+use semantic_scholar::{
+    cache::{CacheCategory, CacheTtls},
+    fields::Endpoint,
+    Error, ResponseCache, SemanticScholarClient,
+};
+use serde_json::{json, Value};
+
+type Hits = Arc<Mutex<u32>>;
+
+async fn paper_handler(State(hits): State<Hits>, Path(id): Path<String>) -> Json<Value> {
+    *hits.lock().unwrap() += 1;
+    Json(json!({ "paperId": id, "title": format!("Title of {id}") }))
+}
+
+async fn batch_handler(State(hits): State<Hits>, Json(body): Json<Value>) -> Json<Value> {
+    *hits.lock().unwrap() += 1;
+    let ids = body["ids"].as_array().unwrap();
+    Json(Value::Array(
+        ids.iter().map(|id| json!({ "paperId": id })).collect(),
+    ))
+}
+
+/// Returns the mock's base URL and its request counter.
+async fn start_mock() -> (String, Hits) {
+    let hits = Hits::default();
+    let app = Router::new()
+        .route("/graph/v1/paper/batch", post(batch_handler))
+        .route("/graph/v1/paper/:id", get(paper_handler))
+        .with_state(hits.clone());
+    (common::serve(app).await, hits)
+}
+
+fn client(base_url: &str, cache: ResponseCache) -> SemanticScholarClient {
+    SemanticScholarClient::builder()
+        .base_url(base_url)
+        .cache(cache)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn categories_follow_paths() {
+    for (path, category) in [
+        ("/graph/v1/paper/search/bulk", CacheCategory::Search),
+        ("/graph/v1/author/search", CacheCategory::Search),
+        ("/graph/v1/paper/autocomplete", CacheCategory::Search),
+        ("/graph/v1/paper/DOI:10.1%2Fx", CacheCategory::Paper),
+        ("/graph/v1/paper/batch", CacheCategory::Paper),
+        (
+            "/graph/v1/paper/abc/citations",
+            CacheCategory::CitationGraph,
+        ),
+        ("/graph/v1/author/42/papers", CacheCategory::CitationGraph),
+        (
+            "/recommendations/v1/papers/forpaper/abc",
+            CacheCategory::Recommendations,
+        ),
+        (
+            "/datasets/v1/release/latest/dataset/papers",
+            CacheCategory::Datasets,
+        ),
+    ] {
+        assert_eq!(CacheCategory::for_path(path), category, "{path}");
+    }
+    assert!(CacheTtls::default().paper > CacheTtls::default().search);
+}
+
+#[tokio::test]
+async fn repeated_request_is_served_from_disk() {
+    let (base, hits) = start_mock().await;
+    let dir = tempfile::tempdir().unwrap();
+    let client = client(&base, ResponseCache::open(dir.path()).unwrap());
+
+    let first = client
+        .get_paper(&id("p1"), &fields_for(Endpoint::PaperDetail, "title"))
+        .await
+        .unwrap();
+    let second = client
+        .get_paper(&id("p1"), &fields_for(Endpoint::PaperDetail, "title"))
+        .await
+        .unwrap();
+
+    assert_eq!(first.title, second.title);
+    assert_eq!(*hits.lock().unwrap(), 1);
+    // A different field selection is a different request.
+    client
+        .get_paper(&id("p1"), &fields_for(Endpoint::PaperDetail, "title,year"))
+        .await
+        .unwrap();
+    assert_eq!(*hits.lock().unwrap(), 2);
+}
+
+#[tokio::test]
+async fn cache_survives_a_new_client_and_base_url() {
+    let dir = tempfile::tempdir().unwrap();
+    let (base, _) = start_mock().await;
+    client(&base, ResponseCache::open(dir.path()).unwrap())
+        .get_paper(&id("p1"), &fields_for(Endpoint::PaperDetail, "title"))
+        .await
+        .unwrap();
+
+    // Keys are relative to the base URL, so a fresh mock on another port still hits.
+    let (other, hits) = start_mock().await;
+    client(&other, ResponseCache::open(dir.path()).unwrap())
+        .get_paper(&id("p1"), &fields_for(Endpoint::PaperDetail, "title"))
+        .await
+        .unwrap();
+    assert_eq!(*hits.lock().unwrap(), 0);
+}
+
+#[tokio::test]
+async fn expired_entries_are_refetched() {
+    let (base, hits) = start_mock().await;
+    let dir = tempfile::tempdir().unwrap();
+    let cache = ResponseCache::open(dir.path())
+        .unwrap()
+        .ttl(CacheCategory::Paper, Duration::ZERO);
+    let client = client(&base, cache);
+
+    client
+        .get_paper(&id("p1"), &fields_for(Endpoint::PaperDetail, "title"))
+        .await
+        .unwrap();
+    client
+        .get_paper(&id("p1"), &fields_for(Endpoint::PaperDetail, "title"))
+        .await
+        .unwrap();
+    assert_eq!(*hits.lock().unwrap(), 2);
+}
+
+#[tokio::test]
+async fn offline_mode_serves_stale_entries_and_fails_on_miss() {
+    let (base, hits) = start_mock().await;
+    let dir = tempfile::tempdir().unwrap();
+    client(&base, ResponseCache::open(dir.path()).unwrap())
+        .get_paper(&id("p1"), &fields_for(Endpoint::PaperDetail, "title"))
+        .await
+        .unwrap();
+
+    let offline = client(
+        &base,
+        ResponseCache::open(dir.path())
+            .unwrap()
+            .ttl(CacheCategory::Paper, Duration::ZERO)
+            .offline(),
+    );
+    let paper = offline
+        .get_paper(&id("p1"), &fields_for(Endpoint::PaperDetail, "title"))
+        .await
+        .unwrap();
+    assert_eq!(paper.title.as_deref(), Some("Title of p1"));
+
+    let err = offline
+        .get_paper(&id("p2"), &fields_for(Endpoint::PaperDetail, "title"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::CacheMiss(_)), "unexpected: {err}");
+    assert_eq!(*hits.lock().unwrap(), 1);
+}
+
+#[tokio::test]
+async fn post_body_is_part_of_the_key() {
+    let (base, hits) = start_mock().await;
+    let dir = tempfile::tempdir().unwrap();
+    let client = client(&base, ResponseCache::open(dir.path()).unwrap());
+
+    client
+        .get_papers_batch(&[id("a")], &fields_for(Endpoint::PaperBatch, "title"))
+        .await
+        .unwrap();
+    client
+        .get_papers_batch(&[id("b")], &fields_for(Endpoint::PaperBatch, "title"))
+        .await
+        .unwrap();
+    client
+        .get_papers_batch(&[id("a")], &fields_for(Endpoint::PaperBatch, "title"))
+        .await
+        .unwrap();
+    assert_eq!(*hits.lock().unwrap(), 2);
+}
+
+#[tokio::test]
+async fn clear_empties_the_cache() {
+    let (base, hits) = start_mock().await;
+    let dir = tempfile::tempdir().unwrap();
+    let cache = ResponseCache::open(dir.path()).unwrap();
+    let client = client(&base, cache.clone());
+
+    client
+        .get_paper(&id("p1"), &fields_for(Endpoint::PaperDetail, "title"))
+        .await
+        .unwrap();
+    cache.clear().unwrap();
+    client
+        .get_paper(&id("p1"), &fields_for(Endpoint::PaperDetail, "title"))
+        .await
+        .unwrap();
+    assert_eq!(*hits.lock().unwrap(), 2);
+}
+
+#[tokio::test]
+async fn concurrent_writes_of_one_key_leave_one_complete_entry() {
+    let (base, _) = start_mock().await;
+    let dir = tempfile::tempdir().unwrap();
+    let writer = client(&base, ResponseCache::open(dir.path()).unwrap());
+
+    // The lookups race the first write, so several tasks store the same entry at once.
+    let same = vec![id("p1"); 16];
+    let results = writer
+        .fetch_many(
+            &same,
+            same.len(),
+            |client, id| async move {
+                client
+                    .get_paper(&id, &fields_for(Endpoint::PaperDetail, "title"))
+                    .await
+            },
+            |_| {},
+        )
+        .await;
+    assert!(results.iter().all(|(_, r)| r.is_ok()));
+
+    let files: Vec<_> = std::fs::read_dir(dir.path())
+        .unwrap()
+        .map(|e| e.unwrap().file_name().into_string().unwrap())
+        .collect();
+    assert_eq!(files.len(), 1, "{files:?}");
+    assert!(files[0].ends_with(".json"), "{files:?}");
+
+    let offline = client(&base, ResponseCache::open(dir.path()).unwrap().offline());
+    let paper = offline
+        .get_paper(&id("p1"), &fields_for(Endpoint::PaperDetail, "title"))
+        .await
+        .unwrap();
+    assert_eq!(paper.title.as_deref(), Some("Title of p1"));
+}
@@ -0,0 +1,183 @@
+/// Tests for `SemanticScholarClientBuilder`.
+///
+/// Each test spins up an in-process axum server on an ephemeral port and points
+/// the client at it via `base_url`. No real network calls are made.
+mod common;
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use common::{fields_for, id};
+use semantic_scholar::{fields::Endpoint, Error, RetryPolicy, SemanticScholarClient};
+use serde_json::json;
+
+// ─── Mock-server infrastructure ──────────────────────────────────────────────
+
+#[derive(Clone, Default)]
+struct MockState {
+    /// Headers of every request received, in order.
+    headers: Arc<Mutex<Vec<HeaderMap>>>,
+    /// Number of leading requests answered with 429 before succeeding.
+    rate_limited: Arc<Mutex<u32>>,
+}
+
+async fn paper_handler(State(state): State<MockState>, headers: HeaderMap) -> Response {
+    state.headers.lock().unwrap().push(headers);
+    let mut remaining = state.rate_limited.lock().unwrap();
+    if *remaining > 0 {
+        *remaining -= 1;
+        return (StatusCode::TOO_MANY_REQUESTS, "slow down").into_response();
+    }
+    Json(json!({ "paperId": "abc123", "title": "A mock paper" })).into_response()
+}
+
+async fn start_mock(rate_limited: u32) -> (String, MockState) {
+    let state = MockState {
+        rate_limited: Arc::new(Mutex::new(rate_limited)),
+        ..MockState::default()
+    };
+    let app = Router::new()
+        .route("/graph/v1/paper/:id", get(paper_handler))
+        .with_state(state.clone());
+
+    (common::serve(app).await, state)
+}
+
+fn fast_retries(max_retries: u32) -> RetryPolicy {
+    RetryPolicy {
+        max_retries,
+        initial_backoff: Duration::from_millis(1),
+        max_backoff: Duration::from_millis(5),
+        multiplier: 2,
+        jitter: 0.0,
+        retry_transient: true,
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[test]
+fn default_base_url_is_public_api() {
+    let client = SemanticScholarClient::builder().build().unwrap();
+    assert_eq!(client.base_url(), "https://api.semanticscholar.org");
+}
+
+#[test]
+fn base_url_trailing_slash_is_trimmed() {
+    let client = SemanticScholarClient::builder()
+        .base_url("http://localhost:1234/")
+        .build()
+        .unwrap();
+    assert_eq!(client.base_url(), "http://localhost:1234");
+}
+
+#[test]
+fn invalid_api_key_is_a_config_error() {
+    let err = SemanticScholarClient::builder()
+        .api_key("bad\nkey")
+        .build()
+        .err()
+        .expect("expected build to fail");
+    assert!(matches!(err, Error::Config(_)), "unexpected error: {err}");
+}
+
+#[test]
+fn new_ignores_invalid_api_key() {
+    // Must not panic.
+    let _ = SemanticScholarClient::new(Some("bad\nkey"));
+}
+
+#[test]
+fn default_retry_backoff_doubles_from_one_second() {
+    let policy = RetryPolicy::default();
+    assert_eq!(policy.backoff(0), Duration::from_secs(1));
+    assert_eq!(policy.backoff(1), Duration::from_secs(2));
+    assert_eq!(policy.backoff(2), Duration::from_secs(4));
+}
+
+#[test]
+fn backoff_is_capped_at_max() {
+    let policy = RetryPolicy {
+        max_backoff: Duration::from_secs(3),
+        ..RetryPolicy::default()
+    };
+    assert_eq!(policy.backoff(10), Duration::from_secs(3));
+}
+
+#[tokio::test]
+async fn requests_go_to_configured_base_url() {
+    let (base_url, state) = start_mock(0).await;
+    let client = SemanticScholarClient::builder()
+        .base_url(&base_url)
+        .build()
+        .unwrap();
+
+    let paper = client
+        .get_paper(&id("abc123"), &fields_for(Endpoint::PaperDetail, "title"))
+        .await
+        .unwrap();
+    assert_eq!(paper.title.as_deref(), Some("A mock paper"));
+    assert_eq!(state.headers.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn api_key_and_user_agent_headers_sent() {
+    let (base_url, state) = start_mock(0).await;
+    SemanticScholarClient::builder()
+        .base_url(&base_url)
+        .api_key("s2-secret")
+        .user_agent("therapy-research/1.0")
+        .build()
+        .unwrap()
+        .get_paper(&id("abc123"), &fields_for(Endpoint::PaperDetail, "title"))
+        .await
+        .unwrap();
+
+    let headers = state.headers.lock().unwrap();
+    assert_eq!(headers[0]["x-api-key"], "s2-secret");
+    assert_eq!(headers[0]["user-agent"], "therapy-research/1.0");
+}
+
+#[tokio::test]
+async fn rate_limited_request_is_retried() {
+    let (base_url, state) = start_mock(2).await;
+    let client = SemanticScholarClient::builder()
+        .base_url(&base_url)
+        .retry_policy(fast_retries(3))
+        .build()
+        .unwrap();
+
+    client
+        .get_paper(&id("abc123"), &fields_for(Endpoint::PaperDetail, "title"))
+        .await
+        .unwrap();
+    assert_eq!(state.headers.lock().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn max_retries_zero_fails_on_first_429() {
+    let (base_url, state) = start_mock(1).await;
+    let err = SemanticScholarClient::builder()
+        .base_url(&base_url)
+        .retry_policy(fast_retries(0))
+        .build()
+        .unwrap()
+        .get_paper(&id("abc123"), &fields_for(Endpoint::PaperDetail, "title"))
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(err, Error::RateLimited { .. }),
+        "unexpected: {err}"
+    );
+    assert_eq!(state.headers.lock().unwrap().len(), 1);
+}
@@ -0,0 +1,43 @@
+//! Helpers shared by the integration tests that drive the client against an
+//! in-process axum router.
+#![allow(dead_code)]
+
+use axum::Router;
+use semantic_scholar::{
+    fields::{Endpoint, FieldSet},
+    PaperId, SemanticScholarClient,
+};
+use tokio::net::TcpListener;
+
+/// Serve `app` on an ephemeral localhost port and return its base URL.
+pub async fn serve(app: Router) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    format!("http://{addr}")
+}
+
+/// A default client pointed at `base_url`.
+pub fn client(base_url: &str) -> SemanticScholarClient {
+    SemanticScholarClient::builder()
+        .base_url(base_url)
+        .build()
+        .unwrap()
+}
+
+/// Mock IDs such as `p1` aren't S2-shaped, so fall back to wrapping them directly.
+pub fn id(s: &str) -> PaperId {
+    s.parse().unwrap_or_else(|_| PaperId::S2(s.into()))
+}
+
+/// [`id`] for each of `raw`.
+pub fn ids(raw: &[&str]) -> Vec<PaperId> {
+    raw.iter().map(|s| id(s)).collect()
+}
+
+/// `fields=` selection for `endpoint`, e.g. `fields_for(Endpoint::PaperDetail, "title")`.
+pub fn fields_for(endpoint: Endpoint, fields: &str) -> FieldSet {
+    FieldSet::parse(endpoint, fields).unwrap()
+}
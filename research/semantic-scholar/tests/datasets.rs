@@ -0,0 +1,234 @@
+/// Tests for the Datasets API endpoints and the shard readers.
+mod common;
+
+use std::io::Write;
+
+use axum::{extract::Path, routing::get, Json, Router};
+use flate2::{write::GzEncoder, Compression};
+use semantic_scholar::{
+    datasets::{
+        open_shard, read_papers, AbstractRecord, PaperRecord, ShardRecord, TldrRecord,
+        LATEST_RELEASE,
+    },
+    Error, SemanticScholarClient,
+};
+use serde_json::{json, Value};
+
+async fn start_mock() -> SemanticScholarClient {
+    let app = Router::new()
+        .route(
+            "/datasets/v1/release/",
+            get(|| async { Json(json!(["2024-11-05", "2024-11-12"])) }),
+        )
+        .route(
+            "/datasets/v1/release/:release",
+            get(|Path(release): Path<String>| async move {
+                Json(json!({
+                    "release_id": if release == "latest" { "2024-11-12".into() } else { release },
+                    "README": "Subject to the license ...",
+                    "datasets": [
+                        { "name": "papers", "description": "Core paper metadata", "README": "..." },
+                        { "name": "abstracts", "description": "Paper abstracts", "README": "..." }
+                    ]
+                }))
+            }),
+        )
+        .route(
+            "/datasets/v1/release/:release/dataset/:name",
+            get(|Path((release, name)): Path<(String, String)>| async move {
+                Json(json!({
+                    "name": name,
+                    "description": "Core paper metadata",
+                    "README": "...",
+                    "files": [format!("https://s3.example/{release}/{name}/0.gz?sig=abc")]
+                }))
+            }),
+        )
+        .route(
+            "/datasets/v1/diffs/:start/to/:end/:name",
+            get(
+                |Path((start, end, name)): Path<(String, String, String)>| async move {
+                    Json(json!({
+                        "dataset": name,
+                        "start_release": start,
+                        "end_release": end,
+                        "diffs": [{
+                            "from_release": start,
+                            "to_release": end,
+                            "update_files": ["https://s3.example/upd/0.gz"],
+                            "delete_files": []
+                        }]
+                    }))
+                },
+            ),
+        );
+    common::client(&common::serve(app).await)
+}
+
+#[tokio::test]
+async fn releases_datasets_and_diffs() {
+    let client = start_mock().await;
+
+    let releases = client.list_releases().await.unwrap();
+    assert_eq!(releases, ["2024-11-05", "2024-11-12"]);
+
+    let release = client.get_release(LATEST_RELEASE).await.unwrap();
+    assert_eq!(release.release_id, "2024-11-12");
+    let names: Vec<_> = release.datasets.iter().map(|d| d.name.as_str()).collect();
+    assert_eq!(names, ["papers", "abstracts"]);
+
+    let dataset = client.get_dataset("2024-11-12", "papers").await.unwrap();
+    assert_eq!(dataset.files.len(), 1);
+    assert!(dataset.files[0].contains("/2024-11-12/papers/"));
+
+    let diffs = client
+        .get_dataset_diffs("2024-11-05", LATEST_RELEASE, "papers")
+        .await
+        .unwrap();
+    assert_eq!(diffs.end_release, "latest");
+    assert_eq!(diffs.diffs[0].update_files.len(), 1);
+    assert!(diffs.diffs[0].delete_files.is_empty());
+}
+
+fn paper_line(corpus_id: u64) -> Value {
+    json!({
+        "corpusid": corpus_id,
+        "externalids": { "DOI": "10.1016/j.jaac.2011.12.001", "PubMed": "22265362", "CorpusId": corpus_id.to_string(), "MAG": null },
+        "url": format!("https://www.semanticscholar.org/paper/{corpus_id}"),
+        "title": "Trauma-focused CBT for children",
+        "authors": [{ "authorId": "1741101", "name": "Judith A. Cohen" }],
+        "venue": "JAACAP",
+        "publicationvenueid": null,
+        "year": 2012,
+        "referencecount": 40,
+        "citationcount": 300,
+        "influentialcitationcount": 25,
+        "isopenaccess": false,
+        "s2fieldsofstudy": [
+            { "category": "Psychology", "source": "external" },
+            { "category": "Medicine", "source": "external" },
+            { "category": "Psychology", "source": "s2-fos-model" }
+        ],
+        "publicationtypes": ["JournalArticle", "ClinicalTrial"],
+        "publicationdate": "2012-03-01",
+        "journal": { "name": "Journal of the American Academy of Child & Adolescent Psychiatry", "volume": "51", "pages": "" }
+    })
+}
+
+fn write_lines(path: &std::path::Path, lines: &[Value], gzip: bool) {
+    let mut text = String::new();
+    for line in lines {
+        text.push_str(&line.to_string());
+        text.push('\n');
+    }
+    if gzip {
+        let mut enc = GzEncoder::new(std::fs::File::create(path).unwrap(), Compression::fast());
+        enc.write_all(text.as_bytes()).unwrap();
+        enc.finish().unwrap();
+    } else {
+        std::fs::write(path, text).unwrap();
+    }
+}
+
+#[test]
+fn papers_shard_reads_into_paper_gzipped_or_plain() {
+    let dir = tempfile::tempdir().unwrap();
+    for (name, gzip) in [("papers-0", true), ("papers-1.jsonl", false)] {
+        let path = dir.path().join(name);
+        write_lines(&path, &[paper_line(1), paper_line(2)], gzip);
+
+        let papers: Vec<_> = read_papers::<PaperRecord>(&path)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(papers.len(), 2, "{name}");
+        let p = &papers[0];
+        let ext = p.external_ids.as_ref().unwrap();
+        assert_eq!(ext.corpus_id, Some(1));
+        assert_eq!(p.doi(), Some("10.1016/j.jaac.2011.12.001"));
+        assert_eq!(ext.mag, None);
+        assert_eq!(p.citation_count, Some(300));
+        assert_eq!(
+            p.authors.as_ref().unwrap()[0].name.as_deref(),
+            Some("Judith A. Cohen")
+        );
+        assert_eq!(
+            p.fields_of_study.as_deref(),
+            Some(&["Psychology".to_string(), "Medicine".to_string()][..])
+        );
+        assert!(p.has_publication_type("ClinicalTrial"));
+        assert_eq!(p.s2_fields_of_study.as_ref().unwrap().len(), 3);
+    }
+}
+
+#[test]
+fn abstracts_and_tldrs_merge_onto_papers() {
+    let dir = tempfile::tempdir().unwrap();
+    let abstracts = dir.path().join("abstracts");
+    let tldrs = dir.path().join("tldrs");
+    write_lines(
+        &abstracts,
+        &[
+            json!({ "corpusid": 1, "abstract": "Children with PTSD ...", "openaccessinfo": { "status": null } }),
+        ],
+        true,
+    );
+    write_lines(
+        &tldrs,
+        &[
+            json!({ "corpusid": 1, "model": "tldr@v2.0.0", "text": "TF-CBT beats child-centred therapy." }),
+        ],
+        true,
+    );
+
+    let mut paper = PaperRecord::into_paper(serde_json::from_value(paper_line(1)).unwrap());
+    for record in open_shard::<AbstractRecord>(&abstracts).unwrap() {
+        let record = record.unwrap();
+        assert_eq!(record.corpus_id(), 1);
+        record.merge_into(&mut paper);
+    }
+    for record in open_shard::<TldrRecord>(&tldrs).unwrap() {
+        record.unwrap().merge_into(&mut paper);
+    }
+    assert_eq!(
+        paper.abstract_text.as_deref(),
+        Some("Children with PTSD ...")
+    );
+    let tldr = paper.tldr.as_ref().unwrap();
+    assert_eq!(tldr.model.as_deref(), Some("tldr@v2.0.0"));
+    assert_eq!(
+        paper.title.as_deref(),
+        Some("Trauma-focused CBT for children")
+    );
+
+    let tldr_only = read_papers::<TldrRecord>(&tldrs)
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap();
+    assert_eq!(tldr_only.external_ids.unwrap().corpus_id, Some(1));
+    assert!(tldr_only.title.is_none());
+}
+
+#[test]
+fn malformed_line_reports_file_and_line() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("tldrs.jsonl");
+    std::fs::write(
+        &path,
+        "{\"corpusid\": 1, \"text\": \"ok\"}\n\n{\"corpusid\": \"not a number\"}\n",
+    )
+    .unwrap();
+
+    let records: Vec<_> = open_shard::<TldrRecord>(&path).unwrap().collect();
+    assert_eq!(records.len(), 2, "blank lines are skipped");
+    assert!(records[0].is_ok());
+    let err = records[1].as_ref().unwrap_err();
+    assert!(matches!(err, Error::Dataset(_)), "unexpected: {err}");
+    assert!(err.to_string().contains("tldrs.jsonl:3:"), "{err}");
+
+    let missing = open_shard::<TldrRecord>(dir.path().join("nope"))
+        .err()
+        .unwrap();
+    assert!(matches!(missing, Error::Dataset(_)));
+}
@@ -0,0 +1,141 @@
+/// Tests for error classification and request context.
+mod common;
+
+use axum::{
+    extract::Path,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use common::fields_for;
+use semantic_scholar::{fields::Endpoint, Error, PaperId, RetryPolicy, SemanticScholarClient};
+use tokio::net::TcpListener;
+
+/// `/graph/v1/paper/{status}` answers with that status and an S2-style error body, or
+/// a malformed paper for 200.
+async fn status_handler(Path(code): Path<u16>) -> Response {
+    let status = StatusCode::from_u16(code).unwrap();
+    let body = match code {
+        404 => r#"{"error":"Paper with id 404 not found"}"#.to_string(),
+        400 => r#"{"error":"Unrecognized or unsupported fields: [bogus]"}"#.to_string(),
+        403 => r#"{"message":"Forbidden"}"#.to_string(),
+        418 => String::new(),
+        200 => r#"{"paperId":"200","title":["not","a","string"]}"#.to_string(),
+        _ => "<html>upstream exploded</html>".to_string(),
+    };
+    (status, [("x-amzn-requestid", "req-123")], body).into_response()
+}
+
+async fn client() -> SemanticScholarClient {
+    let app = Router::new().route("/graph/v1/paper/:code", get(status_handler));
+    let base = common::serve(app).await;
+    SemanticScholarClient::builder()
+        .base_url(&base)
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap()
+}
+
+async fn fail(client: &SemanticScholarClient, code: u16) -> Error {
+    let id = PaperId::S2(code.to_string());
+    client
+        .get_paper(&id, &fields_for(Endpoint::PaperDetail, "title"))
+        .await
+        .unwrap_err()
+}
+
+#[tokio::test]
+async fn statuses_map_to_variants_with_parsed_messages() {
+    let client = client().await;
+
+    let err = fail(&client, 404).await;
+    assert!(matches!(err, Error::NotFound { .. }), "unexpected: {err}");
+    assert_eq!(err.api_message(), Some("Paper with id 404 not found"));
+
+    let err = fail(&client, 400).await;
+    assert!(matches!(err, Error::BadRequest { .. }), "unexpected: {err}");
+    assert!(err.api_message().unwrap().contains("[bogus]"));
+
+    let err = fail(&client, 403).await;
+    assert!(
+        matches!(err, Error::Unauthorized { status: 403, .. }),
+        "unexpected: {err}"
+    );
+    assert_eq!(err.api_message(), Some("Forbidden"));
+
+    let err = fail(&client, 503).await;
+    assert!(
+        matches!(err, Error::ServerError { status: 503, .. }),
+        "unexpected: {err}"
+    );
+    assert_eq!(err.api_message(), Some("<html>upstream exploded</html>"));
+
+    let err = fail(&client, 418).await;
+    assert!(
+        matches!(err, Error::Api { status: 418, .. }),
+        "unexpected: {err}"
+    );
+    assert_eq!(err.api_message(), Some("I'm a teapot"));
+}
+
+#[tokio::test]
+async fn errors_carry_method_path_and_request_id() {
+    let client = client().await;
+    let err = fail(&client, 404).await;
+
+    let ctx = err.context().unwrap();
+    assert_eq!(ctx.method, "GET");
+    assert_eq!(ctx.path, "/graph/v1/paper/404");
+    assert_eq!(ctx.request_id.as_deref(), Some("req-123"));
+    assert_eq!(err.status(), Some(404));
+    assert_eq!(
+        err.to_string(),
+        "GET /graph/v1/paper/404 (request id req-123): not found: Paper with id 404 not found"
+    );
+}
+
+#[tokio::test]
+async fn only_transient_failures_are_retryable() {
+    let client = client().await;
+    for (code, retryable) in [(400, false), (403, false), (404, false), (503, true)] {
+        assert_eq!(
+            fail(&client, code).await.is_retryable(),
+            retryable,
+            "{code}"
+        );
+    }
+
+    let addr = TcpListener::bind("127.0.0.1:0")
+        .await
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let closed = SemanticScholarClient::builder()
+        .base_url(&format!("http://{addr}"))
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap();
+    let err = fail(&closed, 404).await;
+    assert!(matches!(err, Error::Http { .. }), "unexpected: {err}");
+    assert!(err.is_retryable());
+    assert_eq!(err.context().unwrap().request_id, None);
+    assert!(!Error::InvalidPaperId("x".into()).is_retryable());
+}
+
+#[tokio::test]
+async fn malformed_success_body_carries_request_context() {
+    let client = client().await;
+
+    let err = fail(&client, 200).await;
+    assert!(matches!(err, Error::Json { .. }), "unexpected: {err}");
+    let context = err.context().unwrap();
+    assert_eq!(context.method, "GET");
+    assert_eq!(context.path, "/graph/v1/paper/200");
+    assert_eq!(context.request_id.as_deref(), Some("req-123"));
+    assert!(
+        err.to_string().starts_with("GET /graph/v1/paper/200"),
+        "{err}"
+    );
+    assert!(!err.is_retryable());
+}
@@ -0,0 +1,145 @@
+/// Tests for BibTeX, RIS, CSL-JSON, CSV and Markdown export.
+use semantic_scholar::{
+    export::{to_bibtex, to_csl_json, to_ris, Table},
+    Error, ExportFormat, Paper,
+};
+use serde_json::json;
+
+fn meta_analysis() -> Paper {
+    serde_json::from_value(json!({
+        "paperId": "0f3b1c",
+        "title": "Trauma-focused CBT for children: 50% fewer symptoms & {more}",
+        "abstract": "We pooled 24 trials.\nEffects held at follow-up.",
+        "year": 2019,
+        "publicationDate": "2019-08-21",
+        "citationCount": 212,
+        "url": "https://www.semanticscholar.org/paper/0f3b1c",
+        "authors": [
+            { "authorId": "1", "name": "Judith A. Cohen" },
+            { "authorId": "2", "name": "Ludwig van Beethoven" },
+            { "authorId": "3", "name": "Madonna" }
+        ],
+        "externalIds": { "DOI": "10.1016/j.cpr.2019.101752", "PubMed": "31446278", "PubMedCentral": "6789012" },
+        "publicationTypes": ["Review", "MetaAnalysis", "JournalArticle"],
+        "journal": { "name": "Clinical Psychology Review", "volume": "73", "pages": "101752-101760" },
+        "fieldsOfStudy": ["Psychology", "Medicine"]
+    }))
+    .unwrap()
+}
+
+fn bare(id: &str, title: &str) -> Paper {
+    Paper {
+        paper_id: Some(id.into()),
+        title: Some(title.into()),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn formats_parse_leniently() {
+    for format in ExportFormat::ALL {
+        assert_eq!(format.as_str().parse::<ExportFormat>().unwrap(), format);
+    }
+    assert_eq!("BIB".parse::<ExportFormat>().unwrap(), ExportFormat::Bibtex);
+    assert_eq!(
+        "md".parse::<ExportFormat>().unwrap(),
+        ExportFormat::Markdown
+    );
+    let err = "docx".parse::<ExportFormat>().unwrap_err();
+    assert!(matches!(err, Error::InvalidFormat(_)), "{err}");
+}
+
+#[test]
+fn bibtex_entries_escape_text_and_dedupe_keys() {
+    let papers = [meta_analysis(), meta_analysis(), bare("ab12", "Untitled")];
+    let bib = to_bibtex(&papers);
+
+    assert!(bib.starts_with("@article{cohen2019trauma,\n"));
+    assert!(bib.contains("@article{cohen2019traumaa,\n"));
+    assert!(bib.contains("@misc{untitled,\n"));
+    assert!(bib.contains(
+        "  title = {Trauma-focused CBT for children: 50\\% fewer symptoms \\& \\{more\\}},\n"
+    ));
+    assert!(bib.contains("  author = {Cohen, Judith A. and van Beethoven, Ludwig and Madonna},\n"));
+    assert!(bib.contains("  journal = {Clinical Psychology Review},\n"));
+    assert!(bib.contains("  pages = {101752--101760},\n"));
+    assert!(bib.contains("  doi = {10.1016/j.cpr.2019.101752},\n"));
+    assert!(bib.contains("  abstract = {We pooled 24 trials. Effects held at follow-up.},\n"));
+    assert_eq!(bib.matches("\n}\n").count(), 3);
+}
+
+#[test]
+fn ris_records_use_tagged_lines() {
+    let ris = to_ris(&[meta_analysis(), bare("ab12", "Untitled")]);
+    let lines: Vec<&str> = ris.split("\r\n").collect();
+
+    assert_eq!(lines[0], "TY  - JOUR");
+    assert!(lines.contains(&"AU  - Cohen, Judith A."));
+    assert!(lines.contains(&"AU  - Madonna"));
+    assert!(lines.contains(&"PY  - 2019"));
+    assert!(lines.contains(&"DA  - 2019/08/21/"));
+    assert!(lines.contains(&"T2  - Clinical Psychology Review"));
+    assert!(lines.contains(&"SP  - 101752"));
+    assert!(lines.contains(&"EP  - 101760"));
+    assert!(lines.contains(&"AN  - PMID:31446278"));
+    assert!(lines.contains(&"AB  - We pooled 24 trials. Effects held at follow-up."));
+    assert!(lines.contains(&"TY  - GEN"));
+    assert_eq!(lines.iter().filter(|l| **l == "ER  - ").count(), 2);
+}
+
+#[test]
+fn csl_json_items_carry_structured_names_and_dates() {
+    let csl = to_csl_json(&[meta_analysis(), Paper::default()]);
+    let item = &csl[0];
+
+    assert_eq!(item["id"], "0f3b1c");
+    assert_eq!(item["type"], "article-journal");
+    assert_eq!(
+        item["author"][1],
+        json!({ "family": "van Beethoven", "given": "Ludwig" })
+    );
+    assert_eq!(item["author"][2], json!({ "literal": "Madonna" }));
+    assert_eq!(item["issued"], json!({ "date-parts": [[2019, 8, 21]] }));
+    assert_eq!(item["container-title"], "Clinical Psychology Review");
+    assert_eq!(item["DOI"], "10.1016/j.cpr.2019.101752");
+    assert_eq!(item["PMCID"], "PMC6789012");
+    // Unknown metadata is omitted rather than written as null.
+    assert_eq!(csl[1], json!({ "id": "item-2", "type": "article" }));
+}
+
+#[test]
+fn tables_quote_and_escape_cells() {
+    let papers = [meta_analysis(), bare("ab12", "Pipes | and \"quotes\"")];
+    let csv = ExportFormat::Csv.render(&papers);
+    let rows: Vec<&str> = csv.split("\r\n").collect();
+
+    assert_eq!(
+        rows[0],
+        "paper_id,title,authors,year,venue,citations,doi,url"
+    );
+    assert!(rows[1].starts_with(
+        "0f3b1c,Trauma-focused CBT for children: 50% fewer symptoms & {more},Judith A. Cohen; Ludwig van Beethoven; Madonna,2019,"
+    ));
+    assert_eq!(rows[2], "ab12,\"Pipes | and \"\"quotes\"\"\",,,,,,");
+
+    let md = ExportFormat::Markdown.render(&papers);
+    let lines: Vec<&str> = md.lines().collect();
+    assert_eq!(
+        lines[1],
+        "| --- | --- | --- | --- | --- | --- | --- | --- |"
+    );
+    assert_eq!(
+        lines[3],
+        "| ab12 | Pipes \\| and \"quotes\" |  |  |  |  |  |  |"
+    );
+
+    let short = Table::new(["a", "b"]).row(["only one"]).to_csv();
+    assert_eq!(short, "a,b\r\nonly one,\r\n");
+}
+
+#[test]
+fn empty_result_set_exports_an_empty_file() {
+    assert_eq!(ExportFormat::Bibtex.render(&[]), "");
+    assert_eq!(ExportFormat::Ris.render(&[]), "");
+    assert_eq!(ExportFormat::CslJson.render(&[]), "[]\n");
+}
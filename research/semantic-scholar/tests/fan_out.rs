@@ -0,0 +1,155 @@
+/// Tests for `fetch_many` against an in-process axum mock that tracks concurrency.
+mod common;
+
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use common::{fields_for, ids};
+use semantic_scholar::{fields::Endpoint, Error, SemanticScholarClient};
+use serde_json::json;
+
+#[derive(Default)]
+struct Load {
+    in_flight: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+async fn paper_handler(State(load): State<Arc<Load>>, Path(id): Path<String>) -> Response {
+    let now = load.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+    load.peak.fetch_max(now, Ordering::SeqCst);
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    load.in_flight.fetch_sub(1, Ordering::SeqCst);
+    if id == "missing" {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": "Paper with id missing not found" })),
+        )
+            .into_response();
+    }
+    Json(json!({ "paperId": id, "title": format!("Paper {id}") })).into_response()
+}
+
+async fn start_mock() -> (String, Arc<Load>) {
+    let load = Arc::new(Load::default());
+    let app = Router::new()
+        .route("/graph/v1/paper/:id", get(paper_handler))
+        .with_state(load.clone());
+    (common::serve(app).await, load)
+}
+
+#[tokio::test]
+async fn results_keep_input_order_with_bounded_concurrency() {
+    let (base, load) = start_mock().await;
+    let client = common::client(&base);
+    let ids = ids(&["p0", "p1", "missing", "p3", "p4", "p5", "p6", "p7"]);
+
+    let mut progress = Vec::new();
+    let results = client
+        .fetch_many(
+            &ids,
+            3,
+            |client, id| async move {
+                client
+                    .get_paper(&id, &fields_for(Endpoint::PaperDetail, "title"))
+                    .await
+            },
+            |p| progress.push((p.completed, p.failed, p.total, p.ok)),
+        )
+        .await;
+
+    assert_eq!(load.peak.load(Ordering::SeqCst), 3);
+    assert_eq!(results.len(), ids.len());
+    for (i, (id, result)) in results.iter().enumerate() {
+        assert_eq!(id, &ids[i]);
+        match result {
+            Ok(paper) => assert_eq!(paper.paper_id.as_deref(), Some(id.to_string().as_str())),
+            Err(e) => {
+                assert_eq!(id.to_string(), "missing");
+                assert!(matches!(e, Error::NotFound { .. }), "{e}");
+            }
+        }
+    }
+
+    let completed: Vec<_> = progress.iter().map(|p| p.0).collect();
+    assert_eq!(completed, (1..=8).collect::<Vec<_>>());
+    assert!(progress.iter().all(|p| p.2 == 8));
+    assert_eq!(progress.iter().filter(|p| !p.3).count(), 1);
+    assert_eq!(progress.last().unwrap().1, 1);
+}
+
+#[tokio::test]
+async fn fan_out_goes_through_the_rate_limiter() {
+    let (base, load) = start_mock().await;
+    let client = SemanticScholarClient::builder()
+        .base_url(&base)
+        .rate_limit(20.0, 1)
+        .build()
+        .unwrap();
+    let ids = ids(&["a", "b", "c", "d", "e", "f"]);
+
+    let started = Instant::now();
+    let results = client
+        .fetch_many(
+            &ids,
+            ids.len(),
+            |client, id| async move {
+                client
+                    .get_paper(&id, &fields_for(Endpoint::PaperDetail, "title"))
+                    .await
+            },
+            |_| {},
+        )
+        .await;
+
+    assert!(results.iter().all(|(_, r)| r.is_ok()));
+    // One token every 50ms after the first: five waits at least.
+    assert!(started.elapsed() >= Duration::from_millis(240));
+    assert!(load.peak.load(Ordering::SeqCst) < ids.len());
+}
+
+#[tokio::test]
+async fn empty_input_and_zero_concurrency() {
+    let (base, _) = start_mock().await;
+    let client = common::client(&base);
+
+    let none = client
+        .fetch_many(
+            &[],
+            4,
+            |client, id| async move {
+                client
+                    .get_paper(&id, &fields_for(Endpoint::PaperDetail, "title"))
+                    .await
+            },
+            |_| panic!("no progress without work"),
+        )
+        .await;
+    assert!(none.is_empty());
+
+    // Zero is treated as one rather than stalling forever.
+    let one = client
+        .fetch_many(
+            &ids(&["p1"]),
+            0,
+            |client, id| async move {
+                client
+                    .get_paper(&id, &fields_for(Endpoint::PaperDetail, "title"))
+                    .await
+            },
+            |_| {},
+        )
+        .await;
+    assert!(one[0].1.is_ok());
+}
@@ -0,0 +1,208 @@
+/// Tests for `PaperField` / `FieldSet` endpoint validation.
+use semantic_scholar::{
+    fields::{AuthorField, AuthorFieldSet, EdgeField, Endpoint, FieldSet, PaperField},
+    types::{AUTHOR_FIELDS, PAPER_FIELDS_BRIEF, PAPER_FIELDS_FULL, SEARCH_FIELDS},
+    Error,
+};
+
+#[test]
+fn every_field_round_trips_through_its_api_name() {
+    for field in PaperField::ALL {
+        assert_eq!(field.as_str().parse::<PaperField>().unwrap(), field);
+    }
+}
+
+#[test]
+fn unknown_field_name_is_rejected() {
+    let err = "tldrr".parse::<PaperField>().unwrap_err();
+    assert!(matches!(err, Error::InvalidFields(_)), "unexpected: {err}");
+}
+
+#[test]
+fn presets_match_string_constants() {
+    assert_eq!(
+        FieldSet::search(Endpoint::BulkSearch).unwrap().as_str(),
+        SEARCH_FIELDS
+    );
+    assert_eq!(
+        FieldSet::paper_full(Endpoint::PaperDetail)
+            .unwrap()
+            .as_str(),
+        PAPER_FIELDS_FULL
+    );
+    assert_eq!(
+        FieldSet::paper_brief(Endpoint::Citations).unwrap().as_str(),
+        PAPER_FIELDS_BRIEF
+    );
+}
+
+#[test]
+fn tldr_rejected_by_search_and_nested_endpoints() {
+    for endpoint in [
+        Endpoint::Search,
+        Endpoint::BulkSearch,
+        Endpoint::Citations,
+        Endpoint::References,
+        Endpoint::Recommendations,
+        Endpoint::AuthorPapers,
+    ] {
+        let err = FieldSet::new(endpoint, [PaperField::Title, PaperField::Tldr]).unwrap_err();
+        assert!(
+            err.to_string().contains("tldr"),
+            "{endpoint}: unexpected error {err}"
+        );
+    }
+}
+
+#[test]
+fn tldr_allowed_in_paper_detail_and_batch() {
+    assert!(FieldSet::new(Endpoint::PaperDetail, [PaperField::Tldr]).is_ok());
+    assert!(FieldSet::new(Endpoint::PaperBatch, [PaperField::Tldr]).is_ok());
+}
+
+#[test]
+fn influential_count_rejected_by_search_only() {
+    assert!(FieldSet::new(Endpoint::BulkSearch, [PaperField::InfluentialCitationCount]).is_err());
+    assert!(FieldSet::new(Endpoint::Citations, [PaperField::InfluentialCitationCount]).is_ok());
+}
+
+#[test]
+fn builder_joins_in_order_and_drops_duplicates() {
+    let fs = FieldSet::builder(Endpoint::BulkSearch)
+        .field(PaperField::Title)
+        .fields([PaperField::Year, PaperField::Title, PaperField::Authors])
+        .build()
+        .unwrap();
+    assert_eq!(fs.as_str(), "title,year,authors");
+    assert_eq!(fs.fields().len(), 3);
+    assert!(fs.contains(PaperField::Year));
+    assert_eq!(fs.endpoint(), Endpoint::BulkSearch);
+}
+
+#[test]
+fn empty_selection_is_rejected() {
+    assert!(FieldSet::builder(Endpoint::PaperDetail).build().is_err());
+    assert!(FieldSet::parse(Endpoint::PaperDetail, " , ").is_err());
+}
+
+#[test]
+fn parse_trims_whitespace() {
+    let fs = FieldSet::parse(Endpoint::PaperDetail, "title, year ,tldr").unwrap();
+    assert_eq!(fs.to_string(), "title,year,tldr");
+}
+
+#[test]
+fn dotted_subfields_parse_for_nested_fields() {
+    let fs = FieldSet::parse(
+        Endpoint::PaperDetail,
+        "title,authors.affiliations,citations.title,embedding.specter_v2",
+    )
+    .unwrap();
+    assert_eq!(
+        fs.as_str(),
+        "title,authors.affiliations,citations.title,embedding.specter_v2"
+    );
+    assert_eq!(
+        fs.fields(),
+        [
+            PaperField::Title,
+            PaperField::Authors,
+            PaperField::Citations,
+            PaperField::Embedding
+        ]
+    );
+
+    let built = FieldSet::builder(Endpoint::BulkSearch)
+        .subfield(PaperField::Authors, "name")
+        .build()
+        .unwrap();
+    assert_eq!(built.as_str(), "authors.name");
+}
+
+#[test]
+fn dotted_subfields_follow_endpoint_and_field_rules() {
+    // Nested citation lists are still unavailable from search.
+    assert!(FieldSet::parse(Endpoint::BulkSearch, "citations.title").is_err());
+    for bad in ["title.text", "authors.", "nope.name", "authors.a-b"] {
+        let err = FieldSet::parse(Endpoint::PaperDetail, bad).unwrap_err();
+        assert!(matches!(err, Error::InvalidFields(_)), "{bad}: {err}");
+    }
+}
+
+#[test]
+fn edge_fields_only_for_citations_and_references() {
+    let fs = FieldSet::parse(Endpoint::References, "title")
+        .unwrap()
+        .with_edge_fields()
+        .unwrap();
+    assert_eq!(fs.as_str(), "contexts,intents,isInfluential,title");
+    assert_eq!(fs.edge_fields(), EdgeField::ALL);
+    assert_eq!(fs.fields(), [PaperField::Title]);
+
+    assert!(FieldSet::parse(Endpoint::PaperDetail, "intents").is_err());
+    assert!(FieldSet::builder(Endpoint::Citations)
+        .edge_field(EdgeField::IsInfluential)
+        .build()
+        .is_ok());
+}
+
+#[test]
+fn for_endpoint_revalidates() {
+    let detail = FieldSet::parse(Endpoint::PaperDetail, "title,tldr").unwrap();
+    assert!(detail.for_endpoint(Endpoint::Citations).is_err());
+    let brief = FieldSet::parse(Endpoint::PaperDetail, "title,year").unwrap();
+    assert_eq!(
+        brief.for_endpoint(Endpoint::Citations).unwrap().endpoint(),
+        Endpoint::Citations
+    );
+}
+
+#[test]
+fn author_fields_validate_names_and_nested_papers() {
+    assert_eq!(AuthorFieldSet::default().as_str(), AUTHOR_FIELDS);
+    for field in AuthorField::ALL {
+        assert_eq!(field.as_str().parse::<AuthorField>().unwrap(), field);
+    }
+
+    let fs = AuthorFieldSet::parse("name, hIndex,papers.title,papers.year,name").unwrap();
+    assert_eq!(fs.as_str(), "name,hIndex,papers.title,papers.year");
+    assert_eq!(
+        fs.fields(),
+        [AuthorField::Name, AuthorField::HIndex, AuthorField::Papers]
+    );
+    assert_eq!(
+        AuthorFieldSet::new([AuthorField::Name, AuthorField::Url])
+            .unwrap()
+            .as_str(),
+        "name,url"
+    );
+
+    // Paper-only names, unknown nesting and expansions the nested papers can't serve.
+    for bad in [
+        "title",
+        "hindex",
+        "name.first",
+        "papers.tldr",
+        "papers.nope",
+        " , ",
+    ] {
+        let err = AuthorFieldSet::parse(bad).unwrap_err();
+        assert!(matches!(err, Error::InvalidFields(_)), "{bad}: {err}");
+    }
+}
+
+#[tokio::test]
+async fn client_rejects_field_set_for_another_endpoint() {
+    // Nothing listens here; the mismatch is caught before any request is sent.
+    let client = semantic_scholar::SemanticScholarClient::builder()
+        .base_url("http://127.0.0.1:9")
+        .build()
+        .unwrap();
+    let search = FieldSet::search(Endpoint::Search).unwrap();
+    let id: semantic_scholar::PaperId = "DOI:10.1/x".parse().unwrap();
+    let err = client.get_paper(&id, &search).await.unwrap_err();
+    assert!(
+        matches!(&err, Error::InvalidFields(m) if m.contains("/paper/search")),
+        "unexpected: {err}"
+    );
+}
@@ -0,0 +1,89 @@
+/// Tests for record-and-replay `Fixtures`.
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use axum::{extract::State, routing::get, Json, Router};
+use common::{fields_for, id};
+use semantic_scholar::{fields::Endpoint, fixtures::Fixtures, Error, SemanticScholarClient};
+use serde_json::{json, Value};
+
+type Hits = Arc<Mutex<u32>>;
+
+async fn paper_handler(State(hits): State<Hits>) -> Json<Value> {
+    *hits.lock().unwrap() += 1;
+    Json(json!({ "paperId": "p1", "title": "Recorded title" }))
+}
+
+async fn start_mock() -> (String, Hits) {
+    let hits = Hits::default();
+    let app = Router::new()
+        .route("/graph/v1/paper/:id", get(paper_handler))
+        .with_state(hits.clone());
+    (common::serve(app).await, hits)
+}
+
+#[tokio::test]
+async fn recorded_responses_replay_without_a_server() {
+    let dir = tempfile::tempdir().unwrap();
+    let (base, hits) = start_mock().await;
+    let recorder = SemanticScholarClient::builder()
+        .base_url(&base)
+        .fixtures(Fixtures::record(dir.path()))
+        .build()
+        .unwrap();
+    recorder
+        .get_paper(
+            &id("DOI:10.1/x"),
+            &fields_for(Endpoint::PaperDetail, "title"),
+        )
+        .await
+        .unwrap();
+    recorder
+        .get_paper(
+            &id("DOI:10.1/x"),
+            &fields_for(Endpoint::PaperDetail, "title"),
+        )
+        .await
+        .unwrap();
+    // Recording always goes to the network.
+    assert_eq!(*hits.lock().unwrap(), 2);
+
+    let files: Vec<_> = std::fs::read_dir(dir.path()).unwrap().flatten().collect();
+    assert_eq!(files.len(), 1);
+    let name = files[0].file_name().into_string().unwrap();
+    assert!(name.starts_with("get-graph-v1-paper-DOI-10-1"), "{name}");
+    let fixture: Value =
+        serde_json::from_str(&std::fs::read_to_string(files[0].path()).unwrap()).unwrap();
+    assert_eq!(fixture["request"]["params"]["fields"], "title");
+
+    // Nothing listens here; replay must not try.
+    let replayer = SemanticScholarClient::builder()
+        .base_url("http://127.0.0.1:9")
+        .fixtures(Fixtures::replay(dir.path()))
+        .build()
+        .unwrap();
+    let paper = replayer
+        .get_paper(
+            &id("DOI:10.1/x"),
+            &fields_for(Endpoint::PaperDetail, "title"),
+        )
+        .await
+        .unwrap();
+    assert_eq!(paper.title.as_deref(), Some("Recorded title"));
+}
+
+#[tokio::test]
+async fn missing_fixture_is_an_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let client = SemanticScholarClient::builder()
+        .fixtures(Fixtures::replay(dir.path()))
+        .build()
+        .unwrap();
+    let err = client
+        .get_paper(&id("p1"), &fields_for(Endpoint::PaperDetail, "title"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Fixture(_)), "unexpected: {err}");
+    assert!(err.to_string().contains("SEMANTIC_SCHOLAR_RECORD=1"));
+}
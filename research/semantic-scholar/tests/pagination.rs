@@ -0,0 +1,232 @@
+/// Tests for the paginated `*_stream` methods.
+///
+/// The mock serves a fixed corpus of `TOTAL` papers and pages through it the way the
+/// real API does: bulk search via an opaque `token`, citations/references via
+/// `offset` + `next`. Pages never exceed `SERVER_PAGE` items, whatever `limit` asks for.
+mod common;
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use axum::{
+    extract::{Query, State},
+    routing::get,
+    Json, Router,
+};
+use common::{fields_for, id};
+use futures::{StreamExt, TryStreamExt};
+use semantic_scholar::{fields::Endpoint, SearchFilters, SemanticScholarClient};
+use serde_json::{json, Value};
+
+const TOTAL: usize = 25;
+const SERVER_PAGE: usize = 10;
+
+// ─── Mock-server infrastructure ──────────────────────────────────────────────
+
+#[derive(Clone, Default)]
+struct MockState {
+    /// Query strings of every request received, in order.
+    requests: Arc<Mutex<Vec<HashMap<String, String>>>>,
+}
+
+fn paper(i: usize) -> Value {
+    json!({ "paperId": format!("p{i}"), "title": format!("Paper {i}") })
+}
+
+fn page_bounds(q: &HashMap<String, String>, start: usize) -> (usize, usize) {
+    let limit: usize = q["limit"].parse().unwrap();
+    (start, (start + limit.min(SERVER_PAGE)).min(TOTAL))
+}
+
+async fn bulk_handler(
+    State(state): State<MockState>,
+    Query(q): Query<HashMap<String, String>>,
+) -> Json<Value> {
+    state.requests.lock().unwrap().push(q.clone());
+    let start = q.get("token").map_or(0, |t| t.parse().unwrap());
+    let (start, end) = page_bounds(&q, start);
+    let token = (end < TOTAL).then(|| end.to_string());
+    Json(json!({
+        "total": TOTAL,
+        "token": token,
+        "data": (start..end).map(paper).collect::<Vec<_>>(),
+    }))
+}
+
+async fn citations_handler(
+    State(state): State<MockState>,
+    Query(q): Query<HashMap<String, String>>,
+) -> Json<Value> {
+    state.requests.lock().unwrap().push(q.clone());
+    let start = q.get("offset").map_or(0, |o| o.parse().unwrap());
+    let (start, end) = page_bounds(&q, start);
+    let next = (end < TOTAL).then_some(end);
+    Json(json!({
+        "offset": start,
+        "next": next,
+        "data": (start..end)
+            .map(|i| json!({ "citingPaper": paper(i), "isInfluential": i % 2 == 0 }))
+            .collect::<Vec<_>>(),
+    }))
+}
+
+async fn references_handler(
+    State(state): State<MockState>,
+    Query(q): Query<HashMap<String, String>>,
+) -> Json<Value> {
+    state.requests.lock().unwrap().push(q.clone());
+    let start = q.get("offset").map_or(0, |o| o.parse().unwrap());
+    let (start, end) = page_bounds(&q, start);
+    let next = (end < TOTAL).then_some(end);
+    Json(json!({
+        "offset": start,
+        "next": next,
+        "data": (start..end).map(|i| json!({ "citedPaper": paper(i) })).collect::<Vec<_>>(),
+    }))
+}
+
+async fn start_mock() -> (SemanticScholarClient, MockState) {
+    let state = MockState::default();
+    let app = Router::new()
+        .route("/graph/v1/paper/search/bulk", get(bulk_handler))
+        .route("/graph/v1/paper/:id/citations", get(citations_handler))
+        .route("/graph/v1/paper/:id/references", get(references_handler))
+        .with_state(state.clone());
+
+    let client = common::client(&common::serve(app).await);
+    (client, state)
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn bulk_stream_follows_token_to_the_end() {
+    let (client, state) = start_mock().await;
+    let papers: Vec<_> = client
+        .search_bulk_stream(
+            "cbt",
+            &fields_for(Endpoint::BulkSearch, "title"),
+            &SearchFilters::new(),
+            None,
+            10_000,
+        )
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(papers.len(), TOTAL);
+    assert_eq!(papers[24].paper_id.as_deref(), Some("p24"));
+    let reqs = state.requests.lock().unwrap();
+    assert_eq!(reqs.len(), 3);
+    assert!(!reqs[0].contains_key("token"));
+    assert_eq!(reqs[1]["token"], "10");
+    assert_eq!(reqs[2]["token"], "20");
+}
+
+#[tokio::test]
+async fn bulk_stream_respects_cap_and_sends_token() {
+    let (client, state) = start_mock().await;
+    let papers: Vec<_> = client
+        .search_bulk_stream(
+            "cbt",
+            &fields_for(Endpoint::BulkSearch, "title"),
+            &SearchFilters::new().year("2020-").min_citations(5),
+            None,
+            12,
+        )
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(papers.len(), 12);
+    let reqs = state.requests.lock().unwrap();
+    assert_eq!(reqs.len(), 2);
+    assert_eq!(reqs[0]["limit"], "12");
+    assert_eq!(reqs[1]["limit"], "2");
+    assert_eq!(reqs[0]["year"], "2020-");
+    assert_eq!(reqs[0]["minCitationCount"], "5");
+}
+
+#[tokio::test]
+async fn citations_stream_walks_offsets() {
+    let (client, state) = start_mock().await;
+    let items: Vec<_> = client
+        .citations_stream(&id("p0"), &fields_for(Endpoint::Citations, "title"), 100)
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(items.len(), TOTAL);
+    let ids: Vec<_> = items
+        .iter()
+        .map(|c| c.citing_paper.as_ref().unwrap().paper_id.clone().unwrap())
+        .collect();
+    assert_eq!(ids.first().map(String::as_str), Some("p0"));
+    assert_eq!(ids.last().map(String::as_str), Some("p24"));
+    let offsets: Vec<_> = state
+        .requests
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|q| q["offset"].clone())
+        .collect();
+    assert_eq!(offsets, ["0", "10", "20"]);
+}
+
+#[tokio::test]
+async fn references_stream_stops_at_cap() {
+    let (client, state) = start_mock().await;
+    let items: Vec<_> = client
+        .references_stream(&id("p0"), &fields_for(Endpoint::References, "title"), 7)
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(items.len(), 7);
+    assert_eq!(state.requests.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn stream_is_lazy() {
+    let (client, state) = start_mock().await;
+    let mut stream =
+        client.citations_stream(&id("p0"), &fields_for(Endpoint::Citations, "title"), 100);
+    assert!(state.requests.lock().unwrap().is_empty());
+
+    stream.next().await.unwrap().unwrap();
+    assert_eq!(state.requests.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn zero_cap_makes_no_requests() {
+    let (client, state) = start_mock().await;
+    let items: Vec<_> = client
+        .search_bulk_stream(
+            "cbt",
+            &fields_for(Endpoint::BulkSearch, "title"),
+            &SearchFilters::new(),
+            None,
+            0,
+        )
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert!(items.is_empty());
+    assert!(state.requests.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn get_citations_sends_offset() {
+    let (client, state) = start_mock().await;
+    let resp = client
+        .get_citations(&id("p0"), &fields_for(Endpoint::Citations, "title"), 10, 20)
+        .await
+        .unwrap();
+
+    assert_eq!(resp.data.len(), 5);
+    assert_eq!(resp.next, None);
+    assert_eq!(state.requests.lock().unwrap()[0]["offset"], "20");
+}
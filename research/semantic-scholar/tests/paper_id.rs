@@ -0,0 +1,191 @@
+/// Tests for `PaperId` parsing, normalisation and path encoding.
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use axum::{
+    extract::{Path, State},
+    routing::get,
+    Json, Router,
+};
+use common::fields_for;
+use semantic_scholar::{fields::Endpoint, Error, PaperId};
+use serde_json::{json, Value};
+
+fn parse(s: &str) -> PaperId {
+    s.parse().unwrap_or_else(|e| panic!("{s}: {e}"))
+}
+
+#[test]
+fn doi_forms_normalise_to_the_same_id() {
+    let expected = PaperId::Doi("10.1037/a0012345".into());
+    for input in [
+        "10.1037/a0012345",
+        "DOI:10.1037/a0012345",
+        "doi:10.1037/a0012345",
+        "https://doi.org/10.1037/a0012345",
+        "http://dx.doi.org/10.1037/a0012345",
+        "https://www.doi.org/10.1037/a0012345",
+        "  10.1037/a0012345\n",
+    ] {
+        assert_eq!(parse(input), expected, "{input}");
+    }
+    assert_eq!(expected.to_string(), "DOI:10.1037/a0012345");
+}
+
+#[test]
+fn doi_containing_colons_parses_bare_and_prefixed() {
+    let sici = "10.1002/(SICI)1097-4679(199901)55:1<1::AID-JCLP1>3.0.CO;2-Q";
+    let expected = PaperId::Doi(sici.into());
+    assert_eq!(parse(sici), expected);
+    assert_eq!(parse(&format!("DOI:{sici}")), expected);
+    assert_eq!(parse(&format!("https://doi.org/{sici}")), expected);
+    assert_eq!(
+        expected.path_segment(),
+        "DOI:10.1002%2F%28SICI%291097-4679%28199901%2955:1%3C1::AID-JCLP1%3E3.0.CO%3B2-Q"
+    );
+}
+
+#[test]
+fn arxiv_forms() {
+    assert_eq!(
+        parse("arXiv:1705.10311"),
+        PaperId::ArXiv("1705.10311".into())
+    );
+    assert_eq!(
+        parse("https://arxiv.org/abs/1706.03762v5"),
+        PaperId::ArXiv("1706.03762v5".into())
+    );
+    assert_eq!(
+        parse("https://arxiv.org/pdf/1706.03762.pdf"),
+        PaperId::ArXiv("1706.03762".into())
+    );
+    assert_eq!(parse("arxiv:1705.10311").to_string(), "ARXIV:1705.10311");
+}
+
+#[test]
+fn pubmed_forms() {
+    assert_eq!(parse("PMID:19380436"), PaperId::Pmid("19380436".into()));
+    assert_eq!(
+        parse("https://pubmed.ncbi.nlm.nih.gov/19380436/"),
+        PaperId::Pmid("19380436".into())
+    );
+    assert_eq!(parse("PMC2323736"), PaperId::Pmcid("2323736".into()));
+    assert_eq!(parse("PMCID:PMC2323736").to_string(), "PMCID:2323736");
+}
+
+#[test]
+fn other_schemes() {
+    assert_eq!(parse("CorpusId:215416146"), PaperId::CorpusId(215416146));
+    assert_eq!(parse("ACL:W12-3903"), PaperId::Acl("W12-3903".into()));
+    assert_eq!(parse("MAG:112218234"), PaperId::Mag("112218234".into()));
+    assert_eq!(
+        parse("https://www.biorxiv.org/content/10.1101/2020.02.06.937847v1"),
+        PaperId::Url("https://www.biorxiv.org/content/10.1101/2020.02.06.937847v1".into())
+    );
+}
+
+#[test]
+fn s2_ids_and_links() {
+    let sha = "649def34f8be52c8b66281af98ae884c09aef38b";
+    assert_eq!(parse(sha), PaperId::S2(sha.into()));
+    assert_eq!(
+        parse(&format!(
+            "https://www.semanticscholar.org/paper/Construction-of-the-Literature-Graph/{sha}"
+        )),
+        PaperId::S2(sha.into())
+    );
+    assert_eq!(parse(sha).to_string(), sha);
+}
+
+#[test]
+fn garbage_is_rejected() {
+    for input in [
+        "",
+        "   ",
+        "two words",
+        "ISBN:123",
+        "PMID:abc",
+        "CorpusId:x",
+        "a/b",
+        "hello",
+        "ab€x",
+        "pm€1",
+        "€€€",
+        "PMC€",
+    ] {
+        let err = input.parse::<PaperId>().unwrap_err();
+        assert!(matches!(err, Error::InvalidPaperId(_)), "{input}: {err}");
+    }
+}
+
+#[test]
+fn bare_ids_by_shape() {
+    assert_eq!(parse("1705.10311"), PaperId::ArXiv("1705.10311".into()));
+    assert_eq!(parse("1706.03762v5"), PaperId::ArXiv("1706.03762v5".into()));
+    assert_eq!(
+        parse("hep-th/9901001"),
+        PaperId::ArXiv("hep-th/9901001".into())
+    );
+    let sha = "649DEF34F8BE52C8B66281AF98AE884C09AEF38B";
+    assert_eq!(parse(sha), PaperId::S2(sha.to_ascii_lowercase()));
+}
+
+#[test]
+fn bare_digits_ask_for_a_prefix() {
+    let err = "12345".parse::<PaperId>().unwrap_err();
+    assert!(matches!(err, Error::AmbiguousPaperId(_)), "{err}");
+    assert!(err.to_string().contains("PMID:"), "{err}");
+}
+
+#[test]
+fn path_segment_encodes_slashes() {
+    assert_eq!(
+        parse("10.1037/a0012345").path_segment(),
+        "DOI:10.1037%2Fa0012345"
+    );
+    assert_eq!(
+        parse("URL:https://aclanthology.org/W12-3903").path_segment(),
+        "URL:https:%2F%2Faclanthology.org%2FW12-3903"
+    );
+    assert_eq!(parse("PMID:19380436").path_segment(), "PMID:19380436");
+}
+
+#[test]
+fn serde_uses_api_form() {
+    let id = parse("https://doi.org/10.1/x");
+    assert_eq!(serde_json::to_value(&id).unwrap(), json!("DOI:10.1/x"));
+    let back: PaperId = serde_json::from_value(json!("doi:10.1/x")).unwrap();
+    assert_eq!(back, id);
+}
+
+async fn paper_handler(
+    State(seen): State<Arc<Mutex<Vec<String>>>>,
+    Path(id): Path<String>,
+) -> Json<Value> {
+    seen.lock().unwrap().push(id.clone());
+    Json(json!({ "paperId": "p1", "title": format!("Title of {id}") }))
+}
+
+#[tokio::test]
+async fn doi_with_slash_reaches_single_segment_route() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let app = Router::new()
+        .route("/graph/v1/paper/:id", get(paper_handler))
+        .with_state(seen.clone());
+    let client = common::client(&common::serve(app).await);
+
+    let paper = client
+        .get_paper(
+            &parse("https://doi.org/10.1037/a0012345"),
+            &fields_for(Endpoint::PaperDetail, "title"),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        paper.title.as_deref(),
+        Some("Title of DOI:10.1037/a0012345")
+    );
+    assert_eq!(*seen.lock().unwrap(), ["DOI:10.1037/a0012345"]);
+}
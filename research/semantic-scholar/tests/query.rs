@@ -0,0 +1,180 @@
+/// Tests for the bulk query builder and the search filter parameters.
+mod common;
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use axum::{
+    extract::{Query, State},
+    routing::get,
+    Json, Router,
+};
+use common::fields_for;
+use semantic_scholar::{
+    fields::Endpoint, BulkQuery, Error, FieldOfStudy, PublicationType, SearchFilters,
+    SemanticScholarClient,
+};
+use serde_json::{json, Value};
+
+type Queries = Arc<Mutex<Vec<HashMap<String, String>>>>;
+
+async fn bulk_handler(
+    State(queries): State<Queries>,
+    Query(q): Query<HashMap<String, String>>,
+) -> Json<Value> {
+    queries.lock().unwrap().push(q);
+    Json(json!({ "total": 0, "token": null, "data": [] }))
+}
+
+async fn start_mock() -> (SemanticScholarClient, Queries) {
+    let queries = Queries::default();
+    let app = Router::new()
+        .route("/graph/v1/paper/search/bulk", get(bulk_handler))
+        .with_state(queries.clone());
+    let client = common::client(&common::serve(app).await);
+    (client, queries)
+}
+
+#[test]
+fn builder_renders_operators() {
+    let q = BulkQuery::new()
+        .term("PTSD")
+        .phrase("trauma focused")
+        .require("children")
+        .exclude("adult")
+        .exclude("case report")
+        .prefix("adolescen")
+        .any_of([
+            BulkQuery::new().term("CBT"),
+            BulkQuery::new().phrase("exposure therapy"),
+        ]);
+    assert_eq!(
+        q.to_string(),
+        r#"PTSD "trauma focused" +children -adult -"case report" adolescen* (CBT | "exposure therapy")"#
+    );
+}
+
+#[test]
+fn operator_characters_are_neutralised() {
+    // Hyphens, pipes and stars inside a term would otherwise negate, OR or wildcard.
+    assert_eq!(BulkQuery::new().term("TF-CBT").build(), r#""TF-CBT""#);
+    assert_eq!(BulkQuery::new().term("a|b c*").build(), r#""a|b" "c*""#);
+    assert_eq!(BulkQuery::new().exclude("-adult").build(), r#"-"-adult""#);
+    // Quotes and backslashes cannot appear inside a phrase.
+    assert_eq!(
+        BulkQuery::new().phrase(r#"the "gold\ standard""#).build(),
+        r#""the gold standard""#
+    );
+    assert_eq!(BulkQuery::new().prefix("self-regul").build(), "self regul*");
+}
+
+#[test]
+fn empty_parts_are_dropped() {
+    let q = BulkQuery::new()
+        .phrase("  ")
+        .exclude("")
+        .prefix("*")
+        .any_of([BulkQuery::new(), BulkQuery::new()]);
+    assert!(q.is_empty());
+
+    let single = BulkQuery::new().any_of([BulkQuery::new(), BulkQuery::new().term("CBT")]);
+    assert_eq!(single.build(), "CBT");
+    let grouped = BulkQuery::new().any_of([BulkQuery::new().term("parent training")]);
+    assert_eq!(grouped.build(), "(parent training)");
+}
+
+#[test]
+fn terms_of_only_quotes_and_backslashes_are_dropped() {
+    let q = BulkQuery::new()
+        .term("\"")
+        .term("\\")
+        .require("\"\\")
+        .exclude("\"");
+    assert!(q.is_empty());
+
+    let q = BulkQuery::new().term("grief \" therapy");
+    assert_eq!(q.build(), "grief therapy");
+}
+
+#[test]
+fn filter_values_parse_leniently() {
+    assert_eq!(
+        "meta-analysis".parse::<PublicationType>().unwrap(),
+        PublicationType::MetaAnalysis
+    );
+    assert_eq!(
+        "computer-science".parse::<FieldOfStudy>().unwrap(),
+        FieldOfStudy::ComputerScience
+    );
+    assert_eq!(
+        FieldOfStudy::AgriculturalAndFoodSciences.to_string(),
+        "Agricultural and Food Sciences"
+    );
+    assert!(matches!(
+        "preprint".parse::<PublicationType>(),
+        Err(Error::InvalidFilter(_))
+    ));
+}
+
+#[tokio::test]
+async fn filters_become_query_parameters() {
+    let (client, queries) = start_mock().await;
+
+    let filters = SearchFilters::new()
+        .year("2015-")
+        .publication_date_or_year("2016-01:2020")
+        .min_citations(10)
+        .publication_type(PublicationType::MetaAnalysis)
+        .publication_type(PublicationType::Review)
+        .publication_type(PublicationType::Review)
+        .field_of_study(FieldOfStudy::Psychology)
+        .field_of_study(FieldOfStudy::Medicine)
+        .venue("JAMA Pediatrics")
+        .venue("Child Dev")
+        .open_access_pdf();
+    let query = BulkQuery::new().term("TF-CBT").prefix("child");
+    client
+        .search_bulk(
+            &query.build(),
+            &fields_for(Endpoint::BulkSearch, "title"),
+            &filters,
+            None,
+            5,
+        )
+        .await
+        .unwrap();
+    client
+        .search_bulk(
+            "cbt",
+            &fields_for(Endpoint::BulkSearch, "title"),
+            &SearchFilters::new(),
+            None,
+            5,
+        )
+        .await
+        .unwrap();
+
+    let reqs = queries.lock().unwrap();
+    let q = &reqs[0];
+    assert_eq!(q["query"], r#""TF-CBT" child*"#);
+    assert_eq!(q["year"], "2015-");
+    assert_eq!(q["publicationDateOrYear"], "2016-01:2020");
+    assert_eq!(q["minCitationCount"], "10");
+    assert_eq!(q["publicationTypes"], "MetaAnalysis,Review");
+    assert_eq!(q["fieldsOfStudy"], "Psychology,Medicine");
+    assert_eq!(q["venue"], "JAMA Pediatrics,Child Dev");
+    assert_eq!(q["openAccessPdf"], "");
+
+    let keys: Vec<_> = {
+        let mut k: Vec<_> = reqs[1].keys().map(String::as_str).collect();
+        k.sort();
+        k
+    };
+    assert_eq!(
+        keys,
+        ["fields", "limit", "query"],
+        "empty filters add no parameters"
+    );
+}
@@ -0,0 +1,104 @@
+/// Tests for the client-side token-bucket `RateLimiter`.
+///
+/// Time is paused, so waits are measured on tokio's virtual clock.
+use std::time::Duration;
+
+use semantic_scholar::{RateLimiter, SemanticScholarClient};
+use tokio::time::Instant;
+
+#[tokio::test(start_paused = true)]
+async fn burst_is_served_immediately() {
+    let limiter = RateLimiter::new(1.0, 3);
+    let start = Instant::now();
+    for _ in 0..3 {
+        limiter.acquire().await;
+    }
+    assert_eq!(start.elapsed(), Duration::ZERO);
+}
+
+#[tokio::test(start_paused = true)]
+async fn requests_beyond_burst_wait_for_refill() {
+    let limiter = RateLimiter::new(2.0, 1);
+    let start = Instant::now();
+    for _ in 0..3 {
+        limiter.acquire().await;
+    }
+    // First token is free, the next two arrive every 500ms.
+    assert!(start.elapsed() >= Duration::from_millis(1000));
+    assert!(start.elapsed() < Duration::from_millis(1100));
+}
+
+#[tokio::test(start_paused = true)]
+async fn clones_share_one_bucket() {
+    let limiter = RateLimiter::new(1.0, 1);
+    let other = limiter.clone();
+    let start = Instant::now();
+    limiter.acquire().await;
+    other.acquire().await;
+    assert!(start.elapsed() >= Duration::from_secs(1));
+}
+
+#[tokio::test(start_paused = true)]
+async fn concurrent_acquires_are_spaced_out() {
+    let limiter = RateLimiter::new(4.0, 1);
+    let start = Instant::now();
+    let tasks: Vec<_> = (0..5)
+        .map(|_| {
+            let l = limiter.clone();
+            tokio::spawn(async move {
+                l.acquire().await;
+                Instant::now()
+            })
+        })
+        .collect();
+    let mut finished = Vec::new();
+    for t in tasks {
+        finished.push(t.await.unwrap());
+    }
+    let last = finished.iter().max().unwrap();
+    assert!(last.duration_since(start) >= Duration::from_millis(1000));
+}
+
+#[tokio::test(start_paused = true)]
+async fn idle_time_refills_up_to_burst_only() {
+    let limiter = RateLimiter::new(1.0, 2);
+    limiter.acquire().await;
+    limiter.acquire().await;
+    tokio::time::sleep(Duration::from_secs(60)).await;
+
+    let start = Instant::now();
+    limiter.acquire().await;
+    limiter.acquire().await;
+    assert_eq!(start.elapsed(), Duration::ZERO);
+    limiter.acquire().await;
+    assert!(start.elapsed() >= Duration::from_secs(1));
+}
+
+#[tokio::test(start_paused = true)]
+async fn client_clones_share_limiter() {
+    let client = SemanticScholarClient::builder()
+        .rate_limit(1.0, 1)
+        .build()
+        .unwrap();
+    let clone = client.clone();
+
+    let start = Instant::now();
+    client.rate_limiter().unwrap().acquire().await;
+    assert_eq!(start.elapsed(), Duration::ZERO);
+    // The burst token was spent through the original, so the clone has to wait.
+    clone.rate_limiter().unwrap().acquire().await;
+    let waited = start.elapsed();
+    assert!(waited >= Duration::from_secs(1), "{waited:?}");
+    assert!(waited < Duration::from_millis(1100), "{waited:?}");
+}
+
+#[test]
+fn rate_limiter_is_off_by_default() {
+    assert!(SemanticScholarClient::new(None).rate_limiter().is_none());
+}
+
+#[test]
+#[should_panic(expected = "burst must be at least 1")]
+fn zero_burst_panics() {
+    RateLimiter::new(1.0, 0);
+}
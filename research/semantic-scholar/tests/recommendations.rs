@@ -0,0 +1,133 @@
+/// Tests for the Recommendations API methods against an in-process axum mock.
+mod common;
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use axum::{
+    extract::{Path, Query, State},
+    routing::{get, post},
+    Json, Router,
+};
+use common::{fields_for, id};
+use semantic_scholar::{fields::Endpoint, SemanticScholarClient};
+use serde_json::{json, Value};
+
+#[derive(Debug, Clone)]
+struct Capture {
+    path: String,
+    query: HashMap<String, String>,
+    body: Value,
+}
+
+type Captures = Arc<Mutex<Vec<Capture>>>;
+
+fn recommended() -> Json<Value> {
+    Json(json!({
+        "recommendedPapers": [
+            { "paperId": "r1", "title": "TF-CBT for school-age children" },
+            { "paperId": "r2", "title": "Parent-child trauma therapy RCT" },
+        ]
+    }))
+}
+
+async fn forpaper_handler(
+    State(caps): State<Captures>,
+    Path(id): Path<String>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Json<Value> {
+    caps.lock().unwrap().push(Capture {
+        path: format!("forpaper/{id}"),
+        query,
+        body: Value::Null,
+    });
+    recommended()
+}
+
+async fn multi_handler(
+    State(caps): State<Captures>,
+    Query(query): Query<HashMap<String, String>>,
+    Json(body): Json<Value>,
+) -> Json<Value> {
+    caps.lock().unwrap().push(Capture {
+        path: "papers".into(),
+        query,
+        body,
+    });
+    recommended()
+}
+
+async fn start_mock() -> (SemanticScholarClient, Captures) {
+    let caps = Captures::default();
+    let app = Router::new()
+        .route(
+            "/recommendations/v1/papers/forpaper/:id",
+            get(forpaper_handler),
+        )
+        .route("/recommendations/v1/papers/", post(multi_handler))
+        .with_state(caps.clone());
+
+    let client = common::client(&common::serve(app).await);
+    (client, caps)
+}
+
+#[tokio::test]
+async fn single_seed_uses_forpaper_endpoint() {
+    let (client, caps) = start_mock().await;
+    let resp = client
+        .get_recommendations(
+            &id("seed1"),
+            &fields_for(Endpoint::Recommendations, "title"),
+            5,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(resp.recommended_papers.len(), 2);
+    let caps = caps.lock().unwrap();
+    assert_eq!(caps[0].path, "forpaper/seed1");
+    assert_eq!(caps[0].query["limit"], "5");
+}
+
+#[tokio::test]
+async fn multi_seed_posts_positive_and_negative_ids() {
+    let (client, caps) = start_mock().await;
+    let resp = client
+        .get_recommendations_multi(
+            &[id("rct1"), id("rct2"), id("rct3")],
+            &[id("adult-ptsd")],
+            &fields_for(Endpoint::Recommendations, "title,year"),
+            8,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(resp.recommended_papers[0].paper_id.as_deref(), Some("r1"));
+    let caps = caps.lock().unwrap();
+    assert_eq!(caps[0].path, "papers");
+    assert_eq!(caps[0].query["fields"], "title,year");
+    assert_eq!(caps[0].query["limit"], "8");
+    assert_eq!(
+        caps[0].body["positivePaperIds"],
+        json!(["rct1", "rct2", "rct3"])
+    );
+    assert_eq!(caps[0].body["negativePaperIds"], json!(["adult-ptsd"]));
+}
+
+#[tokio::test]
+async fn multi_seed_without_negatives_sends_empty_list() {
+    let (client, caps) = start_mock().await;
+    client
+        .get_recommendations_multi(
+            &[id("rct1")],
+            &[],
+            &fields_for(Endpoint::Recommendations, "title"),
+            3,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(caps.lock().unwrap()[0].body["negativePaperIds"], json!([]));
+}
@@ -0,0 +1,250 @@
+/// Tests for the retry layer: `Retry-After` handling, jitter and transient 5xx retries.
+mod common;
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime},
+};
+
+use axum::{
+    extract::State,
+    http::{HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use common::{fields_for, id};
+use reqwest::header::HeaderMap;
+use semantic_scholar::{
+    fields::Endpoint, retry::parse_retry_after, Error, RetryPolicy, SemanticScholarClient,
+};
+use serde_json::json;
+use tokio::net::TcpListener;
+
+// ─── Mock-server infrastructure ──────────────────────────────────────────────
+
+/// A scripted reply: status code plus an optional `Retry-After` header value.
+type Reply = (u16, Option<&'static str>);
+
+#[derive(Clone)]
+struct MockState {
+    replies: Arc<Mutex<VecDeque<Reply>>>,
+    hits: Arc<Mutex<u32>>,
+}
+
+async fn paper_handler(State(state): State<MockState>) -> Response {
+    *state.hits.lock().unwrap() += 1;
+    match state.replies.lock().unwrap().pop_front() {
+        Some((200, _)) | None => Json(json!({ "paperId": "abc123" })).into_response(),
+        Some((code, retry_after)) => {
+            let mut resp = (StatusCode::from_u16(code).unwrap(), "error").into_response();
+            if let Some(v) = retry_after {
+                resp.headers_mut()
+                    .insert("retry-after", HeaderValue::from_static(v));
+            }
+            resp
+        }
+    }
+}
+
+async fn start_mock(replies: Vec<Reply>) -> (SemanticScholarClient, Arc<Mutex<u32>>) {
+    let state = MockState {
+        replies: Arc::new(Mutex::new(replies.into_iter().collect())),
+        hits: Arc::new(Mutex::new(0)),
+    };
+    let hits = state.hits.clone();
+    let app = Router::new()
+        .route("/graph/v1/paper/:id", get(paper_handler))
+        .with_state(state);
+
+    let base = common::serve(app).await;
+    let client = SemanticScholarClient::builder()
+        .base_url(&base)
+        .retry_policy(RetryPolicy {
+            max_retries: 2,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(5),
+            multiplier: 2,
+            jitter: 0.0,
+            retry_transient: true,
+        })
+        .build()
+        .unwrap();
+    (client, hits)
+}
+
+fn headers_with_retry_after(value: &str) -> HeaderMap {
+    let mut h = HeaderMap::new();
+    h.insert("retry-after", value.parse().unwrap());
+    h
+}
+
+// ─── Retry-After parsing ─────────────────────────────────────────────────────
+
+#[test]
+fn retry_after_delta_seconds() {
+    let h = headers_with_retry_after("12");
+    assert_eq!(parse_retry_after(&h), Some(Duration::from_secs(12)));
+}
+
+#[test]
+fn retry_after_http_date_in_future() {
+    let at = SystemTime::now() + Duration::from_secs(30);
+    let h = headers_with_retry_after(&httpdate::fmt_http_date(at));
+    let wait = parse_retry_after(&h).unwrap();
+    assert!(wait <= Duration::from_secs(30) && wait >= Duration::from_secs(28));
+}
+
+#[test]
+fn retry_after_http_date_in_past_is_zero() {
+    let h = headers_with_retry_after("Wed, 21 Oct 2015 07:28:00 GMT");
+    assert_eq!(parse_retry_after(&h), Some(Duration::ZERO));
+}
+
+#[test]
+fn retry_after_missing_or_garbage_is_none() {
+    assert_eq!(parse_retry_after(&HeaderMap::new()), None);
+    assert_eq!(parse_retry_after(&headers_with_retry_after("soon")), None);
+}
+
+// ─── Policy ──────────────────────────────────────────────────────────────────
+
+#[test]
+fn delay_prefers_retry_after() {
+    let policy = RetryPolicy::default();
+    assert_eq!(
+        policy.delay(0, Some(Duration::from_secs(9))),
+        Duration::from_secs(9)
+    );
+}
+
+#[test]
+fn huge_retry_after_is_capped_at_max_backoff() {
+    let policy = RetryPolicy::default();
+    assert_eq!(
+        policy.delay(0, Some(Duration::from_secs(86_400))),
+        policy.max_backoff
+    );
+    let far_future = headers_with_retry_after("Fri, 31 Dec 9999 23:59:59 GMT");
+    assert_eq!(
+        policy.delay(0, parse_retry_after(&far_future)),
+        policy.max_backoff
+    );
+}
+
+#[test]
+fn jitter_stays_within_bounds() {
+    let policy = RetryPolicy {
+        jitter: 0.5,
+        ..RetryPolicy::default()
+    };
+    for _ in 0..50 {
+        let d = policy.delay(1, None);
+        assert!(
+            d >= Duration::from_secs(2) && d <= Duration::from_secs(3),
+            "{d:?}"
+        );
+    }
+}
+
+#[test]
+fn transient_statuses_are_retryable() {
+    let policy = RetryPolicy::default();
+    for code in [429, 500, 502, 503, 504] {
+        assert!(policy.should_retry_status(reqwest::StatusCode::from_u16(code).unwrap()));
+    }
+    for code in [400, 401, 404] {
+        assert!(!policy.should_retry_status(reqwest::StatusCode::from_u16(code).unwrap()));
+    }
+}
+
+#[test]
+fn retry_transient_false_only_retries_429() {
+    let policy = RetryPolicy {
+        retry_transient: false,
+        ..RetryPolicy::default()
+    };
+    assert!(policy.should_retry_status(reqwest::StatusCode::TOO_MANY_REQUESTS));
+    assert!(!policy.should_retry_status(reqwest::StatusCode::SERVICE_UNAVAILABLE));
+}
+
+// ─── Client behaviour ────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn server_errors_are_retried_until_success() {
+    let (client, hits) = start_mock(vec![(503, None), (502, None)]).await;
+    client
+        .get_paper(&id("abc123"), &fields_for(Endpoint::PaperDetail, "title"))
+        .await
+        .unwrap();
+    assert_eq!(*hits.lock().unwrap(), 3);
+}
+
+#[tokio::test]
+async fn persistent_server_error_returns_server_error() {
+    let (client, hits) = start_mock(vec![(500, None), (500, None), (500, None)]).await;
+    let err = client
+        .get_paper(&id("abc123"), &fields_for(Endpoint::PaperDetail, "title"))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, Error::ServerError { status: 500, .. }),
+        "unexpected: {err}"
+    );
+    assert_eq!(*hits.lock().unwrap(), 3);
+}
+
+#[tokio::test]
+async fn client_errors_are_not_retried() {
+    let (client, hits) = start_mock(vec![(404, None)]).await;
+    let err = client
+        .get_paper(&id("abc123"), &fields_for(Endpoint::PaperDetail, "title"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::NotFound { .. }), "unexpected: {err}");
+    assert_eq!(*hits.lock().unwrap(), 1);
+}
+
+#[tokio::test]
+async fn rate_limited_error_reports_server_retry_after() {
+    let (client, _) = start_mock(vec![(429, Some("0")), (429, Some("0")), (429, Some("17"))]).await;
+    let err = client
+        .get_paper(&id("abc123"), &fields_for(Endpoint::PaperDetail, "title"))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            Error::RateLimited {
+                retry_after: 17,
+                ..
+            }
+        ),
+        "unexpected: {err}"
+    );
+}
+
+#[tokio::test]
+async fn connection_refused_is_retried_then_reported() {
+    // Bind and immediately drop a listener so the port is (almost certainly) closed.
+    let addr = TcpListener::bind("127.0.0.1:0")
+        .await
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let client = SemanticScholarClient::builder()
+        .base_url(&format!("http://{addr}"))
+        .retry_policy(RetryPolicy {
+            initial_backoff: Duration::from_millis(1),
+            jitter: 0.0,
+            ..RetryPolicy::default()
+        })
+        .build()
+        .unwrap();
+    let err = client
+        .get_paper(&id("abc123"), &fields_for(Endpoint::PaperDetail, "title"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Http { .. }), "unexpected: {err}");
+}
@@ -0,0 +1,120 @@
+/// Tests for snippet search against an in-process axum mock.
+mod common;
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use axum::{
+    extract::{Query, State},
+    routing::get,
+    Json, Router,
+};
+use semantic_scholar::{types::TextSpan, PaperId, SemanticScholarClient};
+use serde_json::{json, Value};
+
+type Queries = Arc<Mutex<Vec<HashMap<String, String>>>>;
+
+/// Shape of a real `/snippet/search` response, trimmed to one hit.
+fn response() -> Value {
+    json!({
+        "data": [{
+            "snippet": {
+                "text": "TF-CBT produced a large reduction in PTSD symptoms (d = 0.91) [12].",
+                "snippetKind": "body",
+                "section": "Results",
+                "snippetOffset": { "start": 18234, "end": 18301 },
+                "annotations": {
+                    "sentences": [{ "start": 0, "end": 67 }],
+                    "refMentions": [{ "start": 63, "end": 67, "matchedPaperCorpusId": "2402331" }]
+                }
+            },
+            "score": 0.73,
+            "paper": {
+                "corpusId": "13680473",
+                "title": "A multisite randomized controlled trial for children with sexual abuse-related PTSD",
+                "authors": ["Judith A. Cohen", "Esther Deblinger"],
+                "openAccessInfo": { "license": "CCBY", "status": "GREEN", "disclaimer": "..." }
+            }
+        }],
+        "retrievalVersion": "pa1-v1"
+    })
+}
+
+async fn snippet_handler(
+    State(queries): State<Queries>,
+    Query(q): Query<HashMap<String, String>>,
+) -> Json<Value> {
+    queries.lock().unwrap().push(q);
+    Json(response())
+}
+
+async fn start_mock() -> (SemanticScholarClient, Queries) {
+    let queries = Queries::default();
+    let app = Router::new()
+        .route("/graph/v1/snippet/search", get(snippet_handler))
+        .with_state(queries.clone());
+    let client = common::client(&common::serve(app).await);
+    (client, queries)
+}
+
+#[tokio::test]
+async fn snippets_deserialize_with_offsets_and_paper() {
+    let (client, queries) = start_mock().await;
+    let resp = client
+        .search_snippets("TF-CBT effect size", &[], None, None, 5)
+        .await
+        .unwrap();
+
+    assert_eq!(resp.retrieval_version.as_deref(), Some("pa1-v1"));
+    let hit = &resp.data[0];
+    assert_eq!(hit.snippet.section.as_deref(), Some("Results"));
+    assert_eq!(hit.snippet.snippet_kind.as_deref(), Some("body"));
+    assert!(hit.snippet.text.contains("d = 0.91"));
+    assert_eq!(
+        hit.snippet.snippet_offset,
+        Some(TextSpan {
+            start: 18234,
+            end: 18301
+        })
+    );
+    let refs = hit
+        .snippet
+        .annotations
+        .as_ref()
+        .unwrap()
+        .ref_mentions
+        .as_ref()
+        .unwrap();
+    assert_eq!(refs[0].matched_paper_corpus_id.as_deref(), Some("2402331"));
+    assert_eq!(hit.paper.paper_id(), Some(PaperId::CorpusId(13680473)));
+    assert_eq!(hit.paper.authors.as_ref().unwrap().len(), 2);
+
+    let q = &queries.lock().unwrap()[0];
+    assert_eq!(q["query"], "TF-CBT effect size");
+    assert_eq!(q["limit"], "5");
+    assert!(!q.contains_key("paperIds"));
+    assert!(!q.contains_key("year"));
+}
+
+#[tokio::test]
+async fn snippet_filters_are_sent_as_query_params() {
+    let (client, queries) = start_mock().await;
+    let papers: Vec<PaperId> = ["DOI:10.1097/00004583-200404000-00005", "CorpusId:13680473"]
+        .iter()
+        .map(|s| s.parse().unwrap())
+        .collect();
+    client
+        .search_snippets("dropout rate", &papers, Some("2010-"), Some(20), 10)
+        .await
+        .unwrap();
+
+    let q = &queries.lock().unwrap()[0];
+    assert_eq!(
+        q["paperIds"],
+        "DOI:10.1097/00004583-200404000-00005,CorpusId:13680473"
+    );
+    assert_eq!(q["year"], "2010-");
+    assert_eq!(q["minCitationCount"], "20");
+}
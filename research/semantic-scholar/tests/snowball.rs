@@ -0,0 +1,224 @@
+/// Snowballing through `MockScholar`'s citation edges.
+mod common;
+
+use axum::{routing::get, Json, Router};
+use common::{client, ids, serve};
+use semantic_scholar::{
+    snowball::{Direction, SnowballOptions},
+    CitationIntent, Error, Paper,
+};
+use semantic_scholar_mock::{CitationEdge, Corpus, Failure, MockScholar, Route};
+use serde_json::json;
+
+fn paper(id: &str, year: u32, citations: u64) -> Paper {
+    Paper {
+        paper_id: Some(id.into()),
+        title: Some(format!("Paper {id} & co")),
+        year: Some(year),
+        citation_count: Some(citations),
+        ..Default::default()
+    }
+}
+
+/// `a` and `b` cite the seed, the seed cites `c` (old) and `d`, `e` cites `a`.
+fn corpus() -> Corpus {
+    Corpus::new()
+        .paper(paper("seed", 2012, 300))
+        .paper(paper("a", 2016, 40))
+        .paper(paper("b", 2018, 3))
+        .paper(paper("c", 2001, 900))
+        .paper(paper("d", 2010, 50))
+        .paper(paper("e", 2020, 12))
+        .edge(
+            CitationEdge::new("a", "seed")
+                .intents(["methodology"])
+                .influential()
+                .context("We adapted the protocol of [4]."),
+        )
+        .edge(CitationEdge::new("b", "seed").intents(["background"]))
+        .cites("seed", "c")
+        .edge(CitationEdge::new("seed", "d").intents(["result"]))
+        .edge(
+            CitationEdge::new("e", "a")
+                .intents(["methodology"])
+                .influential(),
+        )
+        .cites("a", "d")
+}
+
+fn node_ids(graph: &semantic_scholar::snowball::CitationGraph) -> Vec<(&str, u32)> {
+    graph.nodes.iter().map(|n| (n.id.as_str(), n.hop)).collect()
+}
+
+#[tokio::test]
+async fn one_hop_both_directions_with_filters() {
+    let mock = MockScholar::start(corpus()).await;
+    let options = SnowballOptions {
+        year: Some("2005-".into()),
+        min_citations: Some(10),
+        ..Default::default()
+    };
+    let graph = mock
+        .client()
+        .snowball(&ids(&["seed"]), &options)
+        .await
+        .unwrap();
+
+    // `b` has too few citations and `c` is too old.
+    assert_eq!(node_ids(&graph), [("seed", 0), ("a", 1), ("d", 1)]);
+    let edges: Vec<_> = graph
+        .edges
+        .iter()
+        .map(|e| (e.citing.as_str(), e.cited.as_str()))
+        .collect();
+    assert_eq!(edges, [("a", "seed"), ("seed", "d")]);
+    let first = &graph.edges[0];
+    assert_eq!(first.intents, [CitationIntent::Methodology]);
+    assert!(first.is_influential);
+    assert_eq!(first.contexts, ["We adapted the protocol of [4]."]);
+}
+
+#[tokio::test]
+async fn two_hops_dedupe_and_influential_only() {
+    let mock = MockScholar::start(corpus()).await;
+    let options = SnowballOptions {
+        hops: 2,
+        direction: Direction::Forward,
+        influential_only: true,
+        ..Default::default()
+    };
+    let graph = mock
+        .client()
+        .snowball(&ids(&["seed", "seed", "DOI:10.1/none"]), &options)
+        .await
+        .unwrap();
+
+    assert_eq!(node_ids(&graph), [("seed", 0), ("a", 1), ("e", 2)]);
+    assert_eq!(graph.edges.len(), 2);
+    assert_eq!(graph.unresolved_seeds, ids(&["DOI:10.1/none"]));
+    // Three seed lookups, then one expansion per frontier paper: `seed`, then `a`.
+    assert_eq!(mock.request_count(Route::Paper), 3);
+    assert_eq!(mock.request_count(Route::Citations), 2);
+    assert_eq!(mock.request_count(Route::References), 0);
+
+    let both = SnowballOptions {
+        hops: 2,
+        ..Default::default()
+    };
+    let graph = mock
+        .client()
+        .snowball(&ids(&["seed"]), &both)
+        .await
+        .unwrap();
+    assert_eq!(graph.nodes.len(), 6);
+    // `a -> d` joins two papers found at hop 1 and is recorded once.
+    let a_to_d = graph
+        .edges
+        .iter()
+        .filter(|e| e.citing == "a" && e.cited == "d")
+        .count();
+    assert_eq!(a_to_d, 1);
+    assert_eq!(graph.in_degrees()["d"], 2);
+}
+
+#[tokio::test]
+async fn intent_filter_max_papers_and_bad_year() {
+    let mock = MockScholar::start(corpus()).await;
+    let client = mock.client();
+
+    let graph = client
+        .snowball(
+            &ids(&["seed"]),
+            &SnowballOptions {
+                intents: vec![CitationIntent::Result],
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(node_ids(&graph), [("seed", 0), ("d", 1)]);
+
+    let capped = client
+        .snowball(
+            &ids(&["seed"]),
+            &SnowballOptions {
+                max_papers: 2,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(capped.nodes.len(), 2);
+
+    let err = client
+        .snowball(
+            &ids(&["seed"]),
+            &SnowballOptions {
+                year: Some("recent".into()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::InvalidFilter(_)), "{err}");
+}
+
+#[tokio::test]
+async fn failed_expansion_is_recorded_and_skipped() {
+    let mock = MockScholar::start(corpus()).await;
+    mock.fail_next_on(Route::References, Failure::status(403, "Forbidden"));
+    let options = SnowballOptions {
+        direction: Direction::Backward,
+        concurrency: 1,
+        ..Default::default()
+    };
+    let graph = mock
+        .client()
+        .snowball(&ids(&["seed", "e"]), &options)
+        .await
+        .unwrap();
+
+    // `seed`'s references fail, so `c` and `d` are missing; `e` still reaches `a`.
+    assert_eq!(node_ids(&graph), [("seed", 0), ("e", 0), ("a", 1)]);
+    assert_eq!(graph.failed_expansions.len(), 1);
+    let failure = &graph.failed_expansions[0];
+    assert_eq!((failure.id.as_str(), failure.hop), ("seed", 1));
+    assert!(failure.error.contains("403"), "{}", failure.error);
+}
+
+#[tokio::test]
+async fn seed_without_paper_id_is_unresolved() {
+    let app = Router::new().route(
+        "/graph/v1/paper/:id",
+        get(|| async { Json(json!({ "title": "Withdrawn preprint" })) }),
+    );
+    let base = serve(app).await;
+    let graph = client(&base)
+        .snowball(&ids(&["ghost"]), &SnowballOptions::default())
+        .await
+        .unwrap();
+
+    assert!(graph.nodes.is_empty());
+    assert_eq!(graph.unresolved_seeds, ids(&["ghost"]));
+}
+
+#[tokio::test]
+async fn graphml_is_escaped_and_directed() {
+    let mock = MockScholar::start(corpus()).await;
+    let graph = mock
+        .client()
+        .snowball(&ids(&["seed"]), &SnowballOptions::default())
+        .await
+        .unwrap();
+    let xml = graph.to_graphml();
+
+    assert!(xml.starts_with("<?xml"));
+    assert!(xml.contains(r#"<graph id="snowball" edgedefault="directed">"#));
+    assert!(xml.contains(r#"<node id="seed">"#));
+    assert!(xml.contains("<data key=\"title\">Paper seed &amp; co</data>"));
+    assert!(xml.contains(r#"<edge source="a" target="seed">"#));
+    assert!(xml.contains("<data key=\"intents\">methodology</data>"));
+    assert_eq!(xml.matches("<node ").count(), graph.nodes.len());
+    assert_eq!(xml.matches("<edge ").count(), graph.edges.len());
+    assert!(xml.trim_end().ends_with("</graphml>"));
+}
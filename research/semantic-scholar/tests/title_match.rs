@@ -0,0 +1,87 @@
+/// Tests for `match_paper_by_title` against an in-process mock of
+/// `/graph/v1/paper/search/match`.
+mod common;
+
+use std::collections::HashMap;
+
+use axum::{
+    extract::Query,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use common::fields_for;
+use semantic_scholar::{fields::Endpoint, Error, SemanticScholarClient};
+use serde_json::json;
+
+/// Knows one title; anything containing "nonexistent" is a 404, "broken" a 500.
+async fn match_handler(Query(q): Query<HashMap<String, String>>) -> Response {
+    let query = q["query"].as_str();
+    if query.contains("nonexistent") {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": "Title match not found" })),
+        )
+            .into_response();
+    }
+    if query.contains("broken") {
+        return (StatusCode::BAD_REQUEST, "bad query").into_response();
+    }
+    Json(json!({
+        "data": [{
+            "paperId": "tfcbt-rct",
+            "title": "A multisite, randomized controlled trial for children with sexual abuse-related PTSD symptoms",
+            "year": 2004,
+            "matchScore": 174.2,
+            "fields": q["fields"],
+        }]
+    }))
+    .into_response()
+}
+
+async fn start_mock() -> SemanticScholarClient {
+    let app = Router::new().route("/graph/v1/paper/search/match", get(match_handler));
+
+    common::client(&common::serve(app).await)
+}
+
+#[tokio::test]
+async fn match_returns_paper_and_score() {
+    let client = start_mock().await;
+    let m = client
+        .match_paper_by_title(
+            "A multisite randomized controlled trial for children with sexual abuse related PTSD",
+            &fields_for(Endpoint::TitleMatch, "title,year"),
+        )
+        .await
+        .unwrap()
+        .expect("expected a match");
+
+    assert_eq!(m.paper.paper_id.as_deref(), Some("tfcbt-rct"));
+    assert_eq!(m.paper.year, Some(2004));
+    assert_eq!(m.match_score, Some(174.2));
+}
+
+#[tokio::test]
+async fn no_match_is_none() {
+    let client = start_mock().await;
+    let m = client
+        .match_paper_by_title(
+            "A nonexistent paper the model made up",
+            &fields_for(Endpoint::TitleMatch, "title"),
+        )
+        .await
+        .unwrap();
+    assert!(m.is_none());
+}
+
+#[tokio::test]
+async fn other_errors_propagate() {
+    let client = start_mock().await;
+    let err = client
+        .match_paper_by_title("broken", &fields_for(Endpoint::TitleMatch, "title"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::BadRequest { .. }), "unexpected: {err}");
+}
@@ -0,0 +1,122 @@
+/// Deserialization tests for `Paper` and its nested metadata types.
+use semantic_scholar::{
+    fields::{Endpoint, FieldSet},
+    types::{
+        CitationIntent, CitationItem, Paper, PAPER_FIELDS_BRIEF, PAPER_FIELDS_FULL,
+        SEARCH_FIELDS,
+    },
+    Error,
+};
+use serde_json::json;
+
+fn full_paper() -> Paper {
+    serde_json::from_value(json!({
+        "paperId": "0f3b1c",
+        "title": "Trauma-focused CBT for children: a meta-analysis",
+        "year": 2019,
+        "referenceCount": 84,
+        "externalIds": {
+            "DOI": "10.1016/j.cpr.2019.101752",
+            "PubMed": "31446278",
+            "PubMedCentral": "6789012",
+            "MAG": "2969876543",
+            "CorpusId": 201670123
+        },
+        "publicationTypes": ["Review", "MetaAnalysis", "JournalArticle"],
+        "journal": { "name": "Clinical Psychology Review", "volume": "73", "pages": "101752" },
+        "s2FieldsOfStudy": [
+            { "category": "Psychology", "source": "external" },
+            { "category": "Medicine", "source": "s2-fos-model" }
+        ],
+        "citationStyles": { "bibtex": "@Article{Smith2019TraumafocusedCF, ...}" }
+    }))
+    .unwrap()
+}
+
+#[test]
+fn external_ids_deserialize() {
+    let p = full_paper();
+    let ids = p.external_ids.as_ref().unwrap();
+    assert_eq!(ids.doi.as_deref(), Some("10.1016/j.cpr.2019.101752"));
+    assert_eq!(ids.pubmed.as_deref(), Some("31446278"));
+    assert_eq!(ids.pubmed_central.as_deref(), Some("6789012"));
+    assert_eq!(ids.corpus_id, Some(201670123));
+    assert!(ids.arxiv.is_none());
+    assert_eq!(p.doi(), Some("10.1016/j.cpr.2019.101752"));
+}
+
+#[test]
+fn publication_types_deserialize() {
+    let p = full_paper();
+    assert!(p.has_publication_type("MetaAnalysis"));
+    assert!(!p.has_publication_type("ClinicalTrial"));
+}
+
+#[test]
+fn journal_and_fields_of_study_deserialize() {
+    let p = full_paper();
+    let journal = p.journal.as_ref().unwrap();
+    assert_eq!(journal.name.as_deref(), Some("Clinical Psychology Review"));
+    assert_eq!(journal.volume.as_deref(), Some("73"));
+    let fos = p.s2_fields_of_study.as_ref().unwrap();
+    assert_eq!(fos[1].category.as_deref(), Some("Medicine"));
+    assert_eq!(fos[1].source.as_deref(), Some("s2-fos-model"));
+    assert_eq!(p.reference_count, Some(84));
+    assert!(p.citation_styles.unwrap().bibtex.unwrap().starts_with("@Article"));
+}
+
+#[test]
+fn sparse_paper_has_no_doi() {
+    let p: Paper = serde_json::from_value(json!({ "paperId": "x", "publicationTypes": null })).unwrap();
+    assert_eq!(p.doi(), None);
+    assert!(!p.has_publication_type("Review"));
+}
+
+#[test]
+fn field_constants_request_external_ids_and_types() {
+    for fields in [SEARCH_FIELDS, PAPER_FIELDS_FULL, PAPER_FIELDS_BRIEF] {
+        assert!(fields.contains("externalIds"), "{fields}");
+        assert!(fields.contains("publicationTypes"), "{fields}");
+    }
+    assert!(PAPER_FIELDS_FULL.contains("citationStyles"));
+    assert!(SEARCH_FIELDS.contains("journal"));
+}
+
+#[test]
+fn field_constants_are_valid_for_their_endpoints() {
+    FieldSet::parse(Endpoint::Search, SEARCH_FIELDS).unwrap();
+    FieldSet::parse(Endpoint::BulkSearch, SEARCH_FIELDS).unwrap();
+    FieldSet::parse(Endpoint::PaperDetail, PAPER_FIELDS_FULL).unwrap();
+    FieldSet::parse(Endpoint::Citations, PAPER_FIELDS_BRIEF).unwrap();
+    FieldSet::parse(Endpoint::Recommendations, PAPER_FIELDS_BRIEF).unwrap();
+}
+
+#[test]
+fn citation_intents_deserialize_typed_and_keep_unknown_labels() {
+    let item: CitationItem = serde_json::from_value(json!({
+        "citingPaper": { "paperId": "abc", "title": "PCIT for trauma" },
+        "intents": ["methodology", "result", "futureWork"],
+        "isInfluential": true,
+        "contexts": ["We followed the TF-CBT protocol of Cohen et al. [12]."]
+    }))
+    .unwrap();
+    let intents = item.intents.as_deref().unwrap();
+    assert_eq!(intents[0], CitationIntent::Methodology);
+    assert_eq!(intents[1], CitationIntent::Result);
+    assert_eq!(intents[2], CitationIntent::Other("futureWork".into()));
+    assert!(item.has_intent(&CitationIntent::Methodology));
+    assert!(!item.has_intent(&CitationIntent::Background));
+
+    // Unknown labels round-trip verbatim.
+    let back = serde_json::to_value(&item).unwrap();
+    assert_eq!(back["intents"], json!(["methodology", "result", "futureWork"]));
+
+    assert_eq!(
+        "Background".parse::<CitationIntent>().unwrap(),
+        CitationIntent::Background
+    );
+    assert!(matches!(
+        "futureWork".parse::<CitationIntent>(),
+        Err(Error::InvalidFilter(_))
+    ));
+}
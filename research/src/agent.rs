@@ -5,8 +5,14 @@
 ///   1. POST messages + tool definitions
 ///   2. Execute any tool_calls returned by the model
 ///   3. Append results and repeat until `finish_reason == "stop"`
+///
+/// `prompt` waits for the whole loop; `prompt_stream` runs the same loop over
+/// server-sent events and yields [`AgentEvent`]s as they arrive.
+use std::collections::VecDeque;
+
 use anyhow::{Context, Result};
 use async_trait::async_trait;
+use futures::{Stream, stream};
 use serde_json::{Value, json};
 
 // ─── ToolDefinition ──────────────────────────────────────────────────────────
@@ -102,47 +108,13 @@ pub struct DeepSeekAgent {
 impl DeepSeekAgent {
     /// Run the agentic tool-use loop and return the final text response.
     pub async fn prompt(&self, user_prompt: String) -> Result<String> {
-        let tools_json: Vec<Value> = self
-            .tools
-            .iter()
-            .map(|t| {
-                let def = t.definition();
-                json!({
-                    "type": "function",
-                    "function": {
-                        "name": def.name,
-                        "description": def.description,
-                        "parameters": def.parameters,
-                    }
-                })
-            })
-            .collect();
-
-        let mut messages: Vec<Value> = vec![
-            json!({"role": "system", "content": self.preamble}),
-            json!({"role": "user",   "content": user_prompt}),
-        ];
+        let tools_json = self.tools_json();
+        let mut messages = self.initial_messages(user_prompt);
 
         loop {
-            let mut body = json!({
-                "model": self.model,
-                "messages": messages,
-            });
-            if !tools_json.is_empty() {
-                body["tools"] = json!(tools_json);
-            }
-
-            let url = format!("{}/v1/chat/completions", self.base_url);
             let resp: Value = self
-                .http
-                .post(&url)
-                .bearer_auth(&self.api_key)
-                .json(&body)
-                .send()
-                .await
-                .context("DeepSeek HTTP request failed")?
-                .error_for_status()
-                .context("DeepSeek API returned an error status")?
+                .send(&messages, &tools_json, false)
+                .await?
                 .json()
                 .await
                 .context("parsing DeepSeek JSON response")?;
@@ -164,16 +136,7 @@ impl DeepSeekAgent {
                         let call_id = call["id"].as_str().unwrap_or("").to_string();
                         let fn_name = call["function"]["name"].as_str().unwrap_or("");
                         let args_str = call["function"]["arguments"].as_str().unwrap_or("{}");
-                        let args: Value =
-                            serde_json::from_str(args_str).unwrap_or(json!({}));
-
-                        let result = match self.tools.iter().find(|t| t.name() == fn_name) {
-                            Some(tool) => tool
-                                .call_json(args)
-                                .await
-                                .unwrap_or_else(|e| format!("Tool error: {e}")),
-                            None => format!("Unknown tool: {fn_name}"),
-                        };
+                        let result = self.call_tool(fn_name, args_str).await;
 
                         messages.push(json!({
                             "role":        "tool",
@@ -194,4 +157,368 @@ impl DeepSeekAgent {
             }
         }
     }
+
+    /// Run the same tool-use loop with `stream: true`, yielding text and reasoning
+    /// as they are generated and each tool call as it starts and finishes.
+    ///
+    /// The stream ends after [`AgentEvent::Done`], or after the first error.
+    pub fn prompt_stream(
+        &self,
+        user_prompt: String,
+    ) -> impl Stream<Item = Result<AgentEvent>> + '_ {
+        let state = StreamState {
+            agent: self,
+            tools_json: self.tools_json(),
+            messages: self.initial_messages(user_prompt),
+            response: None,
+            sse: SseDecoder::default(),
+            turn: Turn::default(),
+            queued_calls: VecDeque::new(),
+            pending: VecDeque::new(),
+            finished: false,
+        };
+        stream::unfold(state, |mut state| async move {
+            match state.next_event().await {
+                Ok(Some(event)) => Some((Ok(event), state)),
+                Ok(None) => None,
+                Err(e) => {
+                    state.finished = true;
+                    state.pending.clear();
+                    Some((Err(e), state))
+                }
+            }
+        })
+    }
+
+    fn tools_json(&self) -> Vec<Value> {
+        self.tools
+            .iter()
+            .map(|t| {
+                let def = t.definition();
+                json!({
+                    "type": "function",
+                    "function": {
+                        "name": def.name,
+                        "description": def.description,
+                        "parameters": def.parameters,
+                    }
+                })
+            })
+            .collect()
+    }
+
+    fn initial_messages(&self, user_prompt: String) -> Vec<Value> {
+        vec![
+            json!({"role": "system", "content": self.preamble}),
+            json!({"role": "user",   "content": user_prompt}),
+        ]
+    }
+
+    /// POST one chat completion request and check the status.
+    async fn send(
+        &self,
+        messages: &[Value],
+        tools_json: &[Value],
+        stream: bool,
+    ) -> Result<reqwest::Response> {
+        let mut body = json!({
+            "model": self.model,
+            "messages": messages,
+        });
+        if !tools_json.is_empty() {
+            body["tools"] = json!(tools_json);
+        }
+        if stream {
+            body["stream"] = json!(true);
+        }
+
+        let url = format!("{}/v1/chat/completions", self.base_url);
+        self.http
+            .post(&url)
+            .bearer_auth(&self.api_key)
+            .json(&body)
+            .send()
+            .await
+            .context("DeepSeek HTTP request failed")?
+            .error_for_status()
+            .context("DeepSeek API returned an error status")
+    }
+
+    /// Run a tool by name. Failures become the tool's answer so the model can react.
+    async fn call_tool(&self, name: &str, arguments: &str) -> String {
+        let args: Value = serde_json::from_str(arguments).unwrap_or(json!({}));
+        match self.tools.iter().find(|t| t.name() == name) {
+            Some(tool) => tool
+                .call_json(args)
+                .await
+                .unwrap_or_else(|e| format!("Tool error: {e}")),
+            None => format!("Unknown tool: {name}"),
+        }
+    }
+}
+
+// ─── Streaming ───────────────────────────────────────────────────────────────
+
+/// Progress from [`DeepSeekAgent::prompt_stream`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum AgentEvent {
+    /// A piece of the answer text.
+    TextDelta(String),
+    /// A piece of the model's chain of thought (`reasoning_content`, deepseek-reasoner only).
+    ReasoningDelta(String),
+    /// The model began a tool call; its arguments are still being generated.
+    ToolCallStarted { id: String, name: String },
+    /// A tool call ran; `result` is what was sent back to the model.
+    ToolCallFinished {
+        id: String,
+        name: String,
+        arguments: String,
+        result: String,
+    },
+    /// The final answer, identical to what `prompt` would return.
+    Done { content: String },
+}
+
+/// A tool call being reassembled from `tool_calls` deltas, which carry the ID and
+/// name once and the JSON arguments in fragments.
+#[derive(Default)]
+struct PartialCall {
+    id: String,
+    name: String,
+    arguments: String,
+    announced: bool,
+}
+
+/// What one streamed completion has produced so far.
+#[derive(Default)]
+struct Turn {
+    content: String,
+    calls: Vec<PartialCall>,
+    finish_reason: Option<String>,
+}
+
+struct StreamState<'a> {
+    agent: &'a DeepSeekAgent,
+    tools_json: Vec<Value>,
+    messages: Vec<Value>,
+    /// The completion being read, `None` between turns.
+    response: Option<reqwest::Response>,
+    sse: SseDecoder,
+    turn: Turn,
+    /// Tool calls from the last turn that have not run yet.
+    queued_calls: VecDeque<PartialCall>,
+    pending: VecDeque<AgentEvent>,
+    finished: bool,
+}
+
+impl StreamState<'_> {
+    async fn next_event(&mut self) -> Result<Option<AgentEvent>> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Ok(Some(event));
+            }
+            if self.finished {
+                return Ok(None);
+            }
+            if let Some(call) = self.queued_calls.pop_front() {
+                let result = self.agent.call_tool(&call.name, &call.arguments).await;
+                self.messages.push(json!({
+                    "role":        "tool",
+                    "tool_call_id": call.id,
+                    "content":     result,
+                }));
+                return Ok(Some(AgentEvent::ToolCallFinished {
+                    id: call.id,
+                    name: call.name,
+                    arguments: call.arguments,
+                    result,
+                }));
+            }
+            let Some(response) = self.response.as_mut() else {
+                let response = self
+                    .agent
+                    .send(&self.messages, &self.tools_json, true)
+                    .await?;
+                self.response = Some(response);
+                continue;
+            };
+
+            let chunk = response
+                .chunk()
+                .await
+                .context("reading DeepSeek event stream")?;
+            let (events, ended) = match chunk {
+                Some(bytes) => (self.sse.push(&bytes), false),
+                None => (self.sse.finish(), true),
+            };
+            let mut done = ended;
+            let mut terminated = false;
+            for data in events {
+                if data.trim() == "[DONE]" {
+                    done = true;
+                    terminated = true;
+                    break;
+                }
+                self.apply(&data)?;
+            }
+            if done {
+                // A body that simply stops was cut off mid-completion, not finished.
+                if !terminated && self.turn.finish_reason.is_none() {
+                    anyhow::bail!("DeepSeek event stream ended before the completion finished");
+                }
+                self.response = None;
+                self.sse = SseDecoder::default();
+                self.end_turn()?;
+            }
+        }
+    }
+
+    /// Fold one `chat.completion.chunk` into the turn, queueing events for it.
+    fn apply(&mut self, data: &str) -> Result<()> {
+        let chunk: Value = serde_json::from_str(data)
+            .with_context(|| format!("parsing DeepSeek stream chunk: {data}"))?;
+        let choice = &chunk["choices"][0];
+        let delta = &choice["delta"];
+
+        if let Some(text) = delta["reasoning_content"]
+            .as_str()
+            .filter(|t| !t.is_empty())
+        {
+            self.pending
+                .push_back(AgentEvent::ReasoningDelta(text.into()));
+        }
+        if let Some(text) = delta["content"].as_str().filter(|t| !t.is_empty()) {
+            self.turn.content.push_str(text);
+            self.pending.push_back(AgentEvent::TextDelta(text.into()));
+        }
+        for part in delta["tool_calls"].as_array().into_iter().flatten() {
+            let index = part["index"]
+                .as_u64()
+                .map_or(self.turn.calls.len(), |i| i as usize);
+            // Calls are numbered in order, so an index may only start the next call.
+            if index > self.turn.calls.len() {
+                anyhow::bail!(
+                    "DeepSeek tool call index {index} skips past the {} calls seen so far",
+                    self.turn.calls.len()
+                );
+            }
+            if index == self.turn.calls.len() {
+                self.turn.calls.push(PartialCall::default());
+            }
+            let call = &mut self.turn.calls[index];
+            if let Some(id) = part["id"].as_str() {
+                call.id = id.into();
+            }
+            if let Some(name) = part["function"]["name"].as_str() {
+                call.name.push_str(name);
+            }
+            if let Some(args) = part["function"]["arguments"].as_str() {
+                call.arguments.push_str(args);
+            }
+            if !call.announced && !call.name.is_empty() {
+                call.announced = true;
+                self.pending.push_back(AgentEvent::ToolCallStarted {
+                    id: call.id.clone(),
+                    name: call.name.clone(),
+                });
+            }
+        }
+        if let Some(reason) = choice["finish_reason"].as_str() {
+            self.turn.finish_reason = Some(reason.into());
+        }
+        Ok(())
+    }
+
+    /// Either queue the requested tool calls for the next round or finish.
+    fn end_turn(&mut self) -> Result<()> {
+        let turn = std::mem::take(&mut self.turn);
+        let wants_tools = match turn.finish_reason.as_deref() {
+            Some(reason) => reason == "tool_calls",
+            None => !turn.calls.is_empty(),
+        };
+        if !wants_tools {
+            self.pending.push_back(AgentEvent::Done {
+                content: turn.content,
+            });
+            self.finished = true;
+            return Ok(());
+        }
+        anyhow::ensure!(
+            !turn.calls.is_empty(),
+            "expected tool_calls array from model"
+        );
+
+        let tool_calls: Vec<Value> = turn
+            .calls
+            .iter()
+            .map(|call| {
+                json!({
+                    "id": call.id,
+                    "type": "function",
+                    "function": { "name": call.name, "arguments": call.arguments },
+                })
+            })
+            .collect();
+        let content = (!turn.content.is_empty()).then_some(turn.content);
+        self.messages.push(json!({
+            "role": "assistant",
+            "content": content,
+            "tool_calls": tool_calls,
+        }));
+        for call in &turn.calls {
+            if !call.announced {
+                self.pending.push_back(AgentEvent::ToolCallStarted {
+                    id: call.id.clone(),
+                    name: call.name.clone(),
+                });
+            }
+        }
+        self.queued_calls.extend(turn.calls);
+        Ok(())
+    }
+}
+
+/// Splits a `text/event-stream` body into `data` payloads, across chunk boundaries.
+#[derive(Default)]
+struct SseDecoder {
+    buf: Vec<u8>,
+    data: Vec<String>,
+}
+
+impl SseDecoder {
+    /// Feed bytes and return the data of every event they complete.
+    fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.buf.extend_from_slice(bytes);
+        let mut events = Vec::new();
+        while let Some(end) = self.buf.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buf.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            self.line(line.trim_end_matches(['\r', '\n']), &mut events);
+        }
+        events
+    }
+
+    /// Flush an event left unterminated when the body ends.
+    fn finish(&mut self) -> Vec<String> {
+        let mut events = Vec::new();
+        let rest = std::mem::take(&mut self.buf);
+        let rest = String::from_utf8_lossy(&rest);
+        if !rest.is_empty() {
+            self.line(rest.trim_end_matches('\r'), &mut events);
+        }
+        self.line("", &mut events);
+        events
+    }
+
+    fn line(&mut self, line: &str, events: &mut Vec<String>) {
+        if line.is_empty() {
+            if !self.data.is_empty() {
+                events.push(std::mem::take(&mut self.data).join("\n"));
+            }
+        } else if let Some(value) = line.strip_prefix("data:") {
+            self.data
+                .push(value.strip_prefix(' ').unwrap_or(value).to_string());
+        }
+        // Comments (`: keep-alive`) and `event:`/`id:` fields carry nothing we use.
+    }
 }
@@ -1,13 +1,14 @@
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
 use chrono::Utc;
+use futures::TryStreamExt;
 use research_agent::{
-    agent::Client,
+    agent::{AgentEvent, Client},
     therapy_context::TherapyContext,
     tools::{GetPaperDetail, SearchPapers},
 };
-use semantic_scholar::SemanticScholarClient;
-use std::path::PathBuf;
+use semantic_scholar::{ResponseCache, SemanticScholarClient};
+use std::{io::Write, path::PathBuf};
 use tracing::info;
 
 #[derive(Parser)]
@@ -16,7 +17,8 @@ use tracing::info;
     about = "DeepSeek Reasoner + Semantic Scholar therapeutic research agent",
     long_about = "Researches evidence-based therapeutic interventions for goals and Support Priority.\n\n\
                   Required env vars:\n  DEEPSEEK_API_KEY — DeepSeek API key\n\
-                  Optional:\n  SEMANTIC_SCHOLAR_API_KEY — higher rate limits"
+                  Optional:\n  SEMANTIC_SCHOLAR_API_KEY — higher rate limits\n  \
+                  SEMANTIC_SCHOLAR_CACHE_DIR — reuse paper lookups across runs"
 )]
 struct Cli {
     #[command(subcommand)]
@@ -28,8 +30,20 @@ struct Cli {
     #[arg(long)]
     stdout: bool,
 
+    /// Don't echo the model's reasoning and answer to stderr while it runs
+    #[arg(long)]
+    quiet: bool,
+
     #[arg(long, default_value = "_memory/therapeutic-research")]
     output_dir: PathBuf,
+
+    /// Cache Semantic Scholar responses here (or set SEMANTIC_SCHOLAR_CACHE_DIR)
+    #[arg(long)]
+    scholar_cache_dir: Option<PathBuf>,
+
+    /// Serve Semantic Scholar calls from the cache only
+    #[arg(long)]
+    offline: bool,
 }
 
 #[derive(Subcommand)]
@@ -68,9 +82,27 @@ async fn main() -> Result<()> {
         .or_else(|| std::env::var("DEEPSEEK_API_KEY").ok())
         .context("DEEPSEEK_API_KEY not set — pass --api-key or set the env var")?;
 
-    let scholar = SemanticScholarClient::new(
-        std::env::var("SEMANTIC_SCHOLAR_API_KEY").ok().as_deref(),
-    );
+    // A keyed account gets 1 req/s; throttle up front instead of eating 429s when
+    // the model fires search_papers and get_paper_detail back to back.
+    let mut scholar = SemanticScholarClient::builder();
+    if let Ok(key) = std::env::var("SEMANTIC_SCHOLAR_API_KEY") {
+        scholar = scholar.api_key(key).rate_limit(1.0, 1);
+    }
+    let cache_dir = cli
+        .scholar_cache_dir
+        .or_else(|| std::env::var_os("SEMANTIC_SCHOLAR_CACHE_DIR").map(PathBuf::from));
+    match cache_dir {
+        Some(dir) => {
+            let cache = ResponseCache::open(&dir)
+                .with_context(|| format!("opening cache dir {}", dir.display()))?;
+            scholar = scholar.cache(if cli.offline { cache.offline() } else { cache });
+        }
+        None if cli.offline => {
+            anyhow::bail!("--offline needs --scholar-cache-dir or SEMANTIC_SCHOLAR_CACHE_DIR")
+        }
+        None => {}
+    }
+    let scholar = scholar.build().context("building Semantic Scholar client")?;
 
     let client = Client::new(&api_key);
 
@@ -84,6 +116,7 @@ Research standards:
 - Extract concrete therapeutic techniques from each paper
 - Identify outcome measures and their effect sizes when available
 - Report confidence honestly — say 'insufficient evidence' if the literature is sparse
+- Take DOIs and study designs from the tool output (`doi`, `publication_types`); use null rather than guessing a DOI
 - The final JSON block MUST be valid JSON that can be machine-parsed
 
 Evidence levels:
@@ -141,10 +174,36 @@ Evidence levels:
     let prompt = context.build_agent_prompt();
     info!("Sending therapeutic context to DeepSeek Reasoner (may take 30–120s)…");
 
-    let insights = agent
-        .prompt(prompt)
+    // Echo reasoning and answer to stderr as they stream in; stdout stays machine-readable.
+    let mut events = std::pin::pin!(agent.prompt_stream(prompt));
+    let mut insights = String::new();
+    let mut stderr = std::io::stderr();
+    while let Some(event) = events
+        .try_next()
         .await
-        .context("DeepSeek agent call failed")?;
+        .context("DeepSeek agent call failed")?
+    {
+        match event {
+            AgentEvent::ReasoningDelta(text) | AgentEvent::TextDelta(text) if !cli.quiet => {
+                write!(stderr, "{text}")?;
+                stderr.flush()?;
+            }
+            AgentEvent::ToolCallStarted { name, .. } => {
+                if !cli.quiet {
+                    writeln!(stderr)?;
+                }
+                info!("Calling {name}…");
+            }
+            AgentEvent::ToolCallFinished { name, arguments, result, .. } => {
+                info!(tool = %name, args = %arguments, "Tool returned {} chars", result.len());
+            }
+            AgentEvent::Done { content } => insights = content,
+            _ => {}
+        }
+    }
+    if !cli.quiet {
+        writeln!(stderr)?;
+    }
 
     info!("Research complete ({} chars)", insights.len());
 
@@ -1,4 +1,5 @@
 use anyhow::{Context, Result};
+use semantic_scholar::BulkQuery;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
@@ -121,7 +122,7 @@ impl TherapyContext {
         let queries_block = queries
             .iter()
             .enumerate()
-            .map(|(i, q)| format!("  {}. \"{}\"", i + 1, q))
+            .map(|(i, q)| format!("  {}. `{}`", i + 1, q))
             .collect::<Vec<_>>()
             .join("\n");
 
@@ -250,37 +251,93 @@ The JSON block is machine-parsed — it MUST be valid JSON."#,
     }
 }
 
+/// Bulk-search queries for a goal, in the `search_papers` query syntax.
 fn generate_search_queries(goal_type: &str, title: &str, domains: &[String]) -> Vec<String> {
+    let goal = || {
+        let goal_type = goal_type.replace('_', " ");
+        if goal_type.split_whitespace().count() > 1 {
+            BulkQuery::new().phrase(&goal_type)
+        } else {
+            BulkQuery::new().term(&goal_type)
+        }
+    };
+    let youth = || any_prefix(&["child", "adolescen", "youth"]);
     let mut queries = vec![
-        format!(
-            "{} therapeutic intervention children adolescents evidence-based",
-            goal_type
-        ),
-        format!("{} meta-analysis systematic review", goal_type),
-        format!("{} treatment outcome RCT", goal_type),
+        goal()
+            .any_of([
+                BulkQuery::new().term("intervention"),
+                BulkQuery::new().term("therapy"),
+                BulkQuery::new().term("treatment"),
+            ])
+            .and(youth()),
+        goal().any_of([
+            BulkQuery::new().phrase("meta-analysis"),
+            BulkQuery::new().phrase("systematic review"),
+        ]),
+        goal()
+            .any_of([
+                BulkQuery::new().phrase("randomized controlled trial"),
+                BulkQuery::new().term("RCT"),
+            ])
+            .prefix("outcome"),
     ];
 
     for domain in domains {
         let q = match domain.to_uppercase().as_str() {
-            "ACADEMIC" => "school-based intervention academic functioning children",
-            "PEER" => "peer relationship social skills intervention children",
-            "FAMILY" => "family therapy parent training intervention",
-            "SELF_CARE" | "SELFCARE" => "self-care daily living skills intervention children",
-            "SAFETY" => "safety assessment risk management children mental health",
-            _ => "",
+            "ACADEMIC" => BulkQuery::new()
+                .phrase("school-based")
+                .term("intervention")
+                .and(youth()),
+            "PEER" => BulkQuery::new()
+                .any_of([
+                    BulkQuery::new().phrase("peer relationships"),
+                    BulkQuery::new().phrase("social skills"),
+                ])
+                .term("intervention")
+                .and(youth()),
+            "FAMILY" => BulkQuery::new().any_of([
+                BulkQuery::new().phrase("family therapy"),
+                BulkQuery::new().phrase("parent training"),
+            ]),
+            "SELF_CARE" | "SELFCARE" => BulkQuery::new()
+                .any_of([
+                    BulkQuery::new().phrase("self-care"),
+                    BulkQuery::new().phrase("daily living skills"),
+                ])
+                .and(youth()),
+            "SAFETY" => BulkQuery::new()
+                .any_of([
+                    BulkQuery::new().phrase("risk assessment"),
+                    BulkQuery::new().phrase("safety planning"),
+                ])
+                .and(youth()),
+            _ => BulkQuery::new(),
         };
         if !q.is_empty() {
-            queries.push(q.to_string());
+            queries.push(q);
         }
     }
 
     if title.len() > 10 {
         let keywords: Vec<&str> = title.split_whitespace().take(3).collect();
-        queries.push(format!("{} intervention children", keywords.join(" ")));
+        queries.push(
+            BulkQuery::new()
+                .term(&keywords.join(" "))
+                .term("intervention")
+                .and(youth()),
+        );
     }
 
-    queries.push(format!("{} clinical guidelines practice", goal_type));
-    queries
+    queries.push(goal().any_of([
+        BulkQuery::new().prefix("guideline"),
+        BulkQuery::new().phrase("practice parameter"),
+    ]));
+    queries.iter().map(BulkQuery::build).collect()
+}
+
+/// `(a* | b* | …)`.
+fn any_prefix(stems: &[&str]) -> BulkQuery {
+    BulkQuery::new().any_of(stems.iter().map(|s| BulkQuery::new().prefix(s)))
 }
 
 fn infer_focus_keywords(goal_type: &str, title: &str) -> Vec<String> {
@@ -2,8 +2,7 @@ use crate::agent::{Tool, ToolDefinition};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use semantic_scholar::{
-    SemanticScholarClient,
-    types::{PAPER_FIELDS_FULL, SEARCH_FIELDS},
+    fields::Endpoint, FieldSet, PaperId, PublicationType, SearchFilters, SemanticScholarClient,
 };
 
 #[derive(Deserialize, Serialize)]
@@ -11,6 +10,7 @@ pub struct SearchArgs {
     pub query: String,
     pub year: Option<String>,
     pub min_citations: Option<u32>,
+    pub publication_types: Option<Vec<String>>,
     pub limit: Option<u32>,
 }
 
@@ -35,7 +35,7 @@ impl Tool for SearchPapers {
                 "properties": {
                     "query": {
                         "type": "string",
-                        "description": "Search query for therapeutic/clinical research. Use specific terms like 'CBT', 'meta-analysis', 'RCT', 'systematic review'. Combine with population (children, adolescents, families) and condition."
+                        "description": "Search query for therapeutic/clinical research. Use specific terms like 'CBT', 'meta-analysis', 'RCT', 'systematic review'. Combine with population (children, adolescents, families) and condition. Terms are ANDed; supports \"exact phrase\", a | b, -exclude and prefix*."
                     },
                     "year": {
                         "type": "string",
@@ -45,6 +45,14 @@ impl Tool for SearchPapers {
                         "type": "integer",
                         "description": "Minimum citation count. Use 10+ for established research, 5+ for recent work"
                     },
+                    "publication_types": {
+                        "type": "array",
+                        "items": {
+                            "type": "string",
+                            "enum": ["MetaAnalysis", "Review", "ClinicalTrial", "JournalArticle", "CaseReport", "Study"]
+                        },
+                        "description": "Only papers of any of these types, e.g. [\"MetaAnalysis\", \"Review\"] for the highest evidence level"
+                    },
                     "limit": {
                         "type": "integer",
                         "description": "Max papers to return (default 10, max 20)"
@@ -58,17 +66,28 @@ impl Tool for SearchPapers {
     async fn call_json(&self, args: serde_json::Value) -> anyhow::Result<String> {
         let args: SearchArgs = serde_json::from_value(args)?;
         let limit = args.limit.unwrap_or(10).min(20);
+        let filters = SearchFilters {
+            year: args.year.clone(),
+            min_citations: args.min_citations,
+            publication_types: args
+                .publication_types
+                .iter()
+                .flatten()
+                .map(|t| t.parse::<PublicationType>())
+                .collect::<Result<_, _>>()?,
+            ..Default::default()
+        };
         let resp = self
             .0
             .search_bulk(
                 &args.query,
-                SEARCH_FIELDS,
-                args.year.as_deref(),
-                args.min_citations,
+                &FieldSet::search(Endpoint::BulkSearch)?,
+                &filters,
                 Some("citationCount:desc"),
                 limit,
             )
-            .await?;
+            .await
+            .map_err(explain)?;
 
         let papers: Vec<serde_json::Value> = resp
             .data
@@ -94,6 +113,8 @@ impl Tool for SearchPapers {
                     }),
                     "fields": p.fields_of_study,
                     "venue": p.venue,
+                    "doi": p.doi(),
+                    "publication_types": p.publication_types,
                 })
             })
             .collect();
@@ -124,7 +145,8 @@ impl Tool for GetPaperDetail {
         ToolDefinition {
             name: self.name().into(),
             description: "Get full details for a specific paper: complete abstract, AI-generated \
-                          TLDR summary, all authors, venue, citation context, and PDF link. \
+                          TLDR summary, all authors, venue, DOI, publication types (e.g. \
+                          MetaAnalysis, ClinicalTrial), citation context, and PDF link. \
                           Use this on the most relevant papers from search_papers to extract \
                           therapeutic techniques, outcome measures, and evidence level before \
                           writing your final report."
@@ -134,7 +156,7 @@ impl Tool for GetPaperDetail {
                 "properties": {
                     "paper_id": {
                         "type": "string",
-                        "description": "Paper ID from search results (S2PaperId), or arXiv:xxxx, DOI:xxx/yyy, PMID:xxx"
+                        "description": "Paper ID from search results (S2PaperId), or arXiv:xxxx, DOI:xxx/yyy (a bare DOI or doi.org link also works), PMID:xxx"
                     }
                 },
                 "required": ["paper_id"]
@@ -144,7 +166,12 @@ impl Tool for GetPaperDetail {
 
     async fn call_json(&self, args: serde_json::Value) -> anyhow::Result<String> {
         let args: PaperDetailArgs = serde_json::from_value(args)?;
-        let p = self.0.get_paper(&args.paper_id, PAPER_FIELDS_FULL).await?;
+        let paper_id: PaperId = args.paper_id.parse()?;
+        let p = self
+            .0
+            .get_paper(&paper_id, &FieldSet::paper_full(Endpoint::PaperDetail)?)
+            .await
+            .map_err(explain)?;
         Ok(serde_json::to_string_pretty(&serde_json::json!({
             "paper_id": p.paper_id,
             "title": p.title,
@@ -157,6 +184,11 @@ impl Tool for GetPaperDetail {
                 a.iter().filter_map(|au| au.name.as_deref()).collect::<Vec<_>>()
             }),
             "venue": p.venue,
+            "journal": p.journal.as_ref().and_then(|j| j.name.as_deref()),
+            "doi": p.doi(),
+            "pmid": p.external_ids.as_ref().and_then(|x| x.pubmed.as_deref()),
+            "publication_types": p.publication_types,
+            "reference_count": p.reference_count,
             "publication_date": p.publication_date,
             "is_open_access": p.is_open_access,
             "pdf_url": p.open_access_pdf.as_ref().and_then(|x| x.url.as_deref()),
@@ -165,3 +197,31 @@ impl Tool for GetPaperDetail {
         }))?)
     }
 }
+
+/// Turn a client error into a message the model can act on. The original error stays
+/// attached as the source for logs.
+fn explain(err: semantic_scholar::Error) -> anyhow::Error {
+    use semantic_scholar::Error as E;
+    let msg = match &err {
+        E::NotFound { message, .. } => format!(
+            "Not found on Semantic Scholar ({message}). Check the ID, or find the paper \
+             with search_papers first."
+        ),
+        E::BadRequest { message, .. } => format!(
+            "Semantic Scholar rejected the request: {message}. Fix the arguments and try again."
+        ),
+        E::RateLimited { retry_after, .. } => format!(
+            "Semantic Scholar is rate limiting requests (retry after {retry_after}s). Work with \
+             the papers already found or try again later."
+        ),
+        E::Unauthorized { .. } => "Semantic Scholar refused the API key, so this tool is \
+                                   unavailable. Work with the papers already found."
+            .into(),
+        E::ServerError { status, .. } => format!(
+            "Semantic Scholar is temporarily unavailable (HTTP {status}). Try again later or \
+             work with the papers already found."
+        ),
+        _ => return err.into(),
+    };
+    anyhow::Error::new(err).context(msg)
+}
@@ -494,3 +494,60 @@ async fn no_tools_field_in_request_when_none_registered() {
         "tools key should be absent when no tools registered"
     );
 }
+
+#[tokio::test]
+async fn research_tools_run_end_to_end_against_mock_scholar() {
+    use research_agent::tools::{GetPaperDetail, SearchPapers};
+    use semantic_scholar::{ExternalIds, Paper};
+    use semantic_scholar_mock::{Corpus, Failure, MockScholar, Route};
+
+    let scholar = MockScholar::start(Corpus::new().paper(Paper {
+        paper_id: Some("rct".into()),
+        title: Some("TF-CBT randomized trial in children".into()),
+        year: Some(2012),
+        citation_count: Some(300),
+        external_ids: Some(ExternalIds {
+            doi: Some("10.1016/j.jaac.2011.12.001".into()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }))
+    .await;
+    // The first search is rate-limited; the client must retry it transparently.
+    scholar.fail_next(Failure::rate_limited());
+
+    let mock = start_mock(vec![
+        tool_call_response(vec![call("c1", "search_papers", r#"{"query":"TF-CBT children"}"#)]),
+        tool_call_response(vec![call(
+            "c2",
+            "get_paper_detail",
+            r#"{"paper_id":"https://doi.org/10.1016/j.jaac.2011.12.001"}"#,
+        )]),
+        stop_response("TF-CBT is supported by an RCT."),
+    ])
+    .await;
+
+    let result = Client::new("sk-test")
+        .agent("deepseek-chat")
+        .base_url(&mock.base_url)
+        .tool(SearchPapers(scholar.client()))
+        .tool(GetPaperDetail(scholar.client()))
+        .build()
+        .prompt("Evidence for TF-CBT?".into())
+        .await
+        .unwrap();
+    assert_eq!(result, "TF-CBT is supported by an RCT.");
+
+    let caps = mock.captures.lock().unwrap();
+    let messages = caps[2].body["messages"].as_array().unwrap();
+    let tool_msgs: Vec<Value> = messages
+        .iter()
+        .filter(|m| m["role"] == "tool")
+        .map(|m| serde_json::from_str(m["content"].as_str().unwrap()).unwrap())
+        .collect();
+    assert_eq!(tool_msgs[0]["papers"][0]["paper_id"], "rct");
+    assert_eq!(tool_msgs[1]["doi"], "10.1016/j.jaac.2011.12.001");
+
+    assert_eq!(scholar.request_count(Route::BulkSearch), 2);
+    assert_eq!(scholar.request_count(Route::Paper), 1);
+}
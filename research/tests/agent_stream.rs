@@ -0,0 +1,274 @@
+/// Integration tests for `DeepSeekAgent::prompt_stream`.
+///
+/// The mock answers `/v1/chat/completions` with pre-programmed server-sent event
+/// bodies, cut into small chunks so events and UTF-8 characters straddle reads.
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use axum::{
+    Json, Router,
+    body::{Body, Bytes},
+    extract::State,
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::post,
+};
+use futures::{StreamExt, TryStreamExt, stream};
+use research_agent::agent::{AgentEvent, Client, Tool, ToolDefinition};
+use serde_json::{Value, json};
+use tokio::net::TcpListener;
+
+// ─── Mock-server infrastructure ──────────────────────────────────────────────
+
+#[derive(Clone)]
+struct MockState {
+    /// SSE bodies to serve (FIFO); `None` answers 500.
+    bodies: Arc<Mutex<VecDeque<Option<String>>>>,
+    /// Request bodies captured so far.
+    captures: Arc<Mutex<Vec<Value>>>,
+}
+
+async fn completions_handler(State(state): State<MockState>, Json(body): Json<Value>) -> Response {
+    state.captures.lock().unwrap().push(body);
+    match state.bodies.lock().unwrap().pop_front().flatten() {
+        Some(sse) => {
+            let chunks: Vec<Result<Bytes, std::io::Error>> = sse
+                .into_bytes()
+                .chunks(7)
+                .map(|c| Ok(Bytes::copy_from_slice(c)))
+                .collect();
+            (
+                [(header::CONTENT_TYPE, "text/event-stream")],
+                Body::from_stream(stream::iter(chunks)),
+            )
+                .into_response()
+        }
+        None => (StatusCode::INTERNAL_SERVER_ERROR, "mock exhausted").into_response(),
+    }
+}
+
+struct MockServer {
+    base_url: String,
+    captures: Arc<Mutex<Vec<Value>>>,
+}
+
+async fn start_mock(bodies: Vec<Option<String>>) -> MockServer {
+    let state = MockState {
+        bodies: Arc::new(Mutex::new(bodies.into_iter().collect())),
+        captures: Arc::new(Mutex::new(Vec::new())),
+    };
+    let captures = state.captures.clone();
+    let app = Router::new()
+        .route("/v1/chat/completions", post(completions_handler))
+        .with_state(state);
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    MockServer {
+        base_url: format!("http://{addr}"),
+        captures,
+    }
+}
+
+struct EchoTool;
+
+#[async_trait::async_trait]
+impl Tool for EchoTool {
+    fn name(&self) -> &str { "echo" }
+
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "echo".into(),
+            description: "Echoes the input back.".into(),
+            parameters: json!({
+                "type": "object",
+                "properties": { "text": { "type": "string" } },
+                "required": ["text"]
+            }),
+        }
+    }
+
+    async fn call_json(&self, args: Value) -> anyhow::Result<String> {
+        Ok(args["text"].as_str().unwrap_or("").to_string())
+    }
+}
+
+// ─── Helpers ─────────────────────────────────────────────────────────────────
+
+/// An SSE body from `delta`/`finish_reason` pairs, with a keep-alive comment and
+/// the closing `[DONE]`.
+fn sse(chunks: &[(Value, Option<&str>)]) -> String {
+    let mut out = String::from(": keep-alive\n\n");
+    for (delta, finish) in chunks {
+        let chunk = json!({
+            "object": "chat.completion.chunk",
+            "choices": [{ "index": 0, "delta": delta, "finish_reason": finish }]
+        });
+        out.push_str(&format!("data: {chunk}\r\n\r\n"));
+    }
+    out + "data: [DONE]\n\n"
+}
+
+async fn collect(agent: &research_agent::agent::DeepSeekAgent) -> Vec<AgentEvent> {
+    agent
+        .prompt_stream("Research TF-CBT".into())
+        .try_collect()
+        .await
+        .unwrap()
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn text_and_reasoning_arrive_as_deltas() {
+    let mock = start_mock(vec![Some(sse(&[
+        (json!({ "role": "assistant", "reasoning_content": "Weighing évidence" }), None),
+        (json!({ "reasoning_content": "…" }), None),
+        (json!({ "content": "Héllo, " }), None),
+        (json!({ "content": "world" }), None),
+        (json!({}), Some("stop")),
+    ]))])
+    .await;
+    let agent = Client::new("sk-test")
+        .agent("deepseek-reasoner")
+        .base_url(&mock.base_url)
+        .build();
+
+    let events = collect(&agent).await;
+    assert_eq!(
+        events,
+        [
+            AgentEvent::ReasoningDelta("Weighing évidence".into()),
+            AgentEvent::ReasoningDelta("…".into()),
+            AgentEvent::TextDelta("Héllo, ".into()),
+            AgentEvent::TextDelta("world".into()),
+            AgentEvent::Done { content: "Héllo, world".into() },
+        ]
+    );
+    assert_eq!(mock.captures.lock().unwrap()[0]["stream"], true);
+}
+
+#[tokio::test]
+async fn tool_call_deltas_are_reassembled_and_executed() {
+    let tool_turn = sse(&[
+        (
+            json!({ "tool_calls": [{ "index": 0, "id": "c1", "type": "function",
+                                     "function": { "name": "echo", "arguments": "" } }] }),
+            None,
+        ),
+        (json!({ "tool_calls": [{ "index": 0, "function": { "arguments": "{\"te" } }] }), None),
+        (
+            json!({ "tool_calls": [{ "index": 1, "id": "c2", "type": "function",
+                                     "function": { "name": "echo", "arguments": "{\"text\":\"b\"}" } }] }),
+            None,
+        ),
+        (json!({ "tool_calls": [{ "index": 0, "function": { "arguments": "xt\":\"a\"}" } }] }), None),
+        (json!({}), Some("tool_calls")),
+    ]);
+    let answer = sse(&[(json!({ "content": "a and b" }), Some("stop"))]);
+    let mock = start_mock(vec![Some(tool_turn), Some(answer)]).await;
+    let agent = Client::new("sk-test")
+        .agent("deepseek-chat")
+        .base_url(&mock.base_url)
+        .tool(EchoTool)
+        .build();
+
+    let events = collect(&agent).await;
+    let started = |id: &str| AgentEvent::ToolCallStarted { id: id.into(), name: "echo".into() };
+    let finished = |id: &str, args: &str, result: &str| AgentEvent::ToolCallFinished {
+        id: id.into(),
+        name: "echo".into(),
+        arguments: args.into(),
+        result: result.into(),
+    };
+    assert_eq!(
+        events,
+        [
+            started("c1"),
+            started("c2"),
+            finished("c1", r#"{"text":"a"}"#, "a"),
+            finished("c2", r#"{"text":"b"}"#, "b"),
+            AgentEvent::TextDelta("a and b".into()),
+            AgentEvent::Done { content: "a and b".into() },
+        ]
+    );
+
+    // The second request replays the reassembled calls and their results.
+    let captures = mock.captures.lock().unwrap();
+    let messages = captures[1]["messages"].as_array().unwrap();
+    assert_eq!(messages[2]["role"], "assistant");
+    assert_eq!(messages[2]["content"], Value::Null);
+    assert_eq!(messages[2]["tool_calls"][0]["function"]["arguments"], r#"{"text":"a"}"#);
+    assert_eq!(messages[3], json!({ "role": "tool", "tool_call_id": "c1", "content": "a" }));
+    assert_eq!(messages[4]["tool_call_id"], "c2");
+}
+
+#[tokio::test]
+async fn http_error_ends_the_stream_with_an_error() {
+    let mock = start_mock(vec![None]).await;
+    let agent = Client::new("sk-test")
+        .agent("deepseek-chat")
+        .base_url(&mock.base_url)
+        .build();
+
+    let events: Vec<_> = agent.prompt_stream("hi".into()).collect().await;
+    assert_eq!(events.len(), 1);
+    let err = events[0].as_ref().unwrap_err();
+    assert!(format!("{err:#}").contains("500"), "{err:#}");
+}
+
+#[tokio::test]
+async fn body_without_done_marker_still_finishes() {
+    let body = format!(
+        "data: {}",
+        json!({ "choices": [{ "delta": { "content": "cut short" }, "finish_reason": "stop" }] })
+    );
+    let mock = start_mock(vec![Some(body)]).await;
+    let agent = Client::new("sk-test")
+        .agent("deepseek-chat")
+        .base_url(&mock.base_url)
+        .build();
+
+    let events = collect(&agent).await;
+    assert_eq!(events.last(), Some(&AgentEvent::Done { content: "cut short".into() }));
+}
+
+#[tokio::test]
+async fn body_cut_off_mid_completion_is_an_error() {
+    let body = format!("data: {}\n\n", json!({ "choices": [{ "delta": { "content": "cut" } }] }));
+    let mock = start_mock(vec![Some(body)]).await;
+    let agent = Client::new("sk-test")
+        .agent("deepseek-chat")
+        .base_url(&mock.base_url)
+        .build();
+
+    let events: Vec<_> = agent.prompt_stream("hi".into()).collect().await;
+    assert_eq!(events[0].as_ref().unwrap(), &AgentEvent::TextDelta("cut".into()));
+    let err = events.last().unwrap().as_ref().unwrap_err();
+    assert!(format!("{err:#}").contains("ended before"), "{err:#}");
+    assert!(!events.iter().any(|e| matches!(e, Ok(AgentEvent::Done { .. }))));
+}
+
+#[tokio::test]
+async fn tool_call_index_cannot_skip_ahead() {
+    let body = sse(&[(
+        json!({ "tool_calls": [{ "index": 4_000_000_000u64, "id": "c1",
+                                 "function": { "name": "echo", "arguments": "{}" } }] }),
+        Some("tool_calls"),
+    )]);
+    let mock = start_mock(vec![Some(body)]).await;
+    let agent = Client::new("sk-test")
+        .agent("deepseek-chat")
+        .base_url(&mock.base_url)
+        .tool(EchoTool)
+        .build();
+
+    let events: Vec<_> = agent.prompt_stream("hi".into()).collect().await;
+    assert_eq!(events.len(), 1);
+    let err = events[0].as_ref().unwrap_err();
+    assert!(format!("{err:#}").contains("index 4000000000"), "{err:#}");
+}
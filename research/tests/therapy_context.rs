@@ -195,6 +195,11 @@ fn build_agent_prompt_includes_search_queries() {
         .filter(|i| prompt.contains(&format!("  {i}.")))
         .count();
     assert!(count >= 3, "expected ≥3 search queries, found {count}");
+    assert!(
+        prompt.contains("`Anxiety (intervention | therapy | treatment) (child* | adolescen* | youth*)`"),
+        "queries should use bulk search syntax:\n{prompt}"
+    );
+    assert!(prompt.contains(r#"`Anxiety ("meta-analysis" | "systematic review")`"#));
 }
 
 #[test]
@@ -2,16 +2,28 @@
 ///
 /// These tests cover tool names, JSON schema structure, argument
 /// deserialization, and call_json error paths.
-/// No network calls are made — all tests that would hit real APIs
-/// are either testing the error path (bad args → serde fail before I/O)
-/// or are marked `#[ignore]`.
+/// No network calls are made — successful calls replay fixtures from
+/// `tests/fixtures/semantic-scholar-synthetic` and compare against `tests/snapshots`;
+/// anything else tests the error path (bad args → serde fail before I/O) or is
+/// `#[ignore]`d.
+///
+/// The fixtures are synthetic: hand-written in the fixture file format, with made-up
+/// IDs, abstracts and URLs. They pin the tools' output for a given response, not the
+/// real API's response shape. Recording with `SEMANTIC_SCHOLAR_RECORD=1` replaces
+/// them with live responses; rewrite snapshots with `UPDATE_SNAPSHOTS=1`.
 use research_agent::{
     agent::{Tool, ToolDefinition},
     tools::{GetPaperDetail, SearchPapers},
 };
-use semantic_scholar::SemanticScholarClient;
+use semantic_scholar::{fixtures::Fixtures, SemanticScholarClient};
 use serde_json::json;
 
+const FIXTURES: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/semantic-scholar-synthetic"
+);
+const SNAPSHOTS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/snapshots");
+
 fn search_tool() -> SearchPapers {
     SearchPapers(SemanticScholarClient::new(None))
 }
@@ -20,6 +32,26 @@ fn detail_tool() -> GetPaperDetail {
     GetPaperDetail(SemanticScholarClient::new(None))
 }
 
+fn replay_client() -> SemanticScholarClient {
+    SemanticScholarClient::builder()
+        .fixtures(Fixtures::from_env(FIXTURES))
+        .build()
+        .unwrap()
+}
+
+/// Compare `actual` with `tests/snapshots/{name}.json`, or overwrite it when
+/// `UPDATE_SNAPSHOTS=1` is set.
+fn assert_snapshot(name: &str, actual: &str) {
+    let path = std::path::Path::new(SNAPSHOTS).join(format!("{name}.json"));
+    if std::env::var("UPDATE_SNAPSHOTS").as_deref() == Ok("1") {
+        std::fs::write(&path, format!("{actual}\n")).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("{}: {e}; run with UPDATE_SNAPSHOTS=1", path.display()));
+    assert_eq!(actual, expected.trim_end(), "snapshot {name} changed");
+}
+
 // ─── Names ───────────────────────────────────────────────────────────────────
 
 #[test]
@@ -226,6 +258,64 @@ fn paper_detail_args_deserialize() {
     assert_eq!(a.paper_id, "arXiv:2305.12345");
 }
 
+// ─── Fixture replay snapshots ────────────────────────────────────────────────
+
+#[tokio::test]
+async fn search_papers_call_json_snapshot() {
+    let out = SearchPapers(replay_client())
+        .call_json(json!({ "query": "trauma-focused CBT children", "year": "2015-", "limit": 2 }))
+        .await
+        .unwrap();
+    assert_snapshot("search_papers", &out);
+}
+
+#[tokio::test]
+async fn get_paper_detail_call_json_snapshot() {
+    let out = GetPaperDetail(replay_client())
+        .call_json(json!({ "paper_id": "https://doi.org/10.1016/j.cpr.2019.101752" }))
+        .await
+        .unwrap();
+    assert_snapshot("get_paper_detail", &out);
+}
+
+#[tokio::test]
+async fn unrecorded_call_fails_instead_of_hitting_network() {
+    let err = GetPaperDetail(replay_client())
+        .call_json(json!({ "paper_id": "PMID:1" }))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("no fixture"), "unexpected: {err}");
+}
+
+#[tokio::test]
+async fn api_errors_become_actionable_messages() {
+    use semantic_scholar_mock::{Corpus, Failure, MockScholar};
+
+    let mock = MockScholar::start(Corpus::new()).await;
+    let err = GetPaperDetail(mock.client())
+        .call_json(json!({ "paper_id": "DOI:10.1/missing" }))
+        .await
+        .unwrap_err();
+    let msg = err.to_string();
+    assert!(msg.starts_with("Not found on Semantic Scholar"), "{msg}");
+    assert!(msg.contains("DOI:10.1/missing not found"), "{msg}");
+    assert!(msg.contains("search_papers"), "{msg}");
+    // The client error, with its request context, stays in the chain.
+    let source = err.root_cause().to_string();
+    assert!(source.starts_with("GET /graph/v1/paper/DOI:10.1"), "{source}");
+
+    mock.fail_next(Failure::status(400, "Unrecognized or unsupported fields: [x]"));
+    let err = SearchPapers(mock.client())
+        .call_json(json!({ "query": "CBT" }))
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .starts_with("Semantic Scholar rejected the request: Unrecognized"),
+        "{err}"
+    );
+}
+
 // ─── Live API smoke tests (skipped in CI) ─────────────────────────────────────
 
 /// Verify `search_papers` actually returns results from Semantic Scholar.